@@ -40,6 +40,30 @@ pub use always_context_build;
 /// - **`#[proc_macro]` and `#[proc_macro_derive]`**: Returns `compile_error!` with the error message
 /// - **`#[proc_macro_attribute]`**: Returns `compile_error!` followed by the original input item
 ///
+/// # Keeping the Item
+///
+/// Attribute macros which only add code next to the item (like an `impl` block) can use
+/// `#[anyhow_result(keep_item)]`. On success, the original input item is returned before
+/// the generated tokens, so the function returns only the new code:
+///
+/// ```rust,ignore
+/// #[proc_macro_attribute]
+/// #[anyhow_result(keep_item)]
+/// pub fn with_type_name(_attr: TokenStream, item: TokenStream) -> anyhow::Result<TokenStream> {
+///     let parsed: syn::ItemStruct = syn::parse(item)?;
+///     let name = &parsed.ident;
+///     let name_str = name.to_string();
+///     // `parsed` struct itself is kept, no need to return it
+///     Ok(quote! { impl #name { pub fn type_name() -> &'static str { #name_str } } }.into())
+/// }
+/// ```
+///
+/// `keep_item` is a compile error for `#[proc_macro]` and `#[proc_macro_derive]`.
+///
+/// Set `EASY_MACROS_DEBUG=1` during compilation (`EASY_MACROS_DEBUG=1 cargo build`) to also print
+/// the whole error chain with `context!` locations to stderr. `0` or an empty value disables it,
+/// successful expansions are never affected.
+///
 /// # See Also
 ///
 /// - [`anyhow`](https://docs.rs/anyhow/) - Error handling library
@@ -47,9 +71,31 @@ pub use always_context_build;
 /// - [`quote`](https://docs.rs/quote/) - Code generation
 pub use anyhow_result::anyhow_result;
 
+#[cfg(feature = "anyhow-result")]
+/// Wraps `fn main() -> anyhow::Result<()>` of a binary.
+///
+/// On error the full `{:?}` chain (including `context!` locations) is printed to stderr
+/// and the process exits with code 1. A different code can be set with `#[anyhow_main(exit_code = 2)]`,
+/// which a plain `fn main() -> anyhow::Result<()>` can't do (it always exits with 1).
+///
+/// # Usage
+///
+/// ```rust,ignore
+/// #[anyhow_main]
+/// fn main() -> anyhow::Result<()> {
+///     let config = std::fs::read_to_string("config.toml")
+///         .with_context(context!("Reading configuration"))?;
+///     Ok(())
+/// }
+/// ```
+pub use anyhow_result::anyhow_main;
+
 #[cfg(feature = "attributes")]
 pub use attributes::{
-    AttrWithUnknown, fields_get_attributes, fields_with_attributes, get_attributes, has_attributes,
+    AttrWithUnknown, count_attributes, fields_get_attributes, fields_get_attributes_full,
+    fields_partition_attributes, fields_with_attributes, get_attributes, get_attributes_from,
+    get_attributes_full, get_attributes_many, get_inner_attributes, has_attribute_path,
+    has_attributes, has_attributes_from, has_inner_attributes,
 };
 
 // === Helper Function Exports ===
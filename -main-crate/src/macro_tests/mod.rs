@@ -1,5 +1,8 @@
 use all_syntax_cases::all_syntax_cases;
-use proc_macro_tests::{DeriveTestStruct, macro_test_eq};
+use proc_macro_tests::{
+    DeriveTestStruct, expr_warning_test, keep_item_test, macro_test_eq, parse_items_errors_test,
+    parse_items_test, parse_rest_test, parse_stmts_test,
+};
 use quote::ToTokens;
 
 #[macro_test_eq]
@@ -40,3 +43,77 @@ all_syntax_cases! {
 #[sql(table = =)]
 #[sql(table = 25)]
 struct _AttributeTest {}
+
+//`parse_macro_input!(item as [syn::Item])`
+parse_items_test! {
+    struct _ParsedStruct;
+    fn _parsed_fn() -> i32 { 1 }
+    const _PARSED_CONST: i32 = 2;
+}
+
+//`parse_macro_input!(item as [syn::Item]; combine_errors)`, both invalid items are reported
+#[test]
+fn parse_macro_input_combine_errors() {
+    let errors = parse_items_errors_test! {
+        struct First { a: u8 b: u8 }
+        fn valid() {}
+        const SECOND: = 5;
+        fn also_valid() {}
+    };
+    assert_eq!(errors.matches("compile_error").count(), 2, "{errors}");
+    assert!(errors.contains("expected `,`"), "{errors}");
+    assert!(errors.contains("expected one of"), "{errors}");
+
+    let errors = parse_items_errors_test! {
+        struct Valid;
+    };
+    assert!(errors.is_empty());
+}
+
+//`parse_macro_input!(item as [syn::Stmt])`, last statement without `;`
+#[test]
+fn parse_macro_input_stmts() {
+    let result = parse_stmts_test! {
+        let a = 1;
+        let b = a + _PARSED_CONST;
+        a + b
+    };
+    assert_eq!(result, 4);
+}
+
+//`parse_macro_input!(item as syn::Ident, rest)`, tokens after the ident are returned unparsed
+#[test]
+fn parse_macro_input_rest() {
+    let (name, value) = parse_rest_test!(total 1 + 2 * 3);
+    assert_eq!(name, "total");
+    assert_eq!(value, 7);
+
+    let (name, ()) = parse_rest_test!(empty());
+    assert_eq!(name, "empty");
+}
+
+//`expr_warning_wrap` on stable, deprecation warning is expected here
+#[test]
+#[allow(deprecated)]
+fn expr_warning_wrap_keeps_value() {
+    let value = expr_warning_test!(vec![1, 2, 3].len() * 2);
+    assert_eq!(value, 6);
+
+    const IN_CONST: i32 = expr_warning_test!(40 + 2);
+    assert_eq!(IN_CONST, 42);
+}
+
+#[keep_item_test]
+#[derive(Debug, PartialEq)]
+struct KeptItem {
+    value: u8,
+}
+
+//`#[anyhow_result(keep_item)]`, both the original struct and the generated impl are present
+#[test]
+fn anyhow_result_keep_item() {
+    let kept = KeptItem { value: 3 };
+    assert_eq!(kept, KeptItem { value: 3 });
+    assert_eq!(kept.value, 3);
+    assert_eq!(KeptItem::type_name(), "KeptItem");
+}
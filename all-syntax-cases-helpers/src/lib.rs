@@ -4,6 +4,15 @@ mod struct_check;
 
 use proc_macro::TokenStream;
 
+///Path without spaces, for example `syn::Expr::Try`
+fn path_to_string(path: &syn::Path) -> String {
+    path.segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
 #[proc_macro]
 ///Macro used by all_syntax_cases
 ///
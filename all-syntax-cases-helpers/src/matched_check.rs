@@ -62,9 +62,13 @@ pub fn matched_check(item: TokenStream) -> TokenStream {
 
     let struct_call_name = quote::format_ident!("a");
 
+    //Shown in coverage summary
+    let matched_in = crate::path_to_string(&match_path);
+
     // Supports only one match argument for now
     let result = quote! {
         {
+            let matched_in = #matched_in;
             //check if fields are valid
             let _=||{
                 let _= #struct_path {
@@ -86,11 +90,11 @@ pub fn matched_check(item: TokenStream) -> TokenStream {
             let mut special_call = None;
             //Find matching special function, if any
             for func in special_functions.iter_mut(){
-                if let Some(call) = func.all_inputs_check(&fields1, None, (additional_input_name, additional_input_ty)){
+                if let Some(call) = func.all_inputs_check_in(&fields1, None, (additional_input_name, additional_input_ty), matched_in){
                     special_call = Some(call);
                     break;
                 }
-                if let Some(call) = func.all_inputs_check(&fields2, Some(&struct_call), (additional_input_name, additional_input_ty)){
+                if let Some(call) = func.all_inputs_check_in(&fields2, Some(&struct_call), (additional_input_name, additional_input_ty), matched_in){
                     special_call = Some(call);
                     break;
                 }
@@ -113,28 +117,28 @@ pub fn matched_check(item: TokenStream) -> TokenStream {
                 let mut default_calls= Vec::new();
                 //Functions provided by user
                 for func in default_functions.iter_mut(){
-                    if let Some(call) = func.all_inputs_check(&fields1, None, (additional_input_name, additional_input_ty)){
+                    if let Some(call) = func.all_inputs_check_in(&fields1, None, (additional_input_name, additional_input_ty), matched_in){
                         default_calls.push(call);
                     }
-                    if let Some(call) = func.all_inputs_check(&fields2, Some(&struct_call), (additional_input_name, additional_input_ty)){
+                    if let Some(call) = func.all_inputs_check_in(&fields2, Some(&struct_call), (additional_input_name, additional_input_ty), matched_in){
                         default_calls.push(call);
                     }
                 }
                 //Functions used by the macro, for example for search
                 for func in system_functions.iter_mut(){
-                    if let Some(call) = func.all_inputs_check(&fields1, None, (additional_input_name, additional_input_ty)){
+                    if let Some(call) = func.all_inputs_check_in(&fields1, None, (additional_input_name, additional_input_ty), matched_in){
                         default_calls.push(call);
                     }
-                    if let Some(call) = func.all_inputs_check(&fields2, Some(&struct_call), (additional_input_name, additional_input_ty)){
+                    if let Some(call) = func.all_inputs_check_in(&fields2, Some(&struct_call), (additional_input_name, additional_input_ty), matched_in){
                         default_calls.push(call);
                     }
                 }
                 //Functions provided by user with #[after_system]
                 for func in default_functions_after_system.iter_mut(){
-                    if let Some(call) = func.all_inputs_check(&fields1, None, (additional_input_name, additional_input_ty)){
+                    if let Some(call) = func.all_inputs_check_in(&fields1, None, (additional_input_name, additional_input_ty), matched_in){
                         default_calls.push(call);
                     }
-                    if let Some(call) = func.all_inputs_check(&fields2, Some(&struct_call), (additional_input_name, additional_input_ty)){
+                    if let Some(call) = func.all_inputs_check_in(&fields2, Some(&struct_call), (additional_input_name, additional_input_ty), matched_in){
                         default_calls.push(call);
                     }
                 }
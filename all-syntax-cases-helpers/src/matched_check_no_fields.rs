@@ -40,8 +40,12 @@ pub fn matched_check_no_fields(item: TokenStream) -> TokenStream {
 
     let call_structs = struct_paths.iter();
 
+    //Shown in coverage summary
+    let matched_in = crate::path_to_string(&match_path);
+
     let result = quote! {
         {
+            let matched_in = #matched_in;
 
             //Handle fields from macro input (only structs)
             let fields1: Vec<syn::Field> = vec![#(syn::parse_quote!{
@@ -51,7 +55,7 @@ pub fn matched_check_no_fields(item: TokenStream) -> TokenStream {
             let mut special_call = None;
             //Find matching special function, if any
             for func in special_functions.iter_mut(){
-                if let Some(call) = func.all_inputs_check(&fields1, None, (additional_input_name, additional_input_ty)){
+                if let Some(call) = func.all_inputs_check_in(&fields1, None, (additional_input_name, additional_input_ty), matched_in){
                     special_call = Some(call);
                     break;
                 }
@@ -72,19 +76,19 @@ pub fn matched_check_no_fields(item: TokenStream) -> TokenStream {
                 let mut default_calls = Vec::new();
                 //Functions provided by user
                 for func in default_functions.iter_mut(){
-                    if let Some(call) = func.all_inputs_check(&fields1, None, (additional_input_name, additional_input_ty)){
+                    if let Some(call) = func.all_inputs_check_in(&fields1, None, (additional_input_name, additional_input_ty), matched_in){
                         default_calls.push(call);
                     }
                 }
                 //Functions used by the macro, for example for search
                 for func in system_functions.iter_mut(){
-                    if let Some(call) = func.all_inputs_check(&fields1, None, (additional_input_name, additional_input_ty)){
+                    if let Some(call) = func.all_inputs_check_in(&fields1, None, (additional_input_name, additional_input_ty), matched_in){
                         default_calls.push(call);
                     }
                 }
                 //Functions provided by user with #[after_system]
                 for func in default_functions_after_system.iter_mut(){
-                    if let Some(call) = func.all_inputs_check(&fields1, None, (additional_input_name, additional_input_ty)){
+                    if let Some(call) = func.all_inputs_check_in(&fields1, None, (additional_input_name, additional_input_ty), matched_in){
                         default_calls.push(call);
                     }
                 }
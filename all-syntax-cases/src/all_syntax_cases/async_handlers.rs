@@ -0,0 +1,66 @@
+use std::collections::HashSet;
+
+use quote::ToTokens;
+use syn::visit_mut::VisitMut;
+
+///Makes every generated function `async`, calls of generated functions and handlers are awaited
+///
+///Every call is boxed (`Box::pin(call).await`), generated functions and handlers call each other recursively,
+/// which isn't allowed for `async fn` without indirection
+pub fn async_handlers(
+    search_result: proc_macro2::TokenStream,
+    handlers: &[&syn::Ident],
+) -> proc_macro2::TokenStream {
+    let mut generated: syn::File = match syn::parse2(search_result) {
+        Ok(generated) => generated,
+        Err(e) => return e.to_compile_error(),
+    };
+
+    let mut await_calls = AwaitCalls {
+        called: generated
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                syn::Item::Fn(item_fn) => Some(item_fn.sig.ident.clone()),
+                _ => None,
+            })
+            .chain(handlers.iter().map(|handler| (*handler).clone()))
+            .collect(),
+    };
+
+    for item in generated.items.iter_mut() {
+        if let syn::Item::Fn(item_fn) = item {
+            item_fn.sig.asyncness = Some(Default::default());
+            await_calls.visit_block_mut(&mut item_fn.block);
+        }
+    }
+
+    generated.into_token_stream()
+}
+
+///Replaces `f(...)` with `Box::pin(f(...)).await` for every function in `called`
+struct AwaitCalls {
+    ///Generated functions and handlers, compared by the last path segment (handlers can be `<T>::handler`)
+    called: HashSet<syn::Ident>,
+}
+
+impl VisitMut for AwaitCalls {
+    fn visit_expr_mut(&mut self, expr: &mut syn::Expr) {
+        syn::visit_mut::visit_expr_mut(self, expr);
+
+        let syn::Expr::Call(call) = expr else {
+            return;
+        };
+        let syn::Expr::Path(path) = &*call.func else {
+            return;
+        };
+        if path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| self.called.contains(&segment.ident))
+        {
+            *expr = syn::parse_quote! { ::std::boxed::Box::pin(#call).await };
+        }
+    }
+}
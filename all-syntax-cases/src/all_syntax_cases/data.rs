@@ -4,11 +4,36 @@ use std::collections::HashMap;
 use quote::{ToTokens, quote};
 use syn::{Signature, Token, TypeReference, punctuated::Punctuated};
 
+use super::enclosing::{enclosing_ident_arg, is_enclosing_ident};
+use super::phase::{handler_phase_arg, is_handler_phase};
+
 pub struct InputSetup {
     generated_fn_prefix: String,
     additional_input_type: syn::Type,
     ///False by default
     system_functions_test: bool,
+    ///False by default
+    report_coverage: bool,
+    ///False by default
+    expose_handler_list: bool,
+    ///Name of the generated `syn::visit_mut::VisitMut` implementor, none by default
+    emit_visit_mut: Option<syn::Ident>,
+    ///No limit by default
+    max_depth: Option<usize>,
+    ///Called with `max_depth` when the limit is reached, only allowed with `max_depth`
+    on_depth_exceeded: Option<syn::Path>,
+    ///Type implementing the handlers trait, handlers are called as `<T>::handler(...)`, free functions by default
+    handler_trait: Option<syn::Type>,
+    ///Trait implemented by `handler_trait`, handlers are taken from its methods (emitted without handler markers)
+    handler_trait_def: Option<syn::ItemTrait>,
+    ///False by default
+    async_handlers: bool,
+    ///False by default
+    inline_system_fns: bool,
+    ///Generated functions become methods of this type, handlers are called as `self.handler(...)`, free functions by default
+    impl_on: Option<syn::Type>,
+    ///`additional_input_type` wasn't provided (only allowed with `impl_on`), the parameter is removed from generated methods
+    additional_input_omitted: bool,
 }
 
 impl syn::parse::Parse for InputSetup {
@@ -16,6 +41,17 @@ impl syn::parse::Parse for InputSetup {
         let mut generated_fn_prefix = None;
         let mut additional_input_type = None;
         let mut system_functions_test = false;
+        let mut report_coverage = false;
+        let mut expose_handler_list = false;
+        let mut shared_additional_input = false;
+        let mut emit_visit_mut = None;
+        let mut max_depth = None;
+        let mut on_depth_exceeded = None;
+        let mut handler_trait = None;
+        let mut handler_trait_def = None;
+        let mut async_handlers = false;
+        let mut inline_system_fns = false;
+        let mut impl_on = None;
 
         while !input.is_empty() {
             if input.peek(Token![,]) {
@@ -43,6 +79,50 @@ impl syn::parse::Parse for InputSetup {
                             let lit_bool: syn::LitBool = input.parse()?;
                             system_functions_test = lit_bool.value();
                         }
+                        "report_coverage" => {
+                            let lit_bool: syn::LitBool = input.parse()?;
+                            report_coverage = lit_bool.value();
+                        }
+                        "expose_handler_list" => {
+                            let lit_bool: syn::LitBool = input.parse()?;
+                            expose_handler_list = lit_bool.value();
+                        }
+                        "shared_additional_input" => {
+                            let lit_bool: syn::LitBool = input.parse()?;
+                            shared_additional_input = lit_bool.value();
+                        }
+                        "emit_visit_mut" => {
+                            let struct_name: syn::Ident = input.parse()?;
+                            emit_visit_mut = Some(struct_name);
+                        }
+                        "max_depth" => {
+                            let lit_int: syn::LitInt = input.parse()?;
+                            max_depth = Some(lit_int.base10_parse()?);
+                        }
+                        "on_depth_exceeded" => {
+                            let fn_path: syn::Path = input.parse()?;
+                            on_depth_exceeded = Some(fn_path);
+                        }
+                        "handler_trait" => {
+                            let ty: syn::Type = input.parse()?;
+                            handler_trait = Some(ty);
+                        }
+                        "handler_trait_def" => {
+                            let item_trait: syn::ItemTrait = input.parse()?;
+                            handler_trait_def = Some(item_trait);
+                        }
+                        "async_handlers" => {
+                            let lit_bool: syn::LitBool = input.parse()?;
+                            async_handlers = lit_bool.value();
+                        }
+                        "inline_system_fns" => {
+                            let lit_bool: syn::LitBool = input.parse()?;
+                            inline_system_fns = lit_bool.value();
+                        }
+                        "impl_on" => {
+                            let ty: syn::Type = input.parse()?;
+                            impl_on = Some(ty);
+                        }
                         _ => {
                             panic!("Unknown member in setup: {}", ident_str);
                         }
@@ -52,12 +132,63 @@ impl syn::parse::Parse for InputSetup {
             }
         }
 
+        if on_depth_exceeded.is_some() && max_depth.is_none() {
+            panic!("on_depth_exceeded requires max_depth to be set inside of setup => {{...}}");
+        }
+
+        if handler_trait_def.is_some() && handler_trait.is_none() {
+            panic!(
+                "handler_trait_def requires handler_trait (type implementing the trait) to be set inside of setup => {{...}}"
+            );
+        }
+
+        if async_handlers && emit_visit_mut.is_some() {
+            panic!(
+                "emit_visit_mut can't be used together with async_handlers (syn::visit_mut::VisitMut methods are not async)"
+            );
+        }
+
+        if impl_on.is_some() {
+            if handler_trait.is_some() {
+                panic!(
+                    "impl_on can't be used together with handler_trait (handlers are already methods of the impl_on type)"
+                );
+            }
+            if emit_visit_mut.is_some() {
+                panic!(
+                    "emit_visit_mut can't be used together with impl_on (generated methods need an instance of the impl_on type)"
+                );
+            }
+        }
+
+        //State lives in `self`, additional input is optional
+        let additional_input_omitted = additional_input_type.is_none() && impl_on.is_some();
+        let mut additional_input_type = match additional_input_type {
+            Some(ty) => ty,
+            None if additional_input_omitted => syn::parse_quote! { () },
+            None => panic!("additional_input_type was not provided inside of setup => {{...}}"),
+        };
+        //One instance for the whole traversal, passed everywhere as `&mut` (no `.clone()`)
+        if shared_additional_input {
+            additional_input_type = syn::parse_quote! { &mut #additional_input_type };
+        }
+
         Ok(InputSetup {
             generated_fn_prefix: generated_fn_prefix
                 .expect("generated_fn_prefix was not provided inside of setup => {...}"),
-            additional_input_type: additional_input_type
-                .expect("additional_input_type was not provided inside of setup => {...}"),
+            additional_input_type,
             system_functions_test,
+            report_coverage,
+            expose_handler_list,
+            emit_visit_mut,
+            max_depth,
+            on_depth_exceeded,
+            handler_trait,
+            handler_trait_def,
+            async_handlers,
+            inline_system_fns,
+            impl_on,
+            additional_input_omitted,
         })
     }
 }
@@ -76,13 +207,45 @@ impl syn::parse::Parse for AttrsSignature {
 }
 
 impl AttrsSignature {
-    fn after_system(&self) -> bool {
-        for attr in self.attrs.iter() {
-            if attr.path().is_ident("after_system") {
-                return true;
+    ///Checks `#[both_phases]` and `{Prefix}HandlerPhase` parameter usage, `fn_prefix` is `generated_fn_prefix`
+    fn check_phases(&self, fn_prefix: &str) -> syn::Result<()> {
+        let has_phase_param = self.sig.inputs.iter().any(|input| {
+            matches!(input, syn::FnArg::Typed(pat_type) if is_handler_phase(&pat_type.ty, fn_prefix))
+        });
+        if self.both_phases() {
+            if self.after_system() {
+                return Err(syn::Error::new_spanned(
+                    &self.sig,
+                    "all_syntax_cases: #[both_phases] already includes #[after_system], use only one of them",
+                ));
             }
+            if !has_phase_param {
+                return Err(syn::Error::new_spanned(
+                    &self.sig,
+                    "all_syntax_cases: #[both_phases] handler requires a `{Prefix}HandlerPhase` parameter",
+                ));
+            }
+        } else if has_phase_param {
+            return Err(syn::Error::new_spanned(
+                &self.sig,
+                "all_syntax_cases: `{Prefix}HandlerPhase` parameter is only allowed in #[both_phases] handlers",
+            ));
         }
-        false
+
+        Ok(())
+    }
+
+    fn after_system(&self) -> bool {
+        self.has_attr("after_system")
+    }
+
+    ///Called both before and after child traversal, with `{Prefix}HandlerPhase` telling which one it is
+    fn both_phases(&self) -> bool {
+        self.has_attr("both_phases")
+    }
+
+    fn has_attr(&self, name: &str) -> bool {
+        self.attrs.iter().any(|attr| attr.path().is_ident(name))
     }
 }
 
@@ -121,13 +284,57 @@ impl syn::parse::Parse for Input {
             }
         }
 
-        let setup = setup.expect("setup was not provided! Usage: setup => { <generated_fn_prefix, additional_input_type> }");
-        let default_cases = default_cases.expect(
-            "default_cases was not provided! Usage: default_cases => { <function signatures> }",
-        );
-        let special_cases = special_cases.expect(
-            "special_cases was not provided! Usage: special_cases => { <function signatures> }",
-        );
+        let mut setup = setup.expect("setup was not provided! Usage: setup => { <generated_fn_prefix, additional_input_type> }");
+
+        //Handlers listed in the trait definition, arms are optional then
+        let mut default_cases: Punctuated<AttrsSignature, Token![;]> = match default_cases {
+            Some(default_cases) => default_cases,
+            None if setup.handler_trait_def.is_some() => Punctuated::new(),
+            None => panic!(
+                "default_cases was not provided! Usage: default_cases => {{ <function signatures> }}"
+            ),
+        };
+        let mut special_cases: Punctuated<Signature, Token![;]> = match special_cases {
+            Some(special_cases) => special_cases,
+            None if setup.handler_trait_def.is_some() => Punctuated::new(),
+            None => panic!(
+                "special_cases was not provided! Usage: special_cases => {{ <function signatures> }}"
+            ),
+        };
+
+        if let Some(handler_trait_def) = &mut setup.handler_trait_def {
+            for item in handler_trait_def.items.iter_mut() {
+                let syn::TraitItem::Fn(method) = item else {
+                    continue;
+                };
+                //Markers aren't real attributes, removed from the emitted trait
+                let (markers, attrs): (Vec<_>, Vec<_>) = std::mem::take(&mut method.attrs)
+                    .into_iter()
+                    .partition(|attr| {
+                        ["special_case", "after_system", "both_phases"]
+                            .iter()
+                            .any(|marker| attr.path().is_ident(marker))
+                    });
+                method.attrs = attrs;
+
+                if markers
+                    .iter()
+                    .any(|attr| attr.path().is_ident("special_case"))
+                {
+                    special_cases.push(method.sig.clone());
+                } else {
+                    default_cases.push(AttrsSignature {
+                        attrs: markers,
+                        sig: method.sig.clone(),
+                    });
+                }
+            }
+        }
+
+        //Prefix is known only after parsing everything
+        for sig in default_cases.iter() {
+            sig.check_phases(&setup.generated_fn_prefix)?;
+        }
 
         Ok(Input {
             setup,
@@ -137,6 +344,53 @@ impl syn::parse::Parse for Input {
     }
 }
 
+impl Input {
+    pub fn report_coverage(&self) -> bool {
+        self.setup.report_coverage
+    }
+
+    pub fn expose_handler_list(&self) -> bool {
+        self.setup.expose_handler_list
+    }
+
+    pub fn emit_visit_mut(&self) -> Option<syn::Ident> {
+        self.setup.emit_visit_mut.clone()
+    }
+
+    ///`(max_depth, on_depth_exceeded)`
+    pub fn depth_limit(&self) -> Option<(usize, Option<syn::Path>)> {
+        self.setup
+            .max_depth
+            .map(|max_depth| (max_depth, self.setup.on_depth_exceeded.clone()))
+    }
+
+    ///Trait definition from `handler_trait_def`, handler markers are already removed
+    pub fn handler_trait_def(&self) -> Option<&syn::ItemTrait> {
+        self.setup.handler_trait_def.as_ref()
+    }
+
+    pub fn async_handlers(&self) -> bool {
+        self.setup.async_handlers
+    }
+
+    pub fn inline_system_fns(&self) -> bool {
+        self.setup.inline_system_fns
+    }
+
+    ///`(impl_on type, additional input omitted)`
+    pub fn impl_on(&self) -> Option<(syn::Type, bool)> {
+        self.setup
+            .impl_on
+            .clone()
+            .map(|ty| (ty, self.setup.additional_input_omitted))
+    }
+
+    pub fn fn_prefix(&self) -> &str {
+        &self.setup.generated_fn_prefix
+    }
+}
+
+#[derive(Clone, Copy)]
 pub enum ReferenceType {
     Mutable,
     Immutable,
@@ -156,6 +410,24 @@ pub struct EssentialFnData {
     ident: syn::Ident,
     ///Used for showing errors (if false)
     used_at_least_once: bool,
+    ///Additional input type was found more than once in the signature
+    additional_input_repeats: bool,
+    ///Function signature, used as error span
+    sig_tokens: proc_macro2::TokenStream,
+    ///Set for handlers called through `handler_trait`
+    handler_type: Option<syn::Type>,
+    ///Syn types (variants) where this function was matched, used by `report_coverage`
+    matched_in: Vec<String>,
+    ///`Option<T>` fields are iterated (zero or one call) in 2-param mode
+    ///
+    ///Disabled for system functions, they have dedicated `*_option_*` functions
+    iterate_options: bool,
+    ///Position and type of `{Prefix}EnclosingIdent` parameter, not a part of `input_types` (not matched with fields)
+    enclosing_ident: Option<(usize, syn::Type)>,
+    ///Position and type of `{Prefix}HandlerPhase` parameter (`#[both_phases]` handlers), not matched with fields either
+    handler_phase: Option<(usize, syn::Type)>,
+    ///Handler is called after child traversal, decides the `{Prefix}HandlerPhase` argument
+    after_system: bool,
 }
 
 fn type_equals_path_check(path1: &syn::Path, path2: &syn::Path) -> bool {
@@ -264,6 +536,10 @@ fn type_equals(ty1: &syn::Type, ty2: &syn::Type) -> bool {
             }
             type_equals_path_check(&type_macro.mac.path, &type_macro2.mac.path)
         }
+        (syn::Type::Macro(type_macro), syn::Type::Path(type_path))
+        | (syn::Type::Path(type_path), syn::Type::Macro(type_macro)) => {
+            keyword_token_equals_path(type_macro, type_path)
+        }
         (syn::Type::Never(_), syn::Type::Never(_)) => {
             panic!(
                 "all_syntax_cases Macro: Never type comparison not supported (shouldn't fail anyway)"
@@ -323,6 +599,49 @@ fn type_equals(ty1: &syn::Type, ty2: &syn::Type) -> bool {
     }
 }
 
+///`Token![unsafe]` and `token::Unsafe` are the same type, checks keywords only
+///
+///Type names of keyword tokens are the keyword with an uppercase first letter,
+/// except for `self` (`SelfValue`) and `Self` (`SelfType`). The path has to include `token::`,
+/// `Token![type]` and `syn::Type` are different types
+fn keyword_token_equals_path(type_macro: &syn::TypeMacro, type_path: &syn::TypePath) -> bool {
+    if type_macro
+        .mac
+        .path
+        .segments
+        .last()
+        .is_none_or(|segment| segment.ident != "Token")
+    {
+        return false;
+    }
+    let mut tokens = type_macro.mac.tokens.clone().into_iter();
+    let (Some(proc_macro2::TokenTree::Ident(keyword)), None) = (tokens.next(), tokens.next())
+    else {
+        return false;
+    };
+    let mut segments = type_path.path.segments.iter().rev();
+    let (Some(segment), Some(module)) = (segments.next(), segments.next()) else {
+        return false;
+    };
+    if module.ident != "token" || type_path.qself.is_some() || !segment.arguments.is_none() {
+        return false;
+    }
+
+    let keyword = keyword.to_string();
+    let type_name = match keyword.as_str() {
+        "self" => "SelfValue".to_string(),
+        "Self" => "SelfType".to_string(),
+        _ => {
+            let mut chars = keyword.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => return false,
+            }
+        }
+    };
+    segment.ident == type_name
+}
+
 pub fn additional_type(active: bool, ty: &syn::Type) -> Option<AdditionalType> {
     if active {
         if let syn::Type::Reference(_) = ty {
@@ -343,18 +662,40 @@ fn additional_type_no_ref(active: bool) -> Option<AdditionalType> {
     }
 }
 
+///Generic arguments of `Box` inside of `Option<Box<...>>` arguments
+fn boxed_generic_arguments(args: &syn::PathArguments) -> Option<&syn::PathArguments> {
+    if let syn::PathArguments::AngleBracketed(args) = args
+        && let Some(syn::GenericArgument::Type(syn::Type::Path(ty_path))) = args.args.first()
+        && let Some(segment) = ty_path.path.segments.last()
+        && segment.ident == "Box"
+    {
+        return Some(&segment.arguments);
+    }
+    None
+}
+
 impl EssentialFnData {
-    pub fn new(sig: Signature) -> Self {
+    ///`fn_prefix` - `generated_fn_prefix`, decides which parameter types are `{Prefix}EnclosingIdent` and `{Prefix}HandlerPhase`
+    pub fn new(sig: Signature, fn_prefix: &str) -> Self {
+        let sig_tokens = sig.to_token_stream();
         let mut input_types = Vec::new();
+        let mut enclosing_ident = None;
+        let mut handler_phase = None;
 
-        for input in sig.inputs {
+        for (index, input) in sig.inputs.into_iter().enumerate() {
             match input {
                 syn::FnArg::Receiver(_) => {
                     panic!("self arguments are not supported");
                 }
                 syn::FnArg::Typed(pat_type) => {
                     let ty = *pat_type.ty;
-                    input_types.push(ty);
+                    if enclosing_ident.is_none() && is_enclosing_ident(&ty, fn_prefix) {
+                        enclosing_ident = Some((index, ty));
+                    } else if handler_phase.is_none() && is_handler_phase(&ty, fn_prefix) {
+                        handler_phase = Some((index, ty));
+                    } else {
+                        input_types.push(ty);
+                    }
                 }
             }
         }
@@ -363,10 +704,19 @@ impl EssentialFnData {
             input_types,
             ident: sig.ident,
             used_at_least_once: false,
+            additional_input_repeats: false,
+            sig_tokens,
+            handler_type: None,
+            matched_in: Vec::new(),
+            iterate_options: true,
+            enclosing_ident,
+            handler_phase,
+            after_system: false,
         }
     }
 
     pub fn new_no_check(sig: Signature) -> Self {
+        let sig_tokens = sig.to_token_stream();
         let mut input_types = Vec::new();
 
         for input in sig.inputs {
@@ -385,6 +735,14 @@ impl EssentialFnData {
             input_types,
             ident: sig.ident,
             used_at_least_once: true,
+            additional_input_repeats: false,
+            sig_tokens,
+            handler_type: None,
+            matched_in: Vec::new(),
+            iterate_options: true,
+            enclosing_ident: None,
+            handler_phase: None,
+            after_system: false,
         }
     }
 
@@ -413,6 +771,7 @@ impl EssentialFnData {
 
         ///# Return
         /// `true` - found match
+        #[allow(clippy::too_many_arguments)]
         fn fn_arg_ty_equals<'a>(
             reference_ty: &syn::Type,
             maybe_ty: &syn::Type,
@@ -420,6 +779,7 @@ impl EssentialFnData {
             real_index: &usize,
             result_args: &mut HashMap<usize, Vec<ResultArgData<'a>>>,
             additional_ty: bool,
+            iterate_options: bool,
         ) -> bool {
             if type_equals(reference_ty, maybe_ty) {
                 let arg_data = result_args.entry(*real_index).or_default();
@@ -448,6 +808,7 @@ impl EssentialFnData {
 
                     return true;
                 } else {
+                    #[allow(clippy::too_many_arguments)]
                     fn handle_path<'a>(
                         maybe_ty: &syn::Type,
                         type_reference: &TypeReference,
@@ -456,6 +817,7 @@ impl EssentialFnData {
                         maybe_ident: &'a syn::Ident,
                         additional_ty: bool,
                         current_reference_ty: Option<Option<ReferenceType>>,
+                        iterate_options: bool,
                     ) -> bool {
                         if let syn::Type::Path(ty_path) = maybe_ty {
                             #[allow(clippy::too_many_arguments)]
@@ -477,32 +839,30 @@ impl EssentialFnData {
                                     ) => {
                                         if let Some(syn::GenericArgument::Type(ty)) =
                                             angle_bracketed_generic_arguments.args.first()
+                                            && type_equals(&type_reference.elem, ty)
                                         {
-                                            if type_equals(&type_reference.elem, ty) {
-                                                let reference_ty =
-                                                    if let Some(current_reference_ty) =
-                                                        current_reference_ty
-                                                    {
-                                                        current_reference_ty
-                                                    } else if type_reference.mutability.is_some() {
-                                                        Some(ReferenceType::Mutable)
-                                                    } else {
-                                                        Some(ReferenceType::Immutable)
-                                                    };
-
-                                                let arg_data =
-                                                    result_args.entry(*real_index).or_default();
-                                                arg_data.push(ResultArgData {
-                                                    ident: maybe_ident,
-                                                    reference_ty,
-                                                    additional_ty: additional_type_no_ref(
-                                                        additional_ty,
-                                                    ),
-                                                    list,
-                                                });
-
-                                                return true;
-                                            }
+                                            let reference_ty = if let Some(current_reference_ty) =
+                                                current_reference_ty
+                                            {
+                                                current_reference_ty
+                                            } else if type_reference.mutability.is_some() {
+                                                Some(ReferenceType::Mutable)
+                                            } else {
+                                                Some(ReferenceType::Immutable)
+                                            };
+
+                                            let arg_data =
+                                                result_args.entry(*real_index).or_default();
+                                            arg_data.push(ResultArgData {
+                                                ident: maybe_ident,
+                                                reference_ty,
+                                                additional_ty: additional_type_no_ref(
+                                                    additional_ty,
+                                                ),
+                                                list,
+                                            });
+
+                                            return true;
                                         }
                                     }
                                     a => panic!(
@@ -515,25 +875,44 @@ impl EssentialFnData {
 
                             let name_segment=ty_path.path.segments.last().expect("How the fuck this type doesn't have a single segment?! (should be unreachable)");
                             let ident_str = name_segment.ident.to_string();
+                            if ident_str == "Option" && !iterate_options {
+                                return false;
+                            }
 
-                            let list = matches!(ident_str.as_str(), "Vec" | "Punctuated");
-                            //Handle Boxes, Vectors, Punctuated
-                            match ident_str.as_str() {
-                                "Vec" | "Box" | "Punctuated" => {
-                                    if handle_generic_ty(
-                                        &name_segment.arguments,
-                                        list,
-                                        type_reference,
-                                        result_args,
-                                        real_index,
-                                        maybe_ident,
-                                        additional_ty,
-                                        current_reference_ty,
-                                    ) {
-                                        return true;
-                                    }
-                                }
-                                _ => {}
+                            //`Option` is iterated like a collection with zero or one element
+                            let list =
+                                matches!(ident_str.as_str(), "Vec" | "Punctuated" | "Option");
+                            //Handle Boxes, Vectors, Punctuated, Options
+                            if matches!(ident_str.as_str(), "Vec" | "Box" | "Punctuated" | "Option")
+                                && handle_generic_ty(
+                                    &name_segment.arguments,
+                                    list,
+                                    type_reference,
+                                    result_args,
+                                    real_index,
+                                    maybe_ident,
+                                    additional_ty,
+                                    current_reference_ty,
+                                )
+                            {
+                                return true;
+                            }
+                            //`Option<Box<T>>` - `&mut Box<T>` is coerced to `&mut T` at the call
+                            if ident_str == "Option"
+                                && let Some(box_arguments) =
+                                    boxed_generic_arguments(&name_segment.arguments)
+                                && handle_generic_ty(
+                                    box_arguments,
+                                    true,
+                                    type_reference,
+                                    result_args,
+                                    real_index,
+                                    maybe_ident,
+                                    additional_ty,
+                                    current_reference_ty,
+                                )
+                            {
+                                return true;
                             }
                         }
                         false
@@ -557,6 +936,7 @@ impl EssentialFnData {
                             maybe_ident,
                             additional_ty,
                             reference_ty,
+                            iterate_options,
                         ) {
                             return true;
                         }
@@ -568,6 +948,7 @@ impl EssentialFnData {
                         maybe_ident,
                         additional_ty,
                         None,
+                        iterate_options,
                     ) {
                         return true;
                     }
@@ -588,14 +969,15 @@ impl EssentialFnData {
                 real_index,
                 &mut result_args,
                 true,
+                self.iterate_options,
             ) {
                 if additional_argument_found.is_none() {
                     // Additional input type argument should not repeat
                     additional_argument_found = Some(index);
                 } else {
-                    panic!(
-                        "all_syntax_cases: additional input type should not repeat in function signature (it shouldn't be a type from syn or proc_macro2 libraries, or have the same name as type from any of those libraries)"
-                    );
+                    //Reported by `used_check`
+                    self.additional_input_repeats = true;
+                    return None;
                 }
             }
         }
@@ -613,6 +995,7 @@ impl EssentialFnData {
                     real_index,
                     &mut result_args,
                     false,
+                    self.iterate_options,
                 );
             }
         }
@@ -626,7 +1009,7 @@ impl EssentialFnData {
             let mut result_args_vec = result_args.into_iter().collect::<Vec<_>>();
 
             //Sort arguments
-            result_args_vec.sort_by(|a, b| a.0.cmp(&b.0));
+            result_args_vec.sort_by_key(|a| a.0);
             //Format arguments
             let before_dot = if let Some(before_dot) = before_dot {
                 quote! {#before_dot.}
@@ -640,7 +1023,7 @@ impl EssentialFnData {
                 input_types_len == 1
             };
 
-            let fn_ident = &self.ident;
+            let fn_ident = self.call_path();
             let mut result_call_arguments: Vec<Vec<proc_macro2::TokenStream>> = Vec::new();
 
             let mut list_calls_tokens = quote! {};
@@ -707,26 +1090,20 @@ impl EssentialFnData {
 
                 //Handle list calls
                 let list_calls_iter = result_list_iterators.iter().map(|iter| {
-                    if let Some(additional_arg) = additional_data_argument.clone() {
+                    let args = if let Some(additional_arg) = additional_data_argument.clone() {
                         //More than one required args are not allowed yet
                         if additional_data_pos == 0 {
-                            quote! {
-                                for ____x in #iter{
-                                    #fn_ident(#additional_arg, ____x);
-                                }
-                            }
+                            vec![additional_arg, quote! { ____x }]
                         } else {
-                            quote! {
-                                for ____x in #iter{
-                                    #fn_ident(____x, #additional_arg);
-                                }
-                            }
+                            vec![quote! { ____x }, additional_arg]
                         }
                     } else {
-                        quote! {
-                            for ____x in #iter{
-                                #fn_ident(____x);
-                            }
+                        vec![quote! { ____x }]
+                    };
+                    let call = self.handler_call(&fn_ident, args);
+                    quote! {
+                        for ____x in #iter{
+                            #call
                         }
                     }
                 });
@@ -803,11 +1180,9 @@ impl EssentialFnData {
 
             //Create function calls
 
-            let calls_iter = result_call_arguments.iter().map(|args| {
-                quote! {
-                    #fn_ident(#(#args),*);
-                }
-            });
+            let calls_iter = result_call_arguments
+                .into_iter()
+                .map(|args| self.handler_call(&fn_ident, args));
 
             Some(quote! {
                 #(#calls_iter)*
@@ -818,19 +1193,118 @@ impl EssentialFnData {
         }
     }
 
-    pub fn used_check(&self) {
+    ///Same as `all_inputs_check`, remembers `matched_in` (syn type/variant) for the coverage summary
+    pub fn all_inputs_check_in(
+        &mut self,
+        fields: &[syn::Field],
+        before_dot: Option<&proc_macro2::TokenStream>,
+        additional_input: (&syn::Ident, &syn::Type),
+        matched_in: &str,
+    ) -> Option<proc_macro2::TokenStream> {
+        let result = self.all_inputs_check(fields, before_dot, additional_input);
+        if result.is_some() && !self.matched_in.iter().any(|m| m == matched_in) {
+            self.matched_in.push(matched_in.to_owned());
+        }
+        result
+    }
+
+    ///Error (spanned at the function signature) if additional input type repeats in the signature
+    pub fn additional_input_check(&self) -> syn::Result<()> {
+        if self.additional_input_repeats {
+            return Err(syn::Error::new_spanned(
+                &self.sig_tokens,
+                "all_syntax_cases: additional input type should not repeat in function signature (it shouldn't be a type from syn or proc_macro2 libraries, or have the same name as type from any of those libraries)",
+            ));
+        }
+        Ok(())
+    }
+
+    ///Error (spanned at the function signature) if function was never used or is misconfigured
+    pub fn used_check(&self) -> syn::Result<()> {
+        self.additional_input_check()?;
         if !self.used_at_least_once {
-            panic!(
-                "Function {} was not used while genereting all_syntax_cases macro output",
-                self.ident
-            );
+            return Err(syn::Error::new_spanned(
+                &self.sig_tokens,
+                format!(
+                    "Function {} was not used while genereting all_syntax_cases macro output (signature doesn't match any syn type fields)",
+                    self.ident
+                ),
+            ));
         }
+        Ok(())
+    }
+
+    ///Called as `<T>::handler(...)` instead of `handler(...)`
+    pub fn with_handler_type(mut self, handler_type: Option<syn::Type>) -> Self {
+        self.handler_type = handler_type;
+        self
+    }
+
+    ///Called after child traversal (`#[after_system]`, or the second call of `#[both_phases]`)
+    pub fn with_after_system(mut self, after_system: bool) -> Self {
+        self.after_system = after_system;
+        self
+    }
+
+    ///`#call_path(#args);`, with `{Prefix}EnclosingIdent` and `{Prefix}HandlerPhase` arguments inserted if the handler requests them
+    fn handler_call(
+        &self,
+        call_path: &proc_macro2::TokenStream,
+        mut args: Vec<proc_macro2::TokenStream>,
+    ) -> proc_macro2::TokenStream {
+        let mut inserted = Vec::new();
+        if let Some((index, ty)) = &self.enclosing_ident {
+            inserted.push((*index, enclosing_ident_arg(ty)));
+        }
+        if let Some((index, ty)) = &self.handler_phase {
+            inserted.push((*index, handler_phase_arg(ty, self.after_system)));
+        }
+        //Indexes are positions in the full signature, insert from the first one
+        inserted.sort_by_key(|(index, _)| *index);
+        for (index, arg) in inserted {
+            args.insert(index, arg);
+        }
+        quote! {
+            #call_path(#(#args),*);
+        }
+    }
+
+    ///Handler has `{Prefix}EnclosingIdent` parameter
+    pub fn uses_enclosing_ident(&self) -> bool {
+        self.enclosing_ident.is_some()
+    }
+
+    ///Handler has `{Prefix}HandlerPhase` parameter
+    pub fn uses_handler_phase(&self) -> bool {
+        self.handler_phase.is_some()
+    }
+
+    ///Path used for calling the handler
+    fn call_path(&self) -> proc_macro2::TokenStream {
+        let ident = &self.ident;
+        match &self.handler_type {
+            Some(handler_type) => quote! { <#handler_type>::#ident },
+            None => quote! { #ident },
+        }
+    }
+
+    pub fn ident(&self) -> &syn::Ident {
+        &self.ident
     }
 
     pub fn name_equals(&self, name: &syn::Ident) -> bool {
         &self.ident == name
     }
 
+    ///`(function_name, &[matched_in...])` entry of the coverage summary
+    pub fn coverage(&self) -> proc_macro2::TokenStream {
+        let name = self.ident.to_string();
+        let matched_in = &self.matched_in;
+        quote! {
+            (#name, &[#(#matched_in),*])
+        }
+    }
+
     ///When used outside of search context
     pub fn used(&mut self) {
         self.used_at_least_once = true;
@@ -1004,6 +1478,116 @@ impl MacroFnNames {
             additional_input_name,
         }
     }
+
+    ///Names of all generated `{prefix}_*_handle` functions, used by `expose_handler_list`
+    pub fn handler_names(&self) -> Vec<String> {
+        //Destructured, so new functions can't be forgotten here
+        let MacroFnNames {
+            item,
+            expr,
+            expr_option,
+            block,
+            stmt,
+            generics,
+            generic_param,
+            type_param_bound,
+            bound_lifetimes,
+            bound_lifetimes_option,
+            where_predicate,
+            impl_item,
+            item_mod_content,
+            fields,
+            trait_item,
+            fields_named,
+            option_box_expr,
+            pat,
+            option_else_expr,
+            arm,
+            angle_bracketed_generic_arguments,
+            field_value,
+            local_init,
+            option_local_init,
+            signature,
+            where_clause,
+            where_clause_option,
+            fn_arg,
+            variadic_pat,
+            variadic,
+            variadic_option,
+            field,
+            option_block,
+            option_eq_expr,
+            field_pat,
+            option_at_pat,
+            arm_guard,
+            option_angle_bracketed_generic_arguments,
+            generic_argument,
+            ty,
+            option_ty,
+            bare_fn_arg,
+            return_type,
+            variant,
+            foreign_item,
+            qself,
+            option_qself,
+            option_eq_type,
+            additional_input_name: _,
+        } = self;
+
+        [
+            item,
+            expr,
+            expr_option,
+            block,
+            stmt,
+            generics,
+            generic_param,
+            type_param_bound,
+            bound_lifetimes,
+            bound_lifetimes_option,
+            where_predicate,
+            impl_item,
+            item_mod_content,
+            fields,
+            trait_item,
+            fields_named,
+            option_box_expr,
+            pat,
+            option_else_expr,
+            arm,
+            angle_bracketed_generic_arguments,
+            field_value,
+            local_init,
+            option_local_init,
+            signature,
+            where_clause,
+            where_clause_option,
+            fn_arg,
+            variadic_pat,
+            variadic,
+            variadic_option,
+            field,
+            option_block,
+            option_eq_expr,
+            field_pat,
+            option_at_pat,
+            arm_guard,
+            option_angle_bracketed_generic_arguments,
+            generic_argument,
+            ty,
+            option_ty,
+            bare_fn_arg,
+            return_type,
+            variant,
+            foreign_item,
+            qself,
+            option_qself,
+            option_eq_type,
+        ]
+        .iter()
+        .map(|ident| ident.to_string())
+        .collect()
+    }
 }
 
 pub struct MacroData {
@@ -1017,6 +1601,32 @@ pub struct MacroData {
 }
 
 impl MacroData {
+    ///At least one of the handlers has `{Prefix}EnclosingIdent` parameter
+    pub fn uses_enclosing_ident(&self) -> bool {
+        self.default_functions
+            .iter()
+            .chain(self.default_functions_after_system.iter())
+            .chain(self.special_functions.iter())
+            .any(|f| f.uses_enclosing_ident())
+    }
+
+    ///At least one of the handlers is marked with `#[both_phases]`
+    pub fn uses_handler_phase(&self) -> bool {
+        self.default_functions
+            .iter()
+            .any(|f| f.uses_handler_phase())
+    }
+
+    ///Names of default and special case handlers
+    pub fn handler_idents(&self) -> Vec<&syn::Ident> {
+        self.default_functions
+            .iter()
+            .chain(self.default_functions_after_system.iter())
+            .chain(self.special_functions.iter())
+            .map(|f| f.ident())
+            .collect()
+    }
+
     pub fn new(macro_input: Input) -> Self {
         let Input {
             setup,
@@ -1030,17 +1640,45 @@ impl MacroData {
         //Create function data
         let mut default_functions = Vec::new();
         let mut default_functions_after_system = Vec::new();
-        for sig in default_cases.into_iter() {
-            if sig.after_system() {
-                default_functions_after_system.push(EssentialFnData::new(sig.sig));
+        let handler_type = setup.handler_trait;
+        //Handlers of `impl_on` can be listed with their `self` parameter
+        let impl_on = setup.impl_on.is_some();
+        let without_receiver = |mut sig: Signature| {
+            if impl_on {
+                sig.inputs = sig
+                    .inputs
+                    .into_pairs()
+                    .filter(|pair| !matches!(pair.value(), syn::FnArg::Receiver(_)))
+                    .collect();
+            }
+            sig
+        };
+        for mut sig in default_cases.into_iter() {
+            sig.sig = without_receiver(sig.sig);
+            let after_system = sig.after_system();
+            let both_phases = sig.both_phases();
+            let new_fn_data = |after_system: bool| {
+                EssentialFnData::new(sig.sig.clone(), &setup.generated_fn_prefix)
+                    .with_handler_type(handler_type.clone())
+                    .with_after_system(after_system)
+            };
+            if both_phases {
+                //Same function registered twice, `{Prefix}HandlerPhase` argument differs
+                default_functions.push(new_fn_data(false));
+                default_functions_after_system.push(new_fn_data(true));
+            } else if after_system {
+                default_functions_after_system.push(new_fn_data(true));
             } else {
-                default_functions.push(EssentialFnData::new(sig.sig));
+                default_functions.push(new_fn_data(false));
             }
         }
 
         let mut special_functions = Vec::new();
         for sig in special_cases.iter() {
-            special_functions.push(EssentialFnData::new(sig.clone()));
+            special_functions.push(
+                EssentialFnData::new(without_receiver(sig.clone()), &setup.generated_fn_prefix)
+                    .with_handler_type(handler_type.clone()),
+            );
         }
 
         struct SystemNewFn(fn(syn::Signature) -> EssentialFnData);
@@ -1049,7 +1687,7 @@ impl MacroData {
 
         //Activate checks back for testing
         if setup.system_functions_test {
-            system_new_fn = SystemNewFn(EssentialFnData::new);
+            system_new_fn = SystemNewFn(|sig| EssentialFnData::new(sig, ""));
         }
 
         let mut system_functions = Vec::new();
@@ -1250,6 +1888,10 @@ impl MacroData {
             fn #option_qself(option_qself: &mut Option<QSelf>, #additional_input_name: #additional_input_ty)
         }));
 
+        for system_function in system_functions.iter_mut() {
+            system_function.iterate_options = false;
+        }
+
         Self {
             fn_names,
             additional_input_ty,
@@ -1339,6 +1981,91 @@ fn type_equals_path_test() {
     assert!(type_equals_path_check(&path8, &path7));
 }
 
+///Dry-expands matching of a single handler against a struct, without generating the whole traversal
+///
+///`signature` - handler signature, e.g. `fn handler(expr: &mut syn::Expr, ctx: &mut Context)`
+///
+///`fields` - named fields of the matched struct, e.g. `{ attrs: Vec<syn::Attribute>, expr: Box<syn::Expr> }`
+///
+///`additional_input_ty` - `additional_input_type` from the setup, passed in as `__additional_input`
+///
+///Returns generated calls (same as in the final expansion), `None` if the handler doesn't match
+#[cfg(test)]
+pub fn dry_expand(
+    signature: &str,
+    fields: &str,
+    additional_input_ty: &str,
+) -> Option<proc_macro2::TokenStream> {
+    let mut fn_data = EssentialFnData::new(
+        syn::parse_str(signature).expect("Invalid signature"),
+        "test",
+    );
+    let fields: syn::FieldsNamed = syn::parse_str(fields).expect("Invalid fields");
+    let fields = fields.named.into_iter().collect::<Vec<_>>();
+    let additional_input_ident = quote::format_ident!("__additional_input");
+    let additional_input_ty: syn::Type =
+        syn::parse_str(additional_input_ty).expect("Invalid additional input type");
+
+    fn_data.all_inputs_check(
+        &fields,
+        None,
+        (&additional_input_ident, &additional_input_ty),
+    )
+}
+
+#[test]
+fn dry_expand_table_test() {
+    //(signature, fields, expected call)
+    let cases = [
+        (
+            "fn h(expr: &mut syn::Expr, ctx: &mut Ctx)",
+            "{ attrs: Vec<syn::Attribute>, expr: Box<syn::Expr> }",
+            Some(quote! { h(&mut expr, __additional_input); }),
+        ),
+        (
+            "fn h(expr: &mut syn::Expr, ctx: &mut Ctx)",
+            "{ elems: Punctuated<syn::Expr, Token![,]> }",
+            Some(quote! {
+                for ____x in elems.iter_mut() {
+                    h(____x, __additional_input);
+                }
+            }),
+        ),
+        (
+            "fn h(expr: &mut syn::Expr, ctx: &mut Ctx)",
+            "{ expr: Option<Box<syn::Expr>> }",
+            Some(quote! {
+                for ____x in expr.iter_mut() {
+                    h(____x, __additional_input);
+                }
+            }),
+        ),
+        (
+            "fn h(attrs: &mut Vec<syn::Attribute>, generics: &mut syn::Generics, ctx: &mut Ctx)",
+            "{ attrs: Vec<syn::Attribute>, ident: syn::Ident, generics: syn::Generics }",
+            Some(quote! { h(&mut attrs, &mut generics, __additional_input); }),
+        ),
+        (
+            "fn h(attrs: &mut Vec<syn::Attribute>, generics: &mut syn::Generics, ctx: &mut Ctx)",
+            "{ attrs: Vec<syn::Attribute>, ident: syn::Ident }",
+            None,
+        ),
+        (
+            "fn h(lit: &syn::Lit, ctx: &mut Ctx)",
+            "{ expr: Box<syn::Expr> }",
+            None,
+        ),
+    ];
+
+    for (signature, fields, expected) in cases {
+        assert_eq!(
+            dry_expand(signature, fields, "&mut Ctx").map(|x| x.to_string()),
+            expected.map(|x| x.to_string()),
+            "{signature} with {fields}"
+        );
+    }
+}
+
 #[cfg(test)]
 fn test_vec_eq(vec1: &[syn::Type], vec2: &[syn::Type], expected: bool) {
     for item1 in vec1.iter() {
@@ -1498,6 +2225,44 @@ fn type_equals_test() {
     test_vec_eq(&option_if_expr, &option_if_expr, true);
     all_vectors.push(option_if_expr);
 
+    let option_unsafe: Vec<syn::Type> = vec![
+        syn::parse_quote!(Option<Token![unsafe]>),
+        syn::parse_quote!(Option<syn::Token![unsafe]>),
+        syn::parse_quote!(Option<token::Unsafe>),
+        syn::parse_quote!(Option<syn::token::Unsafe>),
+    ];
+    test_vec_eq(&option_unsafe, &option_unsafe, true);
+    all_vectors.push(option_unsafe);
+
+    let option_async: Vec<syn::Type> = vec![
+        syn::parse_quote!(Option<Token![async]>),
+        syn::parse_quote!(Option<syn::token::Async>),
+    ];
+    test_vec_eq(&option_async, &option_async, true);
+    all_vectors.push(option_async);
+
+    let self_value: Vec<syn::Type> = vec![
+        syn::parse_quote!(Token![self]),
+        syn::parse_quote!(syn::token::SelfValue),
+    ];
+    test_vec_eq(&self_value, &self_value, true);
+    all_vectors.push(self_value);
+
+    let self_type: Vec<syn::Type> = vec![
+        syn::parse_quote!(Token![Self]),
+        syn::parse_quote!(token::SelfType),
+    ];
+    test_vec_eq(&self_type, &self_type, true);
+    all_vectors.push(self_type);
+
+    //Keyword token and a syn type with the same name
+    let type_token: Vec<syn::Type> = vec![
+        syn::parse_quote!(Token![type]),
+        syn::parse_quote!(token::Type),
+    ];
+    test_vec_eq(&type_token, &type_token, true);
+    all_vectors.push(type_token);
+
     //Different Vectors (representing different equal values) should never have equal values (between different vectors)
     for v in all_vectors.iter() {
         for v2 in all_vectors.iter() {
@@ -1519,9 +2284,12 @@ fn essential_fn_checks_1_arg_test() {
     );
     let additional_input = (&additional_input_base.0, &additional_input_base.1);
 
-    let mut fn_data1 = EssentialFnData::new(syn::parse_quote! {
-        fn example_fn1(a: &mut syn::Item)
-    });
+    let mut fn_data1 = EssentialFnData::new(
+        syn::parse_quote! {
+            fn example_fn1(a: &mut syn::Item)
+        },
+        "test",
+    );
 
     let input_fields1 = {
         let input_fields: syn::FieldsNamed = syn::parse_quote! {
@@ -1700,12 +2468,18 @@ fn essential_fn_checks_2_args_test() {
     );
     let additional_input = (&additional_input_base.0, &additional_input_base.1);
 
-    let mut fn_data1 = EssentialFnData::new(syn::parse_quote! {
-        fn example_fn1(a: &mut syn::Expr, __additional_input: &mut AdditionalInput)
-    });
-    let mut fn_data2 = EssentialFnData::new(syn::parse_quote! {
-        fn example_fn2(a: &mut syn::Expr, __additional_input: AdditionalInput)
-    });
+    let mut fn_data1 = EssentialFnData::new(
+        syn::parse_quote! {
+            fn example_fn1(a: &mut syn::Expr, __additional_input: &mut AdditionalInput)
+        },
+        "test",
+    );
+    let mut fn_data2 = EssentialFnData::new(
+        syn::parse_quote! {
+            fn example_fn2(a: &mut syn::Expr, __additional_input: AdditionalInput)
+        },
+        "test",
+    );
 
     let input_fields1 = {
         let input_fields: syn::FieldsNamed = syn::parse_quote! {
@@ -1968,12 +2742,18 @@ fn essential_fn_checks_3_args_test() {
     );
     let additional_input = (&additional_input_base.0, &additional_input_base.1);
 
-    let mut fn_data1 = EssentialFnData::new(syn::parse_quote! {
-        fn example_fn1(a: &mut syn::Expr, b: &mut syn::Item, __additional_input: &mut AdditionalInput)
-    });
-    let mut fn_data2 = EssentialFnData::new(syn::parse_quote! {
-        fn example_fn2(a: &mut syn::Expr, b: &mut syn::Expr, __additional_input: &mut AdditionalInput)
-    });
+    let mut fn_data1 = EssentialFnData::new(
+        syn::parse_quote! {
+            fn example_fn1(a: &mut syn::Expr, b: &mut syn::Item, __additional_input: &mut AdditionalInput)
+        },
+        "test",
+    );
+    let mut fn_data2 = EssentialFnData::new(
+        syn::parse_quote! {
+            fn example_fn2(a: &mut syn::Expr, b: &mut syn::Expr, __additional_input: &mut AdditionalInput)
+        },
+        "test",
+    );
 
     let input_fields1 = {
         let input_fields: syn::FieldsNamed = syn::parse_quote! {
@@ -2071,9 +2851,12 @@ fn essential_fn_checks_return_type_debug_test() {
     let additional_input = (&additional_input_base.0, &additional_input_base.1);
 
     //Debug issues with ReturnType (because of &mut Box<Type>)
-    let mut fn_data1 = EssentialFnData::new(syn::parse_quote! {
-        fn example_ty_handle(ty: &mut Type, __additional_input: AdditionalInput)
-    });
+    let mut fn_data1 = EssentialFnData::new(
+        syn::parse_quote! {
+            fn example_ty_handle(ty: &mut Type, __additional_input: AdditionalInput)
+        },
+        "test",
+    );
 
     let input_fields1 = {
         let input_fields: syn::FieldsNamed = syn::parse_quote! {
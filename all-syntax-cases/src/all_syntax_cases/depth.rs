@@ -0,0 +1,92 @@
+use quote::quote;
+
+use super::data::MacroFnNames;
+
+///Adds a `Depth` counter to functions handling recursive syntax (`Item`, `Expr`, `Stmt`, `Block`, `Pat`, `Type`)
+///
+///Every guarded function increments the counter on entry and decrements it on exit (on drop, so panics are handled too).
+/// When `max_depth` is reached, `on_depth_exceeded(max_depth)` is called (if provided) and the function returns early,
+/// without calling any handlers for the node
+pub fn depth_guard(
+    search_result: proc_macro2::TokenStream,
+    fn_names: &MacroFnNames,
+    fn_prefix: &str,
+    max_depth: usize,
+    on_depth_exceeded: Option<syn::Path>,
+) -> proc_macro2::TokenStream {
+    let mut generated: syn::File = match syn::parse2(search_result) {
+        Ok(generated) => generated,
+        Err(e) => return e.to_compile_error(),
+    };
+
+    let guarded = [
+        &fn_names.item,
+        &fn_names.expr,
+        &fn_names.stmt,
+        &fn_names.block,
+        &fn_names.pat,
+        &fn_names.ty,
+    ];
+
+    let depth_static = quote::format_ident!("__{}_DEPTH", fn_prefix.to_uppercase());
+    let depth_struct = quote::format_ident!("__{}Depth", to_camel_case(fn_prefix));
+
+    let on_exceeded = on_depth_exceeded.map(|f| quote! { #f(#max_depth); });
+
+    for item in generated.items.iter_mut() {
+        if let syn::Item::Fn(item_fn) = item
+            && guarded.contains(&&item_fn.sig.ident)
+        {
+            let guard: syn::Stmt = syn::parse_quote! {
+                let Some(_depth) = #depth_struct::enter() else {
+                    #on_exceeded
+                    return;
+                };
+            };
+            item_fn.block.stmts.insert(0, guard);
+        }
+    }
+
+    quote! {
+        #generated
+
+        thread_local! {
+            static #depth_static: ::std::cell::Cell<usize> = const { ::std::cell::Cell::new(0) };
+        }
+
+        ///Depth counter generated by all_syntax_cases macro, decrements on drop
+        struct #depth_struct;
+
+        impl #depth_struct {
+            fn enter() -> Option<Self> {
+                #depth_static.with(|depth| {
+                    if depth.get() >= #max_depth {
+                        None
+                    } else {
+                        depth.set(depth.get() + 1);
+                        Some(#depth_struct)
+                    }
+                })
+            }
+        }
+
+        impl Drop for #depth_struct {
+            fn drop(&mut self) {
+                #depth_static.with(|depth| depth.set(depth.get() - 1));
+            }
+        }
+    }
+}
+
+pub fn to_camel_case(snake: &str) -> String {
+    snake
+        .split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,163 @@
+use quote::quote;
+
+use super::data::MacroFnNames;
+
+///Suffix of the generated type name (`{Prefix}EnclosingIdent`), handler parameters with this type receive the closest named item
+pub const ENCLOSING_IDENT: &str = "EnclosingIdent";
+
+///`{Prefix}EnclosingIdent`, `{Prefix}` is `fn_prefix` in `CamelCase`
+pub fn enclosing_ident_name(fn_prefix: &str) -> String {
+    format!(
+        "{}{}",
+        super::depth::to_camel_case(fn_prefix),
+        ENCLOSING_IDENT
+    )
+}
+
+///`true` if `ty` is `{Prefix}EnclosingIdent` of this invocation or a reference to it (path prefix is ignored)
+pub fn is_enclosing_ident(ty: &syn::Type, fn_prefix: &str) -> bool {
+    let ty = match ty {
+        syn::Type::Reference(type_reference) => &*type_reference.elem,
+        ty => ty,
+    };
+    match ty {
+        syn::Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == enclosing_ident_name(fn_prefix)),
+        _ => false,
+    }
+}
+
+///Argument passed to the handler in place of `EnclosingIdent` parameter with type `ty`
+pub fn enclosing_ident_arg(ty: &syn::Type) -> proc_macro2::TokenStream {
+    match ty {
+        syn::Type::Reference(type_reference) => {
+            let mutability = &type_reference.mutability;
+            let elem = &type_reference.elem;
+            quote! { &#mutability <#elem>::current() }
+        }
+        ty => quote! { <#ty>::current() },
+    }
+}
+
+///Generates `{Prefix}EnclosingIdent` type (named after `fn_prefix`, so multiple invocations can share a module) and makes `Item`, `ImplItem`, `TraitItem` and `ForeignItem` functions
+/// set the identifier of the handled item as current (items without identifier, like `impl` blocks, keep the outer one)
+///
+///Previous identifier is restored on exit (on drop, so panics are handled too)
+pub fn enclosing_guard(
+    search_result: proc_macro2::TokenStream,
+    fn_names: &MacroFnNames,
+    fn_prefix: &str,
+) -> proc_macro2::TokenStream {
+    let mut generated: syn::File = match syn::parse2(search_result) {
+        Ok(generated) => generated,
+        Err(e) => return e.to_compile_error(),
+    };
+
+    let enclosing_static = quote::format_ident!("__{}_ENCLOSING_IDENT", fn_prefix.to_uppercase());
+    let guard_struct =
+        quote::format_ident!("__{}EnclosingGuard", super::depth::to_camel_case(fn_prefix));
+    let enclosing_ident = quote::format_ident!("{}", enclosing_ident_name(fn_prefix));
+
+    //(function, ident of every named variant)
+    let item_fns = [
+        (
+            &fn_names.item,
+            quote! {
+                syn::Item::Const(syn::ItemConst { ident, .. })
+                | syn::Item::Enum(syn::ItemEnum { ident, .. })
+                | syn::Item::ExternCrate(syn::ItemExternCrate { ident, .. })
+                | syn::Item::Fn(syn::ItemFn { sig: syn::Signature { ident, .. }, .. })
+                | syn::Item::Macro(syn::ItemMacro { ident: Some(ident), .. })
+                | syn::Item::Mod(syn::ItemMod { ident, .. })
+                | syn::Item::Static(syn::ItemStatic { ident, .. })
+                | syn::Item::Struct(syn::ItemStruct { ident, .. })
+                | syn::Item::Trait(syn::ItemTrait { ident, .. })
+                | syn::Item::TraitAlias(syn::ItemTraitAlias { ident, .. })
+                | syn::Item::Type(syn::ItemType { ident, .. })
+                | syn::Item::Union(syn::ItemUnion { ident, .. })
+            },
+        ),
+        (
+            &fn_names.impl_item,
+            quote! {
+                syn::ImplItem::Const(syn::ImplItemConst { ident, .. })
+                | syn::ImplItem::Fn(syn::ImplItemFn { sig: syn::Signature { ident, .. }, .. })
+                | syn::ImplItem::Type(syn::ImplItemType { ident, .. })
+            },
+        ),
+        (
+            &fn_names.trait_item,
+            quote! {
+                syn::TraitItem::Const(syn::TraitItemConst { ident, .. })
+                | syn::TraitItem::Fn(syn::TraitItemFn { sig: syn::Signature { ident, .. }, .. })
+                | syn::TraitItem::Type(syn::TraitItemType { ident, .. })
+            },
+        ),
+        (
+            &fn_names.foreign_item,
+            quote! {
+                syn::ForeignItem::Fn(syn::ForeignItemFn { sig: syn::Signature { ident, .. }, .. })
+                | syn::ForeignItem::Static(syn::ForeignItemStatic { ident, .. })
+                | syn::ForeignItem::Type(syn::ForeignItemType { ident, .. })
+            },
+        ),
+    ];
+
+    for item in generated.items.iter_mut() {
+        if let syn::Item::Fn(item_fn) = item
+            && let Some((_, named_variants)) = item_fns
+                .iter()
+                .find(|(name, _)| *name == &item_fn.sig.ident)
+            && let Some(syn::FnArg::Typed(arg)) = item_fn.sig.inputs.first()
+        {
+            let arg = &arg.pat;
+            let guard: syn::Stmt = syn::parse_quote! {
+                let _enclosing = #enclosing_ident::enter(match &*#arg {
+                    #named_variants => Some(ident),
+                    _ => None,
+                });
+            };
+            item_fn.block.stmts.insert(0, guard);
+        }
+    }
+
+    quote! {
+        #generated
+
+        thread_local! {
+            static #enclosing_static: ::std::cell::RefCell<Option<syn::Ident>> = const { ::std::cell::RefCell::new(None) };
+        }
+
+        ///Identifier of the closest named item (function, struct, module, method, ...) containing the handled node,
+        /// generated by all_syntax_cases macro
+        ///
+        ///Passed to every handler with a parameter of this type, `None` outside of named items
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        struct #enclosing_ident(pub Option<syn::Ident>);
+
+        impl #enclosing_ident {
+            fn current() -> Self {
+                #enclosing_ident(#enclosing_static.with(|current| current.borrow().clone()))
+            }
+
+            ///Makes `ident` current until the returned guard is dropped, `None` keeps the current one
+            fn enter(ident: Option<&syn::Ident>) -> Option<#guard_struct> {
+                let ident = ident?.clone();
+                let previous = #enclosing_static.with(|current| current.replace(Some(ident)));
+                Some(#guard_struct(previous))
+            }
+        }
+
+        ///Restores previous `{Prefix}EnclosingIdent` on drop, generated by all_syntax_cases macro
+        struct #guard_struct(Option<syn::Ident>);
+
+        impl Drop for #guard_struct {
+            fn drop(&mut self) {
+                #enclosing_static.with(|current| *current.borrow_mut() = self.0.take());
+            }
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::HashSet;
+
+use quote::{ToTokens, quote};
+use syn::visit_mut::VisitMut;
+
+///Moves every generated function into `impl #impl_on { ... }` as a `&mut self` method,
+/// calls of generated functions and handlers become `self.f(...)`
+///
+///When `additional_input_type` was omitted, the additional input parameter (and argument) is removed,
+/// state is stored in `self` instead
+pub fn impl_on(
+    search_result: proc_macro2::TokenStream,
+    impl_on: &syn::Type,
+    additional_input_omitted: bool,
+    generated_fns: &[String],
+    handlers: &[&syn::Ident],
+    additional_input_name: &syn::Ident,
+) -> proc_macro2::TokenStream {
+    let generated: syn::File = match syn::parse2(search_result) {
+        Ok(generated) => generated,
+        Err(e) => return e.to_compile_error(),
+    };
+
+    let mut method_calls = MethodCalls {
+        called: handlers
+            .iter()
+            .map(|handler| (*handler).clone())
+            .chain(generated_fns.iter().map(|f| quote::format_ident!("{}", f)))
+            .collect(),
+        removed_arg: additional_input_omitted.then(|| additional_input_name.clone()),
+    };
+
+    let mut methods = Vec::new();
+    let mut other_items = Vec::new();
+
+    for item in generated.items {
+        match item {
+            syn::Item::Fn(mut item_fn) if method_calls.called.contains(&item_fn.sig.ident) => {
+                if additional_input_omitted {
+                    item_fn.sig.inputs = item_fn
+                        .sig
+                        .inputs
+                        .into_pairs()
+                        .filter(|pair| !mentions(pair.value(), additional_input_name))
+                        .collect();
+                }
+                item_fn
+                    .sig
+                    .inputs
+                    .insert(0, syn::parse_quote! { &mut self });
+                method_calls.visit_block_mut(&mut item_fn.block);
+                methods.push(item_fn);
+            }
+            item => other_items.push(item),
+        }
+    }
+
+    quote! {
+        impl #impl_on {
+            #(#methods)*
+        }
+
+        #(#other_items)*
+    }
+}
+
+///Replaces `f(...)` with `self.f(...)` for every function in `called`
+struct MethodCalls {
+    ///Generated functions and handlers
+    called: HashSet<syn::Ident>,
+    ///Arguments mentioning this identifier are removed from the calls (omitted additional input)
+    removed_arg: Option<syn::Ident>,
+}
+
+impl VisitMut for MethodCalls {
+    fn visit_expr_mut(&mut self, expr: &mut syn::Expr) {
+        syn::visit_mut::visit_expr_mut(self, expr);
+
+        let syn::Expr::Call(call) = expr else {
+            return;
+        };
+        let syn::Expr::Path(path) = &*call.func else {
+            return;
+        };
+        let Some(method) = path.path.get_ident() else {
+            return;
+        };
+        if !self.called.contains(method) {
+            return;
+        }
+
+        let args = call.args.iter().filter(|arg| {
+            !self
+                .removed_arg
+                .as_ref()
+                .is_some_and(|name| mentions(arg, name))
+        });
+        *expr = syn::parse_quote! { self.#method(#(#args),*) };
+    }
+}
+
+///`tokens` contain `name` identifier (top level tokens only, enough for generated arguments and parameters)
+fn mentions(tokens: &impl ToTokens, name: &syn::Ident) -> bool {
+    tokens
+        .to_token_stream()
+        .into_iter()
+        .any(|token| matches!(token, proc_macro2::TokenTree::Ident(ident) if &ident == name))
+}
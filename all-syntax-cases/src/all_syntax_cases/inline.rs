@@ -0,0 +1,20 @@
+use quote::ToTokens;
+
+///Adds `#[inline]` to every generated function
+///
+///Only a hint, the compiler still decides (recursive functions are never fully inlined),
+/// but small dispatching functions (`*_option_*`, `Option<Box<Expr>>`, ...) can be merged into their callers
+pub fn inline_system_fns(search_result: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let mut generated: syn::File = match syn::parse2(search_result) {
+        Ok(generated) => generated,
+        Err(e) => return e.to_compile_error(),
+    };
+
+    for item in generated.items.iter_mut() {
+        if let syn::Item::Fn(item_fn) = item {
+            item_fn.attrs.push(syn::parse_quote! { #[inline] });
+        }
+    }
+
+    generated.into_token_stream()
+}
@@ -18,36 +18,264 @@ all_syntax_cases!{
 }
  */
 
+mod async_handlers;
 mod data;
+mod depth;
+mod enclosing;
+mod impl_on;
+mod inline;
+mod phase;
 mod search;
+mod visit_mut;
 
 use data::MacroData;
 use proc_macro::TokenStream;
+use quote::ToTokens;
 
 //TODO Create a list of every type found that can be used in default or special case (while computing this macro) (maybe?)
 
 ///Creates a function covering all cases of provided type
 /// additional_input is passed in deeper as a copy, not a mutable reference
 /// Every item in for example block has it's own copy of additional_input
+/// (unless `shared_additional_input: true` is set, then it's `&mut additional_input_type` everywhere)
 pub fn all_syntax_cases(item: TokenStream) -> TokenStream {
-    let parsed = syn::parse_macro_input!(item as data::Input);
+    expand(item.into()).into()
+}
+
+///`all_syntax_cases` operating on `proc_macro2` types
+///
+///Misconfigured handlers are returned as `compile_error!` next to the generated functions
+fn expand(item: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let parsed = match syn::parse2::<data::Input>(item) {
+        Ok(parsed) => parsed,
+        Err(e) => return e.to_compile_error(),
+    };
+
+    let report_coverage = parsed.report_coverage();
+    let coverage_const = quote::format_ident!("{}_COVERAGE", parsed.fn_prefix().to_uppercase());
+    let expose_handler_list = parsed.expose_handler_list();
+    let emit_visit_mut = parsed.emit_visit_mut();
+    let depth_limit = parsed.depth_limit();
+    let async_handlers = parsed.async_handlers();
+    let inline_system_fns = parsed.inline_system_fns();
+    let impl_on = parsed.impl_on();
+    let handler_trait_def = parsed.handler_trait_def().cloned();
+    let fn_prefix = parsed.fn_prefix().to_string();
+    let handler_list_const =
+        quote::format_ident!("{}_GENERATED_HANDLERS", parsed.fn_prefix().to_uppercase());
 
     let mut macro_data = MacroData::new(parsed);
 
-    let result = search::search(&mut macro_data);
+    let mut result = search::search(&mut macro_data);
+
+    if async_handlers {
+        result = async_handlers::async_handlers(result, &macro_data.handler_idents());
+    }
+
+    if inline_system_fns {
+        result = inline::inline_system_fns(result);
+    }
+
+    if macro_data.uses_enclosing_ident() {
+        result = enclosing::enclosing_guard(result, &macro_data.fn_names, &fn_prefix);
+    }
+
+    if macro_data.uses_handler_phase() {
+        result.extend(phase::handler_phase_type(&fn_prefix));
+    }
+
+    if let Some((max_depth, on_depth_exceeded)) = depth_limit {
+        result = depth::depth_guard(
+            result,
+            &macro_data.fn_names,
+            &fn_prefix,
+            max_depth,
+            on_depth_exceeded,
+        );
+    }
+
+    //Last one, previous steps work with free functions
+    if let Some((impl_on, additional_input_omitted)) = impl_on {
+        result = impl_on::impl_on(
+            result,
+            &impl_on,
+            additional_input_omitted,
+            &macro_data.fn_names.handler_names(),
+            &macro_data.handler_idents(),
+            &macro_data.fn_names.additional_input_name,
+        );
+    }
+
+    if let Some(handler_trait_def) = handler_trait_def {
+        result.extend(handler_trait_def.into_token_stream());
+    }
+
+    if expose_handler_list {
+        let handler_names = macro_data.fn_names.handler_names();
+        result.extend(quote::quote! {
+            ///Names of all functions generated by all_syntax_cases macro
+            #[doc(hidden)]
+            #[allow(dead_code)]
+            pub const #handler_list_const: &[&str] = &[#(#handler_names),*];
+        });
+    }
+
+    if let Some(struct_name) = emit_visit_mut {
+        result.extend(visit_mut::visit_mut_impl(&macro_data, &struct_name));
+    }
+
+    let mut checks = Vec::new();
+
+    if report_coverage {
+        //Unused functions are listed in the summary (with no matches), next to the error below
+        let coverage = macro_data
+            .default_functions
+            .iter()
+            .chain(macro_data.default_functions_after_system.iter())
+            .chain(macro_data.special_functions.iter())
+            .map(|f| f.coverage());
+
+        result.extend(quote::quote! {
+            ///Coverage summary generated by all_syntax_cases macro
+            ///
+            ///Format: `(function_name, &[syn types where the function is called])`
+            #[doc(hidden)]
+            #[allow(dead_code)]
+            const #coverage_const: &[(&str, &[&str])] = &[#(#coverage),*];
+        });
+    }
 
     //Check if all functions were used
     for f in macro_data.default_functions.iter() {
-        f.used_check();
+        checks.push(f.used_check());
     }
     for f in macro_data.special_functions.iter() {
-        f.used_check();
+        checks.push(f.used_check());
     }
     for f in macro_data.system_functions.iter() {
-        f.used_check();
+        checks.push(f.used_check());
+    }
+
+    //Combine all errors into one
+    let mut errors: Option<syn::Error> = None;
+    for e in checks.into_iter().filter_map(|c| c.err()) {
+        match &mut errors {
+            Some(errors) => errors.combine(e),
+            None => errors = Some(e),
+        }
+    }
+    if let Some(errors) = errors {
+        result.extend(errors.to_compile_error());
     }
 
     // panic!("{}", result);
 
-    result.into()
+    result
+}
+
+#[test]
+fn never_matched_handler_spanned_error() {
+    let input: proc_macro2::TokenStream = r#"
+        setup => {
+            generated_fn_prefix: "never",
+            additional_input_type: ()
+        }
+        default_cases => {
+            fn never_matched(value: &mut NotASynType, unit: ());
+        }
+        special_cases => {}
+    "#
+    .parse()
+    .unwrap();
+
+    let result = expand(input);
+
+    //Generated functions are still there, error is added after them
+    let compile_error = result
+        .into_iter()
+        .find(|t| matches!(t, proc_macro2::TokenTree::Ident(i) if i == "compile_error"))
+        .expect("compile_error! should be generated instead of panicking");
+
+    //Points at the `fn never_matched` line
+    assert_eq!(compile_error.span().start().line, 7);
+}
+
+#[test]
+fn coverage_lists_unused_handler() {
+    let input: proc_macro2::TokenStream = r#"
+        setup => {
+            generated_fn_prefix: "coverage",
+            additional_input_type: (),
+            report_coverage: true,
+        }
+        default_cases => {
+            fn coverage_unused_handler(value: &mut NotASynType, unit: ());
+        }
+        special_cases => {}
+    "#
+    .parse()
+    .unwrap();
+
+    let result = expand(input).to_string();
+
+    //Listed with no matches, unused handler error is still there
+    assert!(result.contains(r#"("coverage_unused_handler" , & [])"#));
+    assert!(result.contains("compile_error"));
+    assert!(result.contains("Function coverage_unused_handler was not used"));
+}
+
+#[test]
+fn generated_fns_have_type_docs() {
+    let input: proc_macro2::TokenStream = r#"
+        setup => {
+            generated_fn_prefix: "docs",
+            additional_input_type: ()
+        }
+        default_cases => {
+            fn docs_expr(expr: &mut syn::Expr, unit: ());
+        }
+        special_cases => {}
+    "#
+    .parse()
+    .unwrap();
+
+    let generated: syn::File = syn::parse2(expand(input)).unwrap();
+
+    let docs = generated
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            syn::Item::Fn(item_fn) => Some(item_fn),
+            _ => None,
+        })
+        .map(|item_fn| {
+            let doc = item_fn
+                .attrs
+                .iter()
+                .find(|attr| attr.path().is_ident("doc"))
+                .unwrap_or_else(|| panic!("`{}` has no doc marker", item_fn.sig.ident));
+            let syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(doc),
+                        ..
+                    }),
+                ..
+            }) = &doc.meta
+            else {
+                panic!("doc marker should be a string literal");
+            };
+            (item_fn.sig.ident.to_string(), doc.value())
+        })
+        .collect::<Vec<_>>();
+
+    assert!(docs.len() > 10);
+    assert!(docs.contains(&(
+        "docs_item_handle".to_string(),
+        "Traverses `syn::Item`".to_string()
+    )));
+    assert!(docs.contains(&(
+        "docs_expr_handle".to_string(),
+        "Traverses `syn::Expr`".to_string()
+    )));
 }
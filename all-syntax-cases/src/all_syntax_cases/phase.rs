@@ -0,0 +1,50 @@
+use quote::quote;
+
+///Suffix of the generated enum name (`{Prefix}HandlerPhase`), `#[both_phases]` handlers receive it to tell the two calls apart
+pub const HANDLER_PHASE: &str = "HandlerPhase";
+
+///`{Prefix}HandlerPhase`, `{Prefix}` is `fn_prefix` in `CamelCase`
+pub fn handler_phase_name(fn_prefix: &str) -> String {
+    format!(
+        "{}{}",
+        super::depth::to_camel_case(fn_prefix),
+        HANDLER_PHASE
+    )
+}
+
+///`true` if `ty` is `{Prefix}HandlerPhase` of this invocation (path prefix is ignored), passed by value only
+pub fn is_handler_phase(ty: &syn::Type, fn_prefix: &str) -> bool {
+    match ty {
+        syn::Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == handler_phase_name(fn_prefix)),
+        _ => false,
+    }
+}
+
+///Argument passed to the handler in place of `{Prefix}HandlerPhase` parameter with type `ty`
+pub fn handler_phase_arg(ty: &syn::Type, after_system: bool) -> proc_macro2::TokenStream {
+    if after_system {
+        quote! { <#ty>::After }
+    } else {
+        quote! { <#ty>::Before }
+    }
+}
+
+///Generates `{Prefix}HandlerPhase` enum (named after `fn_prefix`, so multiple invocations can share a module),
+/// used when at least one handler is marked with `#[both_phases]`
+pub fn handler_phase_type(fn_prefix: &str) -> proc_macro2::TokenStream {
+    let handler_phase = quote::format_ident!("{}", handler_phase_name(fn_prefix));
+    quote! {
+        ///Phase of a `#[both_phases]` handler call, generated by all_syntax_cases macro
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        enum #handler_phase {
+            ///Called before child nodes are traversed
+            Before,
+            ///Called after child nodes are traversed (like `#[after_system]` handlers)
+            After,
+        }
+    }
+}
@@ -189,6 +189,7 @@ pub fn item_search(macro_data: &mut MacroData) -> proc_macro2::TokenStream {
     });
 
     quote! {
+        #[doc = "Traverses `syn::Item`"]
         fn #fn_name(
             item: &mut syn::Item,
             mut #additional_input_name: #additional_input_ty,
@@ -502,6 +503,7 @@ pub fn expr_search(macro_data: &mut MacroData) -> proc_macro2::TokenStream {
     });
 
     quote! {
+        #[doc = "Traverses `syn::Expr`"]
         fn #fn_name(
             expr: &mut syn::Expr,
             mut #additional_input_name: #additional_input_ty,
@@ -528,6 +530,7 @@ pub fn option_expr_search(macro_data: &mut MacroData) -> proc_macro2::TokenStrea
     let additional_input_name = &fn_names.additional_input_name;
 
     quote! {
+        #[doc = "Traverses `Option<syn::Expr>`"]
         fn #fn_name(
             expr: &mut Option<syn::Expr>,
             mut #additional_input_name: #additional_input_ty,
@@ -563,6 +566,7 @@ pub fn block_search(macro_data: &mut MacroData) -> proc_macro2::TokenStream {
     });
 
     quote! {
+        #[doc = "Traverses `syn::Block`"]
         fn #fn_name(
             search_item: &mut syn::Block,
             mut #additional_input_name: #additional_input_ty,
@@ -606,6 +610,7 @@ pub fn stmt_search(macro_data: &mut MacroData) -> proc_macro2::TokenStream {
     }
 
     quote! {
+        #[doc = "Traverses `syn::Stmt`"]
         fn #fn_name(
             stmt: &mut syn::Stmt,
             mut #additional_input_name: #additional_input_ty,
@@ -640,6 +645,7 @@ pub fn bound_lifetimes_search(macro_data: &mut MacroData) -> proc_macro2::TokenS
     });
 
     quote! {
+        #[doc = "Traverses `syn::BoundLifetimes`"]
         fn #fn_name(
             search_item: &mut syn::BoundLifetimes,
             mut #additional_input_name: #additional_input_ty,
@@ -664,6 +670,7 @@ pub fn option_bound_lifetimes_search(macro_data: &mut MacroData) -> proc_macro2:
     let additional_input_name = &fn_names.additional_input_name;
 
     let final_result = quote! {
+        #[doc = "Traverses `Option<syn::BoundLifetimes>`"]
         fn #fn_name(
             bound_lifetimes: &mut Option<syn::BoundLifetimes>,
             mut #additional_input_name: #additional_input_ty,
@@ -728,6 +735,7 @@ pub fn type_param_bound_search(macro_data: &mut MacroData) -> proc_macro2::Token
     });
 
     quote! {
+        #[doc = "Traverses `syn::TypeParamBound`"]
         fn #fn_name(
             type_param_bound: &mut syn::TypeParamBound,
             mut #additional_input_name: #additional_input_ty,
@@ -784,6 +792,7 @@ pub fn generic_param_search(macro_data: &mut MacroData) -> proc_macro2::TokenStr
     }
 
     quote! {
+        #[doc = "Traverses `syn::GenericParam`"]
         fn #fn_name(
             generic_param: &mut syn::GenericParam,
             mut #additional_input_name: #additional_input_ty,
@@ -830,6 +839,7 @@ pub fn where_predicate_search(macro_data: &mut MacroData) -> proc_macro2::TokenS
     }
 
     quote! {
+        #[doc = "Traverses `syn::WherePredicate`"]
         fn #fn_name(
             where_predicate: &mut syn::WherePredicate,
             mut #additional_input_name: #additional_input_ty,
@@ -866,6 +876,7 @@ pub fn where_clause_search(macro_data: &mut MacroData) -> proc_macro2::TokenStre
     });
 
     quote! {
+        #[doc = "Traverses `syn::WhereClause`"]
         fn #fn_name(
             search_item: &mut syn::WhereClause,
             mut #additional_input_name: #additional_input_ty,
@@ -891,6 +902,7 @@ pub fn option_where_clause_search(macro_data: &mut MacroData) -> proc_macro2::To
     let additional_input_name = &fn_names.additional_input_name;
 
     let final_result = quote! {
+        #[doc = "Traverses `Option<syn::WhereClause>`"]
         fn #fn_name(
             where_clause: &mut Option<syn::WhereClause>,
             mut #additional_input_name: #additional_input_ty,
@@ -932,6 +944,7 @@ pub fn generics_search(macro_data: &mut MacroData) -> proc_macro2::TokenStream {
     });
 
     quote! {
+        #[doc = "Traverses `syn::Generics`"]
         fn #fn_name(
             search_item: &mut syn::Generics,
             mut #additional_input_name: #additional_input_ty,
@@ -1006,6 +1019,7 @@ pub fn impl_item_search(macro_data: &mut MacroData) -> proc_macro2::TokenStream
     });
 
     quote! {
+        #[doc = "Traverses `syn::ImplItem`"]
         fn #fn_name(
             impl_item: &mut syn::ImplItem,
             mut #additional_input_name: #additional_input_ty,
@@ -1047,6 +1061,7 @@ pub fn signature_search(macro_data: &mut MacroData) -> proc_macro2::TokenStream
     });
 
     quote! {
+        #[doc = "Traverses `syn::Signature`"]
         fn #fn_name(
             search_item: &mut syn::Signature,
             mut #additional_input_name: #additional_input_ty,
@@ -1090,6 +1105,7 @@ pub fn fn_arg_search(macro_data: &mut MacroData) -> proc_macro2::TokenStream {
     }
 
     quote! {
+        #[doc = "Traverses `syn::FnArg`"]
         fn #fn_name(
             search_item: &mut syn::FnArg,
             mut #additional_input_name: #additional_input_ty,
@@ -1116,6 +1132,7 @@ pub fn variadic_pat_search(macro_data: &mut MacroData) -> proc_macro2::TokenStre
     let additional_input_name = &fn_names.additional_input_name;
 
     quote! {
+        #[doc = "Traverses `Option<(Box<syn::Pat>,syn::Token![:])>`"]
         fn #fn_name(
             search_item: &mut Option<(Box<syn::Pat>, syn::Token![:])>,
             mut #additional_input_name: #additional_input_ty,
@@ -1151,6 +1168,7 @@ pub fn variadic_search(macro_data: &mut MacroData) -> proc_macro2::TokenStream {
     });
 
     quote! {
+        #[doc = "Traverses `syn::Variadic`"]
         fn #fn_name(
             search_item: &mut syn::Variadic,
             mut #additional_input_name: #additional_input_ty,
@@ -1175,6 +1193,7 @@ pub fn option_variadic_search(macro_data: &mut MacroData) -> proc_macro2::TokenS
     let additional_input_name = &fn_names.additional_input_name;
 
     let final_result = quote! {
+        #[doc = "Traverses `Option<syn::Variadic>`"]
         fn #fn_name(
             search_item: &mut Option<syn::Variadic>,
             mut #additional_input_name: #additional_input_ty,
@@ -1213,6 +1232,7 @@ pub fn item_mod_content_search(macro_data: &mut MacroData) -> proc_macro2::Token
     };
 
     quote! {
+        #[doc = "Traverses `Option<(syn::token::Brace,Vec<syn::Item>)>`"]
         fn #fn_name(
             search_item: &mut Option<(syn::token::Brace, Vec<syn::Item>)>,
             mut #additional_input_name: #additional_input_ty,
@@ -1256,6 +1276,7 @@ pub fn fields_search(macro_data: &mut MacroData) -> proc_macro2::TokenStream {
     }
 
     quote! {
+        #[doc = "Traverses `syn::Fields`"]
         fn #fn_name(
             search_item: &mut syn::Fields,
             mut #additional_input_name: #additional_input_ty,
@@ -1303,6 +1324,7 @@ pub fn field_search(macro_data: &mut MacroData) -> proc_macro2::TokenStream {
     });
 
     quote! {
+        #[doc = "Traverses `syn::Field`"]
         fn #fn_name(
             search_item: &mut syn::Field,
             mut #additional_input_name: #additional_input_ty,
@@ -1373,6 +1395,7 @@ pub fn trait_item_search(macro_data: &mut MacroData) -> proc_macro2::TokenStream
     });
 
     quote! {
+        #[doc = "Traverses `syn::TraitItem`"]
         fn #fn_name(
             search_item: &mut syn::TraitItem,
             mut #additional_input_name: #additional_input_ty,
@@ -1400,6 +1423,7 @@ pub fn option_block_search(macro_data: &mut MacroData) -> proc_macro2::TokenStre
     let additional_input_name = &fn_names.additional_input_name;
 
     quote! {
+        #[doc = "Traverses `Option<syn::Block>`"]
         fn #fn_name(
             option_block: &mut Option<syn::Block>,
             mut #additional_input_name: #additional_input_ty,
@@ -1428,6 +1452,7 @@ pub fn option_eq_expr_search(macro_data: &mut MacroData) -> proc_macro2::TokenSt
     let additional_input_name = &fn_names.additional_input_name;
 
     quote! {
+        #[doc = "Traverses `Option<(syn::Token![=],syn::Expr)>`"]
         fn #fn_name(
             option_eq_expr: &mut Option<(syn::Token![=], syn::Expr)>,
             mut #additional_input_name: #additional_input_ty,
@@ -1461,6 +1486,7 @@ pub fn fields_named_search(macro_data: &mut MacroData) -> proc_macro2::TokenStre
     });
 
     quote! {
+        #[doc = "Traverses `syn::FieldsNamed`"]
         fn #fn_name(
             search_item: &mut syn::FieldsNamed,
             mut #additional_input_name: #additional_input_ty,
@@ -1486,6 +1512,7 @@ pub fn option_box_expr_search(macro_data: &mut MacroData) -> proc_macro2::TokenS
     let additional_input_name = &fn_names.additional_input_name;
 
     quote! {
+        #[doc = "Traverses `Option<Box<syn::Expr>>`"]
         fn #fn_name(
             option_box_expr: &mut Option<Box<syn::Expr>>,
             mut #additional_input_name: #additional_input_ty,
@@ -1614,6 +1641,7 @@ pub fn pat_search(macro_data: &mut MacroData) -> proc_macro2::TokenStream {
     });
 
     quote! {
+        #[doc = "Traverses `syn::Pat`"]
         fn #fn_name(
             search_item: &mut syn::Pat,
             mut #additional_input_name: #additional_input_ty,
@@ -1647,6 +1675,7 @@ pub fn field_pat_search(macro_data: &mut MacroData) -> proc_macro2::TokenStream
         pub pat: Box<Pat>,
     });
     quote! {
+        #[doc = "Traverses `syn::FieldPat`"]
         fn #fn_name(
             search_item: &mut syn::FieldPat,
             mut #additional_input_name: #additional_input_ty,
@@ -1672,6 +1701,7 @@ pub fn option_at_pat(macro_data: &mut MacroData) -> proc_macro2::TokenStream {
     let additional_input_name = &fn_names.additional_input_name;
 
     quote! {
+        #[doc = "Traverses `Option<(syn::Token![@],Box<syn::Pat>)>`"]
         fn #fn_name(
             search_item: &mut Option<(syn::Token![@], Box<syn::Pat>)>,
             mut #additional_input_name: #additional_input_ty,
@@ -1700,6 +1730,7 @@ pub fn option_else_expr_search(macro_data: &mut MacroData) -> proc_macro2::Token
     let additional_input_name = &fn_names.additional_input_name;
 
     quote! {
+        #[doc = "Traverses `Option<(syn::Token![else],Box<syn::Expr>)>`"]
         fn #fn_name(
             option_else_expr: &mut Option<(syn::Token![else], Box<syn::Expr>)>,
             mut #additional_input_name: #additional_input_ty,
@@ -1737,6 +1768,7 @@ pub fn arm_search(macro_data: &mut MacroData) -> proc_macro2::TokenStream {
     });
 
     quote! {
+        #[doc = "Traverses `syn::Arm`"]
         fn #fn_name(
             search_item: &mut syn::Arm,
             mut #additional_input_name: #additional_input_ty,
@@ -1762,6 +1794,7 @@ pub fn arm_guard_search(macro_data: &mut MacroData) -> proc_macro2::TokenStream
     let additional_input_name = &fn_names.additional_input_name;
 
     quote! {
+        #[doc = "Traverses `Option<(syn::Token![if],Box<syn::Expr>)>`"]
         fn #fn_name(
             search_item: &mut Option<(syn::Token![if], Box<syn::Expr>)>,
             mut #additional_input_name: #additional_input_ty,
@@ -1800,6 +1833,7 @@ pub fn angle_bracketed_generic_arguments_search(
     });
 
     quote! {
+        #[doc = "Traverses `syn::AngleBracketedGenericArguments`"]
         fn #fn_name(
             search_item: &mut syn::AngleBracketedGenericArguments,
             mut #additional_input_name: #additional_input_ty,
@@ -1827,6 +1861,7 @@ pub fn option_angle_bracketed_generic_arguments_search(
     let additional_input_name = &fn_names.additional_input_name;
 
     let final_result = quote! {
+        #[doc = "Traverses `Option<syn::AngleBracketedGenericArguments>`"]
         fn #fn_name(
             search_item: &mut Option<syn::AngleBracketedGenericArguments>,
             mut #additional_input_name: #additional_input_ty,
@@ -1889,6 +1924,7 @@ pub fn generic_argument_search(macro_data: &mut MacroData) -> proc_macro2::Token
     }
 
     quote! {
+        #[doc = "Traverses `syn::GenericArgument`"]
         fn #fn_name(
             search_item: &mut syn::GenericArgument,
             mut #additional_input_name: #additional_input_ty,
@@ -1998,6 +2034,7 @@ pub fn type_search(macro_data: &mut MacroData) -> proc_macro2::TokenStream {
     });
 
     quote! {
+        #[doc = "Traverses `syn::Type`"]
         fn #fn_name(
             search_item: &mut syn::Type,
             mut #additional_input_name: #additional_input_ty,
@@ -2025,6 +2062,7 @@ pub fn option_type_search(macro_data: &mut MacroData) -> proc_macro2::TokenStrea
     let additional_input_name = &fn_names.additional_input_name;
 
     quote! {
+        #[doc = "Traverses `Option<syn::Type>`"]
         fn #fn_name(
             search_item: &mut Option<syn::Type>,
             mut #additional_input_name: #additional_input_ty,
@@ -2059,6 +2097,7 @@ pub fn bare_fn_arg_search(macro_data: &mut MacroData) -> proc_macro2::TokenStrea
     });
 
     quote! {
+        #[doc = "Traverses `syn::BareFnArg`"]
         fn #fn_name(
             search_item: &mut syn::BareFnArg,
             mut #additional_input_name: #additional_input_ty,
@@ -2138,6 +2177,7 @@ pub fn return_type_search(macro_data: &mut MacroData) -> proc_macro2::TokenStrea
     }
 
     quote! {
+        #[doc = "Traverses `syn::ReturnType`"]
         fn #fn_name(
             search_item: &mut syn::ReturnType,
             mut #additional_input_name: #additional_input_ty,
@@ -2180,6 +2220,7 @@ pub fn variant_search(macro_data: &mut MacroData) -> proc_macro2::TokenStream {
     });
 
     quote! {
+        #[doc = "Traverses `syn::Variant`"]
         fn #fn_name(
             search_item: &mut syn::Variant,
             mut #additional_input_name: #additional_input_ty,
@@ -2253,6 +2294,7 @@ pub fn foreign_item_search(macro_data: &mut MacroData) -> proc_macro2::TokenStre
     });
 
     quote! {
+        #[doc = "Traverses `syn::ForeignItem`"]
         fn #fn_name(
             search_item: &mut syn::ForeignItem,
             mut #additional_input_name: #additional_input_ty,
@@ -2289,6 +2331,7 @@ pub fn qself_search(macro_data: &mut MacroData) -> proc_macro2::TokenStream {
     });
 
     quote! {
+        #[doc = "Traverses `syn::QSelf`"]
         fn #fn_name(
             search_item: &mut syn::QSelf,
             mut #additional_input_name: #additional_input_ty,
@@ -2314,6 +2357,7 @@ pub fn option_qself_search(macro_data: &mut MacroData) -> proc_macro2::TokenStre
     let additional_input_name = &fn_names.additional_input_name;
 
     let final_result = quote! {
+        #[doc = "Traverses `Option<syn::QSelf>`"]
         fn #fn_name(
             option_qself: &mut Option<syn::QSelf>,
             mut #additional_input_name: #additional_input_ty,
@@ -2347,6 +2391,7 @@ pub fn option_eq_type_search(macro_data: &mut MacroData) -> proc_macro2::TokenSt
     let additional_input_name = &fn_names.additional_input_name;
 
     quote! {
+        #[doc = "Traverses `Option<(syn::Token![=],syn::Type)>`"]
         fn #fn_name(
             option_eq_type: &mut Option<(syn::Token![=], syn::Type)>,
             mut #additional_input_name: #additional_input_ty,
@@ -2387,6 +2432,7 @@ pub fn field_value_search(macro_data: &mut MacroData) -> proc_macro2::TokenStrea
     });
 
     quote! {
+        #[doc = "Traverses `syn::FieldValue`"]
         fn #fn_name(
             search_item: &mut syn::FieldValue,
             mut #additional_input_name: #additional_input_ty,
@@ -2418,6 +2464,7 @@ pub fn local_init_search(macro_data: &mut MacroData) -> proc_macro2::TokenStream
     });
 
     quote! {
+        #[doc = "Traverses `syn::LocalInit`"]
         fn #fn_name(
             search_item: &mut syn::LocalInit,
             mut #additional_input_name: #additional_input_ty,
@@ -2443,6 +2490,7 @@ pub fn option_local_init_search(macro_data: &mut MacroData) -> proc_macro2::Toke
     let additional_input_name = &fn_names.additional_input_name;
 
     let final_result = quote! {
+        #[doc = "Traverses `Option<syn::LocalInit>`"]
         fn #fn_name(
             option_local_init: &mut Option<syn::LocalInit>,
             mut #additional_input_name: #additional_input_ty,
@@ -0,0 +1,176 @@
+use quote::quote;
+
+use super::data::{AdditionalType, MacroData, MacroFnNames, additional_type};
+
+///Generates `struct #struct_name` implementing `syn::visit_mut::VisitMut`
+///
+///Every overridden `visit_*_mut` method hands the node to the generated `{prefix}_*_handle` function,
+/// which then does the whole traversal below it (default visitor recursion is not used)
+pub fn visit_mut_impl(
+    macro_data: &MacroData,
+    struct_name: &syn::Ident,
+) -> proc_macro2::TokenStream {
+    let MacroFnNames {
+        item,
+        expr,
+        block,
+        stmt,
+        generics,
+        generic_param,
+        type_param_bound,
+        bound_lifetimes,
+        where_predicate,
+        impl_item,
+        fields,
+        trait_item,
+        fields_named,
+        pat,
+        arm,
+        angle_bracketed_generic_arguments,
+        field_value,
+        local_init,
+        signature,
+        where_clause,
+        fn_arg,
+        variadic,
+        field,
+        field_pat,
+        generic_argument,
+        ty,
+        bare_fn_arg,
+        return_type,
+        variant,
+        foreign_item,
+        qself,
+        ..
+    } = &macro_data.fn_names;
+
+    //(visit method, syn type, generated handler)
+    let methods = [
+        (quote! {visit_item_mut}, quote! {Item}, item),
+        (quote! {visit_expr_mut}, quote! {Expr}, expr),
+        (quote! {visit_block_mut}, quote! {Block}, block),
+        (quote! {visit_stmt_mut}, quote! {Stmt}, stmt),
+        (quote! {visit_generics_mut}, quote! {Generics}, generics),
+        (
+            quote! {visit_generic_param_mut},
+            quote! {GenericParam},
+            generic_param,
+        ),
+        (
+            quote! {visit_type_param_bound_mut},
+            quote! {TypeParamBound},
+            type_param_bound,
+        ),
+        (
+            quote! {visit_bound_lifetimes_mut},
+            quote! {BoundLifetimes},
+            bound_lifetimes,
+        ),
+        (
+            quote! {visit_where_predicate_mut},
+            quote! {WherePredicate},
+            where_predicate,
+        ),
+        (quote! {visit_impl_item_mut}, quote! {ImplItem}, impl_item),
+        (quote! {visit_fields_mut}, quote! {Fields}, fields),
+        (
+            quote! {visit_trait_item_mut},
+            quote! {TraitItem},
+            trait_item,
+        ),
+        (
+            quote! {visit_fields_named_mut},
+            quote! {FieldsNamed},
+            fields_named,
+        ),
+        (quote! {visit_pat_mut}, quote! {Pat}, pat),
+        (quote! {visit_arm_mut}, quote! {Arm}, arm),
+        (
+            quote! {visit_angle_bracketed_generic_arguments_mut},
+            quote! {AngleBracketedGenericArguments},
+            angle_bracketed_generic_arguments,
+        ),
+        (
+            quote! {visit_field_value_mut},
+            quote! {FieldValue},
+            field_value,
+        ),
+        (
+            quote! {visit_local_init_mut},
+            quote! {LocalInit},
+            local_init,
+        ),
+        (quote! {visit_signature_mut}, quote! {Signature}, signature),
+        (
+            quote! {visit_where_clause_mut},
+            quote! {WhereClause},
+            where_clause,
+        ),
+        (quote! {visit_fn_arg_mut}, quote! {FnArg}, fn_arg),
+        (quote! {visit_variadic_mut}, quote! {Variadic}, variadic),
+        (quote! {visit_field_mut}, quote! {Field}, field),
+        (quote! {visit_field_pat_mut}, quote! {FieldPat}, field_pat),
+        (
+            quote! {visit_generic_argument_mut},
+            quote! {GenericArgument},
+            generic_argument,
+        ),
+        (quote! {visit_type_mut}, quote! {Type}, ty),
+        (
+            quote! {visit_bare_fn_arg_mut},
+            quote! {BareFnArg},
+            bare_fn_arg,
+        ),
+        (
+            quote! {visit_return_type_mut},
+            quote! {ReturnType},
+            return_type,
+        ),
+        (quote! {visit_variant_mut}, quote! {Variant}, variant),
+        (
+            quote! {visit_foreign_item_mut},
+            quote! {ForeignItem},
+            foreign_item,
+        ),
+        (quote! {visit_qself_mut}, quote! {QSelf}, qself),
+    ];
+
+    let mut additional_input_ty = macro_data.additional_input_ty.clone();
+
+    let clone = match additional_type(true, &additional_input_ty) {
+        Some(AdditionalType::NoReference) => quote! {.clone()},
+        //Reborrowed implicitly
+        Some(AdditionalType::Reference) => quote! {},
+        None => unreachable!("additional_type fn returned none with active: true!"),
+    };
+
+    //References stored in the struct need a named lifetime
+    let mut generics = quote! {};
+    if let syn::Type::Reference(reference) = &mut additional_input_ty {
+        let lifetime = reference
+            .lifetime
+            .get_or_insert_with(|| syn::parse_quote! {'__additional_input})
+            .clone();
+        generics = quote! {<#lifetime>};
+    }
+
+    let methods = methods.into_iter().map(|(visit_fn, syn_ty, handler)| {
+        quote! {
+            fn #visit_fn(&mut self, node: &mut syn::#syn_ty) {
+                #handler(node, self.additional_input #clone);
+            }
+        }
+    });
+
+    quote! {
+        ///`syn::visit_mut::VisitMut` implementor generated by all_syntax_cases macro
+        struct #struct_name #generics {
+            pub additional_input: #additional_input_ty,
+        }
+
+        impl #generics syn::visit_mut::VisitMut for #struct_name #generics {
+            #(#methods)*
+        }
+    }
+}
@@ -18,6 +18,17 @@ mod helpers;
 ///         generated_fn_prefix: "prefix",
 ///         additional_input_type: YourType,
 ///         system_functions_test: false,  // Optional: default false
+///         report_coverage: false,  // Optional: default false
+///         expose_handler_list: false,  // Optional: default false
+///         shared_additional_input: false,  // Optional: default false
+///         emit_visit_mut: StructName,  // Optional: not generated by default
+///         max_depth: 256,  // Optional: no limit by default
+///         on_depth_exceeded: depth_exceeded_fn,  // Optional: requires max_depth
+///         handler_trait: MyVisitor,  // Optional: handlers are free functions by default
+///         handler_trait_def: trait Visitor { ... },  // Optional: requires handler_trait
+///         async_handlers: false,  // Optional: default false
+///         inline_system_fns: false,  // Optional: default false
+///         impl_on: MyVisitor,  // Optional: free functions by default
 ///     }
 ///     default_cases => {
 ///         // Functions called for all matching types
@@ -41,22 +52,93 @@ mod helpers;
 ///   generates `handle_item`, `handle_expr`, etc.)
 /// - `additional_input_type` - Type of additional context passed to all handlers. Can be any type
 ///   (reference, value, mutable reference). This type is passed through the entire traversal.
+///   Optional when `impl_on` is set, generated methods then have no additional input parameter.
 /// - `system_functions_test` - Optional boolean (default: `false`). When `true`, enables validation
 ///   that all system-generated functions are actually invoked during macro expansion. This helps detect
 ///   coverage gaps in the macro's traversal logic. Use this when developing or debugging the macro itself,
 ///   not in production code.
+/// - `report_coverage` - Optional boolean (default: `false`). When `true`, generates a hidden
+///   `{PREFIX}_COVERAGE: &[(&str, &[&str])]` constant (prefix in uppercase) listing every default and special
+///   handler together with the syn types (variants) it is called for. Handlers which never match are
+///   listed with an empty slice, the usual compile error is still generated for them.
+/// - `expose_handler_list` - Optional boolean (default: `false`). When `true`, generates a hidden
+///   `pub const {PREFIX}_GENERATED_HANDLERS: &[&str]` constant (prefix in uppercase) with names of all generated
+///   `{prefix}_*_handle` functions.
+/// - `shared_additional_input` - Optional boolean (default: `false`). By default additional input is passed
+///   deeper by value (`.clone()` for every node). When `true`, `additional_input_type: T` becomes `&mut T`
+///   everywhere, so all handlers mutate a single instance (useful for counters and accumulators, `T` doesn't
+///   have to be `Clone`). Handlers should take `&mut T`.
+/// - `emit_visit_mut` - Optional struct name. Generates `struct StructName { pub additional_input: T }`
+///   implementing `syn::visit_mut::VisitMut` (requires the `visit-mut` feature of `syn` in your crate).
+///   Every overridden `visit_*_mut` method passes the node to the matching generated `{prefix}_*_handle`
+///   function, so the visitor can be dropped into existing `visit_mut` based pipelines. When
+///   `additional_input_type` is a reference without a lifetime, the struct gets a `'__additional_input` lifetime.
+/// - `max_depth` - Optional integer (default: no limit). Limits how deeply nested `Item`, `Expr`, `Stmt`, `Block`,
+///   `Pat` and `Type` nodes are traversed (counted per thread). Nodes nested deeper are skipped, together with
+///   all handlers for them, protecting against stack overflows on pathological input.
+/// - `on_depth_exceeded` - Optional function path, `fn(max_depth: usize)`. Called every time a node is skipped because
+///   of `max_depth`.
+/// - `handler_trait` - Optional type. Default and special case handlers are called as `<MyVisitor>::handler(...)`
+///   instead of free functions, so they can be grouped in a trait (or an inherent `impl`) of `MyVisitor`.
+///   Signatures are listed in `default_cases` / `special_cases` (matching the trait methods, without `self`),
+///   the trait has to be in scope where the macro is used. Or use `handler_trait_def` instead.
+/// - `handler_trait_def` - Optional trait definition, requires `handler_trait`. Handlers are taken from the trait
+///   methods (without `self`), `default_cases` and `special_cases` can be left out (they are added to the trait
+///   methods if present). Methods marked with `#[special_case]` are special case handlers, the others are default
+///   case handlers (`#[after_system]` and `#[both_phases]` are supported). The trait is emitted next to the
+///   generated functions without the markers, implement it for `MyVisitor`:
+///
+///   ```rust,ignore
+///   all_syntax_cases! {
+///       setup => {
+///           generated_fn_prefix: "visit",
+///           additional_input_type: &mut Collected,
+///           handler_trait: MyVisitor,
+///           handler_trait_def: trait Visitor {
+///               fn visit_lit(lit: &mut syn::Lit, collected: &mut Collected);
+///               #[special_case]
+///               fn visit_call(call: &mut syn::ExprCall, collected: &mut Collected);
+///           },
+///       }
+///   }
+///
+///   impl Visitor for MyVisitor { /* ... */ }
+///   ```
+/// - `async_handlers` - Optional boolean (default: `false`). When `true`, every generated `{prefix}_*_handle` is an
+///   `async fn` and every default and special case handler has to be an `async fn` too (handlers can `.await`).
+///   Special case handlers continue traversal with `{prefix}_expr_handle(&mut expr, context).await`.
+///   Can't be combined with `emit_visit_mut`. The futures are never `Send` (syn nodes aren't), poll them on
+///   the current thread (`block_on`, `spawn_local`, ...).
+///
+///   **Performance**: async functions can't recurse directly, so every call of a generated function or a handler
+///   is boxed (`Box::pin(call).await`), one heap allocation per call (for every node of the syntax tree).
+///   `max_depth` and `{Prefix}EnclosingIdent` are stored per thread, don't interleave multiple traversals on one thread.
+/// - `inline_system_fns` - Optional boolean (default: `false`). When `true`, every generated `{prefix}_*_handle`
+///   function gets `#[inline]`. It's only a hint: the functions are private, so the compiler can already inline them
+///   in release builds, and recursive ones are never fully inlined. Measured with the ignored benchmark in
+///   `tests/inline_system_fns_test.rs` (about 2000 lines, trivial handlers) there was no measurable difference
+///   (~80µs per traversal either way), check your own workload before enabling it.
+/// - `impl_on` - Optional type. Generated functions become methods of an `impl MyVisitor { ... }` block
+///   (`fn prefix_item_handle(&mut self, item: &mut Item)`), and handlers are called as `self.handler(...)`, so
+///   they are methods of `MyVisitor` taking `&mut self` (or `&self`). State shared by all handlers lives in the struct
+///   instead of being threaded through `additional_input_type` (no `.clone()` for every node). Handler signatures
+///   in `default_cases` / `special_cases` can be written with or without the `self` parameter.
+///   Special case handlers continue traversal with `self.prefix_expr_handle(&mut expr)`.
+///   Can't be combined with `handler_trait` or `emit_visit_mut`.
 ///
 /// ## default_cases
 ///
 /// Functions that handle any type matching their parameter signature. Handlers are automatically called
-/// for matching fields, with smart unwrapping of `Box<T>`, `Vec<T>`, and `Punctuated<T, _>`. Note: `Option<T>`
-/// fields are traversed via system-generated functions rather than direct smart unwrapping.
+/// for matching fields, with smart unwrapping of `Box<T>`, `Vec<T>`, and `Punctuated<T, _>`.
 ///
 /// **Collection handling**:
-/// - **2 parameters** (param + context): Iterates collections, calling handler per element
+/// - **2 parameters** (param + context): Iterates collections, calling handler per element.
+///   `Option<T>` (and `Option<Box<T>>`) is treated as a collection with zero or one element,
+///   handler is called once for `Some` and not at all for `None`
 /// - **3+ parameters**: Passes entire collections, enabling multi-field correlation from same node
 ///
 /// Mark with `#[after_system]` to run after traversing child nodes (for post-processing).
+/// Mark with `#[both_phases]` to run both before and after, see [`{Prefix}HandlerPhase` parameter](#prefixhandlerphase-parameter).
 ///
 /// ## special_cases
 ///
@@ -67,6 +149,40 @@ mod helpers;
 /// Like `default_cases`, special case handlers also benefit from smart unwrapping of `Box<T>`,
 /// `Vec<T>`, and `Punctuated<T, _>` when matching function parameters to struct fields.
 ///
+/// ## `{Prefix}EnclosingIdent` parameter
+///
+/// Any default or special case handler can take an extra parameter of type `{Prefix}EnclosingIdent` (by value or by reference),
+/// it isn't matched with fields. It contains the identifier of the closest named item containing the handled node
+/// (`fn`, `struct`, `mod`, `const`, method, trait item, ...), `None` outside of named items. Useful for diagnostics
+/// like "error in function `foo`". `impl` blocks don't have an identifier, methods inside of them still do.
+///
+/// ```rust,ignore
+/// // generated_fn_prefix: "lint"
+/// fn handle_call(call: &mut syn::ExprCall, enclosing: &LintEnclosingIdent, ctx: &mut Context);
+/// ```
+///
+/// `struct {Prefix}EnclosingIdent(pub Option<syn::Ident>)` is generated next to the handlers when at least one of them uses it,
+/// `{Prefix}` is `generated_fn_prefix` in `CamelCase` (`my_lint` → `MyLintEnclosingIdent`), so multiple invocations
+/// can share a module. Current identifier is stored in a thread local, set when entering an item and restored when leaving it.
+///
+/// ## `{Prefix}HandlerPhase` parameter
+///
+/// A default case handler marked with `#[both_phases]` is called twice for every match, before and after
+/// child traversal (instead of declaring a near-duplicate `#[after_system]` handler). It requires an extra
+/// parameter of type `{Prefix}HandlerPhase` (by value, not matched with fields), which is `{Prefix}HandlerPhase::Before`
+/// in the first call and `{Prefix}HandlerPhase::After` in the second one.
+///
+/// ```rust,ignore
+/// // generated_fn_prefix: "lint"
+/// #[both_phases]
+/// fn scope(block: &mut syn::Block, phase: LintHandlerPhase, ctx: &mut Context);
+/// ```
+///
+/// `enum {Prefix}HandlerPhase { Before, After }` is generated next to the handlers when at least one of them uses it,
+/// `{Prefix}` is `generated_fn_prefix` in `CamelCase`, like for `{Prefix}EnclosingIdent`.
+/// `{Prefix}HandlerPhase` parameter in a handler without `#[both_phases]` (or `#[both_phases]` combined with `#[after_system]`)
+/// is a compile error.
+///
 /// # Generated Functions
 ///
 /// The macro generates handler functions for all major syn types:
@@ -85,7 +201,18 @@ mod helpers;
 ///
 /// - **Direct types**: `&mut syn::Expr` matches `expr: Expr`, `Box<Expr>`, `Option<Expr>`
 /// - **Collections** (2-param handlers): `&mut syn::Expr` matches `Vec<Expr>` or `Punctuated<Expr, T>` (iterates per element)
+///   and `Option<Expr>` or `Option<Box<Expr>>` (called only when `Some`)
 /// - **Collections** (3+ param handlers): `&mut Vec<syn::Attribute>` matches as whole value (no iteration)
+/// - **Tokens**: `&mut Option<syn::Token![unsafe]>` matches every `unsafety` field (`fn`, `impl`, `trait`, ...),
+///   for detecting, stripping or adding keywords. The whole `Option` is matched, so the handler is called
+///   when the keyword is missing too. `Token![unsafe]` and `syn::token::Unsafe` are interchangeable
+///   for keyword tokens (the `token::` part is required)
+///
+/// ```rust,ignore
+/// fn strip_unsafe(unsafety: &mut Option<syn::Token![unsafe]>, ctx: &mut Context) {
+///     *unsafety = None;
+/// }
+/// ```
 ///
 /// # Examples
 ///
@@ -157,12 +284,14 @@ mod helpers;
 ///
 /// # Errors and Panics
 ///
-/// Compile-time panics occur when:
-/// - Required `setup` parameters (`generated_fn_prefix`, `additional_input_type`) are missing
+/// Compile errors (pointing at the handler signature) are generated when:
 /// - Handler functions never match any syntax node (signature doesn't match any fields)
 /// - `additional_input_type` appears multiple times in a signature (must be distinct from syn types)
 /// - `system_functions_test: true` enabled and internal functions not invoked (for macro debugging)
 ///
+/// Compile-time panics occur when:
+/// - Required `setup` parameters (`generated_fn_prefix`, `additional_input_type`) are missing
+///
 /// # Limitations
 ///
 /// - **Incomplete coverage**: `TokenStream` fields (e.g., `syn::Macro::tokens`) are not traversed
@@ -177,6 +306,9 @@ mod helpers;
 /// 3. If no special case: calls all matching default handlers → recursively traverses nested nodes
 /// 4. Threads `additional_input` through all calls
 ///
+/// Every generated function starts with a `#[doc = "Traverses `syn::Expr`"]` marker naming the type it handles,
+/// useful when reading the expanded code (`cargo expand`).
+///
 /// # Use Cases
 ///
 /// Syntax tree traversal • Code transformation • Pattern detection • Static analysis • Custom linting • Complex procedural macros
@@ -190,7 +322,7 @@ mod helpers;
 /// - **Multi-field correlation**: Access multiple fields from same node in one handler (e.g., `attrs` + `generics` together)
 /// - **Default vs. special cases**: Default handlers run for all types; special cases override and stop traversal
 /// - **Automatic type matching**: Smart unwrapping of `Box<T>`, `Option<T>`, `Vec<T>`, `Punctuated<T, _>`
-/// - **Pre/post-processing**: Use `#[after_system]` to run handlers after child traversal
+/// - **Pre/post-processing**: Use `#[after_system]` to run handlers after child traversal, `#[both_phases]` for both
 ///
 /// ## Advantages of `syn::visit_mut`
 ///
@@ -0,0 +1,100 @@
+//! Tests for `async_handlers: true` setup option
+
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+use easy_macros_all_syntax_cases::all_syntax_cases;
+use quote::ToTokens;
+
+#[derive(Default)]
+struct Collected {
+    exprs: Vec<String>,
+    calls: Vec<String>,
+}
+
+all_syntax_cases! {
+    setup => {
+        generated_fn_prefix: "async_search",
+        additional_input_type: Collected,
+        shared_additional_input: true,
+        async_handlers: true,
+    }
+    default_cases => {
+        fn async_collect_exprs(expr: &mut syn::Expr, collected: &mut Collected);
+    }
+    special_cases => {
+        fn async_collect_call(call: &mut syn::ExprCall, collected: &mut Collected);
+    }
+}
+
+///Returns `Pending` once before completing, like a real I/O future would
+struct YieldOnce(bool);
+
+impl Future for YieldOnce {
+    type Output = ();
+
+    fn poll(mut self: std::pin::Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+async fn async_collect_exprs(expr: &mut syn::Expr, collected: &mut Collected) {
+    YieldOnce(false).await;
+    collected.exprs.push(expr.to_token_stream().to_string());
+}
+
+async fn async_collect_call(call: &mut syn::ExprCall, collected: &mut Collected) {
+    YieldOnce(false).await;
+    collected
+        .calls
+        .push(call.func.to_token_stream().to_string());
+    //Traversal stops at special cases, continue into arguments manually
+    for arg in call.args.iter_mut() {
+        async_search_expr_handle(arg, collected).await;
+    }
+}
+
+///Minimal executor, polls until the future is ready
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+#[test]
+fn async_handlers_awaited() {
+    let mut collected = Collected::default();
+    let mut item: syn::Item = syn::parse_quote! {
+        fn run() {
+            let value = compute(1 + 2, inner(x));
+            value * 2
+        }
+    };
+
+    block_on(async_search_item_handle(&mut item, &mut collected));
+
+    assert_eq!(collected.calls, vec!["compute", "inner"]);
+    //Same order as without `async_handlers`, arguments of calls are handled by `async_collect_call`
+    assert_eq!(
+        collected.exprs,
+        vec![
+            "compute (1 + 2 , inner (x))",
+            "1",
+            "2",
+            "value * 2",
+            "value",
+            "2"
+        ]
+    );
+}
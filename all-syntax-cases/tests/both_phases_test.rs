@@ -0,0 +1,134 @@
+//! Tests for `#[both_phases]` handlers
+
+use easy_macros_all_syntax_cases::all_syntax_cases;
+use quote::ToTokens;
+use syn::parse_quote;
+
+all_syntax_cases! {
+    setup => {
+        generated_fn_prefix: "phases",
+        additional_input_type: &mut Vec<(PhasesHandlerPhase, String)>
+    }
+    default_cases => {
+        #[both_phases]
+        fn record_block(block: &mut syn::Block, phase: PhasesHandlerPhase, calls: &mut Vec<(PhasesHandlerPhase, String)>);
+    }
+    special_cases => {}
+}
+
+fn record_block(
+    block: &mut syn::Block,
+    phase: PhasesHandlerPhase,
+    calls: &mut Vec<(PhasesHandlerPhase, String)>,
+) {
+    calls.push((phase, block.stmts.len().to_string()));
+}
+
+#[test]
+fn handler_called_before_and_after_children() {
+    let mut item: syn::Item = parse_quote! {
+        fn outer() {
+            let a = 1;
+            { inner(); }
+        }
+    };
+    let mut calls = Vec::new();
+
+    phases_item_handle(&mut item, &mut calls);
+
+    //Outer block (2 statements) wraps the inner one (1 statement)
+    assert_eq!(
+        calls,
+        vec![
+            (PhasesHandlerPhase::Before, "2".to_string()),
+            (PhasesHandlerPhase::Before, "1".to_string()),
+            (PhasesHandlerPhase::After, "1".to_string()),
+            (PhasesHandlerPhase::After, "2".to_string()),
+        ]
+    );
+}
+
+//`{Prefix}HandlerPhase` can be anywhere in the signature, next to `{Prefix}EnclosingIdent` too
+//(second invocation in the same module, generated types don't collide)
+all_syntax_cases! {
+    setup => {
+        generated_fn_prefix: "first",
+        additional_input_type: &mut Vec<String>
+    }
+    default_cases => {
+        #[both_phases]
+        fn record_expr(phase: FirstHandlerPhase, enclosing: FirstEnclosingIdent, expr: &mut syn::Expr, calls: &mut Vec<String>);
+    }
+    special_cases => {}
+}
+
+fn record_expr(
+    phase: FirstHandlerPhase,
+    enclosing: FirstEnclosingIdent,
+    expr: &mut syn::Expr,
+    calls: &mut Vec<String>,
+) {
+    calls.push(format!(
+        "{phase:?} {} in {}",
+        expr.to_token_stream(),
+        enclosing.0.unwrap()
+    ));
+}
+
+#[test]
+fn handler_phase_argument_position() {
+    let mut item: syn::Item = parse_quote! {
+        fn run() {
+            x + 1
+        }
+    };
+    let mut calls = Vec::new();
+
+    first_item_handle(&mut item, &mut calls);
+
+    //Handlers receive fields of the traversed node, both operands are handled before traversing into them
+    assert_eq!(
+        calls,
+        vec![
+            "Before x + 1 in run",
+            "Before x in run",
+            "Before 1 in run",
+            "After x in run",
+            "After 1 in run",
+            "After x + 1 in run",
+        ]
+    );
+}
+
+//Only this invocation's `UserPhasesHandlerPhase` is special, other types with the same suffix are regular parameters
+#[derive(Default)]
+struct UserHandlerPhase {
+    idents: Vec<String>,
+}
+
+all_syntax_cases! {
+    setup => {
+        generated_fn_prefix: "user_phases",
+        additional_input_type: &mut UserHandlerPhase
+    }
+    default_cases => {
+        fn record_ident(ident: &mut syn::Ident, found: &mut UserHandlerPhase);
+    }
+    special_cases => {}
+}
+
+fn record_ident(ident: &mut syn::Ident, found: &mut UserHandlerPhase) {
+    found.idents.push(ident.to_string());
+}
+
+#[test]
+fn suffix_alone_is_not_handler_phase() {
+    let mut item: syn::Item = parse_quote! {
+        struct Point;
+    };
+    let mut found = UserHandlerPhase::default();
+
+    user_phases_item_handle(&mut item, &mut found);
+
+    assert_eq!(found.idents, vec!["Point".to_string()]);
+}
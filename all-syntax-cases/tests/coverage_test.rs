@@ -0,0 +1,61 @@
+//! Tests for `report_coverage: true` setup option
+
+use easy_macros_all_syntax_cases::all_syntax_cases;
+use quote::ToTokens;
+
+#[derive(Default, Debug)]
+struct CoverageContext {
+    count: usize,
+}
+
+all_syntax_cases! {
+    setup => {
+        generated_fn_prefix: "coverage",
+        additional_input_type: &mut CoverageContext,
+        report_coverage: true,
+    }
+    default_cases => {
+        fn coverage_handler(expr: &mut syn::Expr, ctx: &mut CoverageContext);
+    }
+    special_cases => {
+        fn coverage_try(expr_try: &mut syn::ExprTry, ctx: &mut CoverageContext);
+    }
+}
+
+fn coverage_handler(_expr: &mut syn::Expr, ctx: &mut CoverageContext) {
+    ctx.count += 1;
+}
+
+fn coverage_try(_expr_try: &mut syn::ExprTry, _ctx: &mut CoverageContext) {}
+
+fn matched_in(name: &str) -> &'static [&'static str] {
+    COVERAGE_COVERAGE
+        .iter()
+        .find(|(fn_name, _)| *fn_name == name)
+        .map(|(_, matched_in)| *matched_in)
+        .unwrap_or_else(|| panic!("{name} is missing from the coverage summary"))
+}
+
+// Unused handlers are listed with no matches next to the usual compile error,
+// checked in `coverage_lists_unused_handler` unit test of the macro crate
+
+#[test]
+fn coverage_lists_matched_types() {
+    let handler = matched_in("coverage_handler");
+    assert!(handler.contains(&"syn::Expr::Binary"));
+    assert!(handler.contains(&"syn::Item::Const"));
+    // Special case overrides default handlers
+    assert!(!handler.contains(&"syn::Expr::Try"));
+
+    assert_eq!(matched_in("coverage_try"), &["syn::Expr::Try"]);
+}
+
+#[test]
+fn coverage_handlers_still_called() {
+    let mut ctx = CoverageContext::default();
+    let mut expr: syn::Expr = syn::parse_quote! { 1 + 2 };
+
+    coverage_expr_handle(&mut expr, &mut ctx);
+
+    assert!(ctx.count > 0);
+}
@@ -0,0 +1,175 @@
+//! Tests for `{Prefix}EnclosingIdent` handler parameter
+
+use easy_macros_all_syntax_cases::all_syntax_cases;
+use quote::ToTokens;
+use syn::parse_quote;
+
+#[derive(Default)]
+struct Calls {
+    ///(called function, enclosing item)
+    found: Vec<(String, Option<String>)>,
+}
+
+all_syntax_cases! {
+    setup => {
+        generated_fn_prefix: "enclosing",
+        additional_input_type: &mut Calls
+    }
+    default_cases => {}
+    special_cases => {
+        fn record_call(call: &mut syn::ExprCall, enclosing: &EnclosingEnclosingIdent, calls: &mut Calls);
+    }
+}
+
+fn record_call(call: &mut syn::ExprCall, enclosing: &EnclosingEnclosingIdent, calls: &mut Calls) {
+    calls.found.push((
+        call.func.to_token_stream().to_string(),
+        enclosing.0.as_ref().map(|ident| ident.to_string()),
+    ));
+}
+
+fn found(calls: &[(&str, Option<&str>)]) -> Vec<(String, Option<String>)> {
+    calls
+        .iter()
+        .map(|(call, enclosing)| (call.to_string(), enclosing.map(str::to_string)))
+        .collect()
+}
+
+#[test]
+fn call_inside_fn_sees_fn_name() {
+    let mut item: syn::Item = parse_quote! {
+        fn foo() {
+            bar();
+        }
+    };
+    let mut calls = Calls::default();
+
+    enclosing_item_handle(&mut item, &mut calls);
+
+    assert_eq!(calls.found, found(&[("bar", Some("foo"))]));
+}
+
+#[test]
+fn nested_items_restore_outer_name() {
+    let mut file: syn::File = parse_quote! {
+        struct S;
+
+        impl S {
+            fn method(&self) {
+                before();
+                fn inner() {
+                    nested();
+                }
+                after();
+            }
+        }
+
+        const VALUE: u8 = compute();
+    };
+    let mut calls = Calls::default();
+
+    for item in file.items.iter_mut() {
+        enclosing_item_handle(item, &mut calls);
+    }
+
+    assert_eq!(
+        calls.found,
+        found(&[
+            ("before", Some("method")),
+            ("nested", Some("inner")),
+            ("after", Some("method")),
+            ("compute", Some("VALUE")),
+        ])
+    );
+}
+
+#[test]
+fn outside_of_items_is_none() {
+    let mut expr: syn::Expr = parse_quote! { { run(); } };
+    let mut calls = Calls::default();
+
+    enclosing_expr_handle(&mut expr, &mut calls);
+
+    assert_eq!(calls.found, found(&[("run", None)]));
+}
+
+//Second invocation in the same module, `EnclosingLitEnclosingIdent` doesn't collide with `EnclosingEnclosingIdent`
+all_syntax_cases! {
+    setup => {
+        generated_fn_prefix: "enclosing_lit",
+        additional_input_type: &mut Vec<(String, Option<String>)>
+    }
+    default_cases => {
+        fn record_lit(enclosing: EnclosingLitEnclosingIdent, lit: &mut syn::Lit, found: &mut Vec<(String, Option<String>)>);
+    }
+    special_cases => {}
+}
+
+fn record_lit(
+    enclosing: EnclosingLitEnclosingIdent,
+    lit: &mut syn::Lit,
+    found: &mut Vec<(String, Option<String>)>,
+) {
+    found.push((
+        lit.to_token_stream().to_string(),
+        enclosing.0.map(|ident| ident.to_string()),
+    ));
+}
+
+#[test]
+fn default_handler_with_enclosing_ident() {
+    let mut item: syn::Item = parse_quote! {
+        mod config {
+            const PORT: u16 = 8080;
+            fn defaults() -> [u8; 2] {
+                [1, 2]
+            }
+        }
+    };
+    let mut lits = Vec::new();
+
+    enclosing_lit_item_handle(&mut item, &mut lits);
+
+    assert_eq!(
+        lits,
+        found(&[
+            ("8080", Some("PORT")),
+            ("1", Some("defaults")),
+            ("2", Some("defaults")),
+            ("2", Some("defaults")),
+        ])
+    );
+}
+
+//Only this invocation's `EnclosingUserEnclosingIdent` is special, other types with the same suffix are regular parameters
+#[derive(Default)]
+struct UserEnclosingIdent {
+    idents: Vec<String>,
+}
+
+all_syntax_cases! {
+    setup => {
+        generated_fn_prefix: "enclosing_user",
+        additional_input_type: &mut UserEnclosingIdent
+    }
+    default_cases => {
+        fn record_ident(ident: &mut syn::Ident, found: &mut UserEnclosingIdent);
+    }
+    special_cases => {}
+}
+
+fn record_ident(ident: &mut syn::Ident, found: &mut UserEnclosingIdent) {
+    found.idents.push(ident.to_string());
+}
+
+#[test]
+fn suffix_alone_is_not_enclosing_ident() {
+    let mut item: syn::Item = parse_quote! {
+        struct Point;
+    };
+    let mut found = UserEnclosingIdent::default();
+
+    enclosing_user_item_handle(&mut item, &mut found);
+
+    assert_eq!(found.idents, vec!["Point".to_string()]);
+}
@@ -0,0 +1,46 @@
+//! Tests for `expose_handler_list: true` setup option
+
+use easy_macros_all_syntax_cases::all_syntax_cases;
+use quote::ToTokens;
+
+all_syntax_cases! {
+    setup => {
+        generated_fn_prefix: "example",
+        additional_input_type: &mut usize,
+        expose_handler_list: true,
+    }
+    default_cases => {
+        fn count_exprs(expr: &mut syn::Expr, count: &mut usize);
+    }
+    special_cases => {}
+}
+
+fn count_exprs(_expr: &mut syn::Expr, count: &mut usize) {
+    *count += 1;
+}
+
+#[test]
+fn handler_list_contains_generated_functions() {
+    assert!(EXAMPLE_GENERATED_HANDLERS.contains(&"example_item_handle"));
+    assert!(EXAMPLE_GENERATED_HANDLERS.contains(&"example_expr_handle"));
+    assert!(EXAMPLE_GENERATED_HANDLERS.contains(&"example_block_handle"));
+    // User handlers are not generated by the macro
+    assert!(!EXAMPLE_GENERATED_HANDLERS.contains(&"count_exprs"));
+    assert!(
+        EXAMPLE_GENERATED_HANDLERS
+            .iter()
+            .all(|name| name.starts_with("example_") && name.ends_with("_handle"))
+    );
+}
+
+#[test]
+fn listed_handlers_are_callable() {
+    let mut count = 0;
+    let mut item: syn::Item = syn::parse_quote! {
+        fn test() { 1 + 2; }
+    };
+
+    example_item_handle(&mut item, &mut count);
+
+    assert!(count > 0);
+}
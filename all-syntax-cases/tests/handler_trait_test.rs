@@ -0,0 +1,107 @@
+//! Tests for `handler_trait: Type` and `handler_trait_def: trait ... { ... }` setup options
+
+use easy_macros_all_syntax_cases::all_syntax_cases;
+use quote::ToTokens;
+
+#[derive(Default)]
+struct Collected {
+    literals: Vec<String>,
+    calls: Vec<String>,
+}
+
+trait Visitor {
+    fn visit_lit(lit: &mut syn::Lit, collected: &mut Collected);
+    fn visit_call(call: &mut syn::ExprCall, collected: &mut Collected);
+}
+
+struct MyVisitor;
+
+impl Visitor for MyVisitor {
+    fn visit_lit(lit: &mut syn::Lit, collected: &mut Collected) {
+        collected.literals.push(lit.to_token_stream().to_string());
+    }
+
+    fn visit_call(call: &mut syn::ExprCall, collected: &mut Collected) {
+        collected
+            .calls
+            .push(call.func.to_token_stream().to_string());
+        // Special cases stop traversal, continue into the arguments manually
+        for arg in call.args.iter_mut() {
+            trait_expr_handle(arg, collected);
+        }
+    }
+}
+
+all_syntax_cases! {
+    setup => {
+        generated_fn_prefix: "trait",
+        additional_input_type: Collected,
+        shared_additional_input: true,
+        handler_trait: MyVisitor,
+    }
+    default_cases => {
+        fn visit_lit(lit: &mut syn::Lit, collected: &mut Collected);
+    }
+    special_cases => {
+        fn visit_call(call: &mut syn::ExprCall, collected: &mut Collected);
+    }
+}
+
+#[test]
+fn handlers_routed_through_trait() {
+    let mut collected = Collected::default();
+    let mut block: syn::Block = syn::parse_quote! {{
+        let a = 1;
+        run(2, "three");
+    }};
+
+    trait_block_handle(&mut block, &mut collected);
+
+    assert_eq!(collected.literals, vec!["1", "2", "\"three\""]);
+    assert_eq!(collected.calls, vec!["run"]);
+}
+
+struct DefVisitor;
+
+all_syntax_cases! {
+    setup => {
+        generated_fn_prefix: "trait_def",
+        additional_input_type: Collected,
+        shared_additional_input: true,
+        handler_trait: DefVisitor,
+        handler_trait_def: trait DefinedVisitor {
+            fn collect_lit(lit: &mut syn::Lit, collected: &mut Collected);
+            #[special_case]
+            fn collect_call(call: &mut syn::ExprCall, collected: &mut Collected);
+        },
+    }
+}
+
+impl DefinedVisitor for DefVisitor {
+    fn collect_lit(lit: &mut syn::Lit, collected: &mut Collected) {
+        collected.literals.push(lit.to_token_stream().to_string());
+    }
+
+    fn collect_call(call: &mut syn::ExprCall, collected: &mut Collected) {
+        collected
+            .calls
+            .push(call.func.to_token_stream().to_string());
+        for arg in call.args.iter_mut() {
+            trait_def_expr_handle(arg, collected);
+        }
+    }
+}
+
+#[test]
+fn handlers_taken_from_trait_definition() {
+    let mut collected = Collected::default();
+    let mut block: syn::Block = syn::parse_quote! {{
+        let a = 1;
+        run(2, "three");
+    }};
+
+    trait_def_block_handle(&mut block, &mut collected);
+
+    assert_eq!(collected.literals, vec!["1", "2", "\"three\""]);
+    assert_eq!(collected.calls, vec!["run"]);
+}
@@ -0,0 +1,103 @@
+//! Tests for `impl_on: Type` setup option
+
+use easy_macros_all_syntax_cases::all_syntax_cases;
+use quote::ToTokens;
+
+#[derive(Default)]
+struct NodeCounter {
+    exprs: usize,
+    literals: Vec<String>,
+    calls: Vec<String>,
+}
+
+impl NodeCounter {
+    fn count_expr(&mut self, _expr: &mut syn::Expr) {
+        self.exprs += 1;
+    }
+
+    fn count_lit(&mut self, lit: &mut syn::Lit) {
+        self.literals.push(lit.to_token_stream().to_string());
+    }
+
+    fn count_call(&mut self, call: &mut syn::ExprCall) {
+        self.calls.push(call.func.to_token_stream().to_string());
+        // Special cases stop traversal, continue into the arguments manually
+        for arg in call.args.iter_mut() {
+            self.counter_expr_handle(arg);
+        }
+    }
+}
+
+all_syntax_cases! {
+    setup => {
+        generated_fn_prefix: "counter",
+        impl_on: NodeCounter,
+    }
+    default_cases => {
+        fn count_expr(&mut self, expr: &mut syn::Expr);
+        fn count_lit(lit: &mut syn::Lit);
+    }
+    special_cases => {
+        fn count_call(&mut self, call: &mut syn::ExprCall);
+    }
+}
+
+#[test]
+fn stateful_visitor_counts_nodes() {
+    let mut counter = NodeCounter::default();
+    let mut block: syn::Block = syn::parse_quote! {{
+        let a = 1 + 2;
+        run(a, "three");
+    }};
+
+    counter.counter_block_handle(&mut block);
+
+    // `1 + 2`, `1`, `2` and `run(...)`, arguments are handled by `count_call`
+    assert_eq!(counter.exprs, 4);
+    assert_eq!(counter.literals, vec!["1", "2", "\"three\""]);
+    assert_eq!(counter.calls, vec!["run"]);
+}
+
+struct DepthTracker {
+    max_nesting: usize,
+}
+
+impl DepthTracker {
+    fn track_lit(&mut self, _lit: &mut syn::Lit, nesting: usize) {
+        self.max_nesting = self.max_nesting.max(nesting);
+    }
+}
+
+all_syntax_cases! {
+    setup => {
+        generated_fn_prefix: "tracker",
+        additional_input_type: usize,
+        impl_on: DepthTracker,
+    }
+    default_cases => {
+        fn track_lit(lit: &mut syn::Lit, nesting: usize);
+    }
+    special_cases => {
+        fn nest_block(expr_block: &mut syn::ExprBlock, nesting: usize);
+    }
+}
+
+impl DepthTracker {
+    fn nest_block(&mut self, expr_block: &mut syn::ExprBlock, nesting: usize) {
+        self.tracker_block_handle(&mut expr_block.block, nesting + 1);
+    }
+}
+
+#[test]
+fn additional_input_still_passed() {
+    let mut tracker = DepthTracker { max_nesting: 0 };
+    let mut block: syn::Block = syn::parse_quote! {{
+        {
+            { let a = 1; }
+        }
+    }};
+
+    tracker.tracker_block_handle(&mut block, 0);
+
+    assert_eq!(tracker.max_nesting, 2);
+}
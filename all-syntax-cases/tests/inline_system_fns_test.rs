@@ -0,0 +1,110 @@
+//! Tests for `inline_system_fns: true` setup option
+
+use easy_macros_all_syntax_cases::all_syntax_cases;
+use quote::ToTokens;
+
+///Large input, parsing and matching code of this crate
+const LARGE_FILE: &str = include_str!("../src/all_syntax_cases/data.rs");
+
+#[derive(Default)]
+struct Collected {
+    exprs: usize,
+    calls: Vec<String>,
+}
+
+mod inlined {
+    use super::*;
+
+    all_syntax_cases! {
+        setup => {
+            generated_fn_prefix: "inlined",
+            additional_input_type: Collected,
+            shared_additional_input: true,
+            inline_system_fns: true,
+        }
+        default_cases => {
+            fn count_expr(expr: &mut syn::Expr, collected: &mut Collected);
+        }
+        special_cases => {
+            fn collect_call(call: &mut syn::ExprCall, collected: &mut Collected);
+        }
+    }
+
+    pub fn run(file: &mut syn::File, collected: &mut Collected) {
+        for item in file.items.iter_mut() {
+            inlined_item_handle(item, collected);
+        }
+    }
+}
+
+mod not_inlined {
+    use super::*;
+
+    all_syntax_cases! {
+        setup => {
+            generated_fn_prefix: "not_inlined",
+            additional_input_type: Collected,
+            shared_additional_input: true,
+        }
+        default_cases => {
+            fn count_expr(expr: &mut syn::Expr, collected: &mut Collected);
+        }
+        special_cases => {
+            fn collect_call(call: &mut syn::ExprCall, collected: &mut Collected);
+        }
+    }
+
+    pub fn run(file: &mut syn::File, collected: &mut Collected) {
+        for item in file.items.iter_mut() {
+            not_inlined_item_handle(item, collected);
+        }
+    }
+}
+
+fn count_expr(_expr: &mut syn::Expr, collected: &mut Collected) {
+    collected.exprs += 1;
+}
+
+fn collect_call(call: &mut syn::ExprCall, collected: &mut Collected) {
+    collected
+        .calls
+        .push(call.func.to_token_stream().to_string());
+}
+
+#[test]
+fn inlined_same_as_not_inlined() {
+    let mut file: syn::File = syn::parse_str(LARGE_FILE).unwrap();
+
+    let mut inlined = Collected::default();
+    inlined::run(&mut file, &mut inlined);
+    let mut not_inlined = Collected::default();
+    not_inlined::run(&mut file, &mut not_inlined);
+
+    assert!(inlined.exprs > 1000);
+    assert!(!inlined.calls.is_empty());
+    assert_eq!(inlined.exprs, not_inlined.exprs);
+    assert_eq!(inlined.calls, not_inlined.calls);
+}
+
+///Run with `cargo test --release --test inline_system_fns_test -- --ignored --nocapture`
+#[test]
+#[ignore = "benchmark"]
+fn inline_system_fns_benchmark() {
+    let file: syn::File = syn::parse_str(LARGE_FILE).unwrap();
+    const RUNS: usize = 200;
+
+    let measure = |run: fn(&mut syn::File, &mut Collected)| {
+        let mut file = file.clone();
+        let start = std::time::Instant::now();
+        for _ in 0..RUNS {
+            run(&mut file, &mut Collected::default());
+        }
+        start.elapsed() / RUNS as u32
+    };
+
+    //Warm up, then alternate to even out noise
+    measure(not_inlined::run);
+    let not_inlined = measure(not_inlined::run);
+    let inlined = measure(inlined::run);
+    println!("not inlined: {not_inlined:?} per run, inlined: {inlined:?} per run");
+}
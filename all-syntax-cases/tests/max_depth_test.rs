@@ -0,0 +1,85 @@
+//! Tests for `max_depth` and `on_depth_exceeded` setup options
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use easy_macros_all_syntax_cases::all_syntax_cases;
+use quote::ToTokens;
+
+static EXCEEDED_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Default)]
+struct Visited {
+    exprs: usize,
+}
+
+all_syntax_cases! {
+    setup => {
+        generated_fn_prefix: "limited",
+        additional_input_type: Visited,
+        shared_additional_input: true,
+        max_depth: 64,
+        on_depth_exceeded: limited_depth_exceeded,
+    }
+    default_cases => {
+        fn limited_count(expr: &mut syn::Expr, visited: &mut Visited);
+    }
+    special_cases => {}
+}
+
+fn limited_count(_expr: &mut syn::Expr, visited: &mut Visited) {
+    visited.exprs += 1;
+}
+
+fn limited_depth_exceeded(max_depth: usize) {
+    assert_eq!(max_depth, 64);
+    EXCEEDED_CALLS.fetch_add(1, Ordering::SeqCst);
+}
+
+///`(((...(1)...)))` built without parsing, parser would overflow the stack itself
+fn nested_parens(depth: usize) -> syn::Expr {
+    let mut expr: syn::Expr = syn::parse_quote! { 1 };
+    for _ in 0..depth {
+        expr = syn::Expr::Paren(syn::ExprParen {
+            attrs: vec![],
+            paren_token: Default::default(),
+            expr: Box::new(expr),
+        });
+    }
+    expr
+}
+
+///Default drop is recursive too
+fn drop_iteratively(mut expr: syn::Expr) {
+    while let syn::Expr::Paren(paren) = expr {
+        expr = *paren.expr;
+    }
+}
+
+#[test]
+fn traversal_stops_at_max_depth() {
+    let mut expr = nested_parens(200_000);
+    let mut visited = Visited::default();
+
+    limited_expr_handle(&mut expr, &mut visited);
+
+    assert_eq!(visited.exprs, 64);
+    assert_eq!(EXCEEDED_CALLS.load(Ordering::SeqCst), 1);
+
+    drop_iteratively(expr);
+}
+
+#[test]
+fn shallow_expressions_are_fully_traversed() {
+    let mut expr: syn::Expr = syn::parse_quote! { (a + b) * c };
+    let mut visited = Visited::default();
+
+    limited_expr_handle(&mut expr, &mut visited);
+
+    //Handlers are called for nested expressions, not for the passed in one
+    assert_eq!(visited.exprs, 5);
+    //Counter is back at zero, the next traversal has the whole limit available
+    let mut expr = nested_parens(10);
+    let mut visited = Visited::default();
+    limited_expr_handle(&mut expr, &mut visited);
+    assert_eq!(visited.exprs, 10);
+}
@@ -0,0 +1,46 @@
+//! Tests for iterating `Option<T>` fields in 2-param handlers (zero or one call)
+
+use easy_macros_all_syntax_cases::all_syntax_cases;
+use quote::ToTokens;
+use syn::parse_quote;
+
+#[derive(Default, Debug)]
+struct ReturnContext {
+    calls: Vec<String>,
+}
+
+all_syntax_cases! {
+    setup => {
+        generated_fn_prefix: "option_iter",
+        additional_input_type: &mut ReturnContext
+    }
+    default_cases => {
+        fn return_value(expr: &mut syn::Expr, context: &mut ReturnContext);
+    }
+    special_cases => {}
+}
+
+fn return_value(expr: &mut syn::Expr, context: &mut ReturnContext) {
+    context.calls.push(expr.to_token_stream().to_string());
+}
+
+#[test]
+fn option_box_present_calls_handler_once() {
+    // ExprReturn has `expr: Option<Box<Expr>>`
+    let mut return_expr: syn::Expr = parse_quote! { return 42 };
+    let mut context = ReturnContext::default();
+
+    option_iter_expr_handle(&mut return_expr, &mut context);
+
+    assert_eq!(context.calls, vec!["42".to_string()]);
+}
+
+#[test]
+fn option_box_none_skips_handler() {
+    let mut return_expr: syn::Expr = parse_quote! { return };
+    let mut context = ReturnContext::default();
+
+    option_iter_expr_handle(&mut return_expr, &mut context);
+
+    assert!(context.calls.is_empty());
+}
@@ -0,0 +1,63 @@
+//! Tests for `shared_additional_input: true` setup option
+
+use easy_macros_all_syntax_cases::all_syntax_cases;
+use quote::ToTokens;
+
+///Not `Clone`, can't be copied for every node
+#[derive(Default)]
+struct Counter {
+    exprs: usize,
+    idents: Vec<String>,
+}
+
+all_syntax_cases! {
+    setup => {
+        generated_fn_prefix: "shared",
+        additional_input_type: Counter,
+        shared_additional_input: true,
+    }
+    default_cases => {
+        fn shared_count_exprs(expr: &mut syn::Expr, counter: &mut Counter);
+    }
+    special_cases => {
+        fn shared_collect_path(expr_path: &mut syn::ExprPath, counter: &mut Counter);
+    }
+}
+
+fn shared_count_exprs(_expr: &mut syn::Expr, counter: &mut Counter) {
+    counter.exprs += 1;
+}
+
+fn shared_collect_path(expr_path: &mut syn::ExprPath, counter: &mut Counter) {
+    counter
+        .idents
+        .push(expr_path.path.to_token_stream().to_string());
+}
+
+#[test]
+fn shared_counter_accumulates() {
+    let mut counter = Counter::default();
+    let mut expr: syn::Expr = syn::parse_quote! { a + b * (c - 1) };
+
+    shared_expr_handle(&mut expr, &mut counter);
+
+    // With copies every node would count into its own instance
+    // Every sub-expression: `a`, `b * (c - 1)`, `b`, `(c - 1)`, `c - 1`, `c`, `1`
+    assert_eq!(counter.exprs, 7);
+    assert_eq!(counter.idents, vec!["a", "b", "c"]);
+}
+
+#[test]
+fn shared_counter_across_items() {
+    let mut counter = Counter::default();
+    let mut item: syn::Item = syn::parse_quote! {
+        fn test() {
+            let x = first;
+            second(x);
+        }
+    };
+
+    shared_item_handle(&mut item, &mut counter);
+
+    assert_eq!(counter.idents, vec!["first", "second", "x"]);
+}
@@ -0,0 +1,80 @@
+//! Tests for handlers matching concrete `Token![...]` fields
+
+use easy_macros_all_syntax_cases::all_syntax_cases;
+use quote::ToTokens;
+use syn::parse_quote;
+
+#[derive(Default)]
+struct Toggle {
+    ///Remove `unsafe` when `true`, add it when `false`
+    strip: bool,
+    seen: usize,
+}
+
+all_syntax_cases! {
+    setup => {
+        generated_fn_prefix: "token_field",
+        additional_input_type: &mut Toggle
+    }
+    default_cases => {
+        fn toggle_unsafe(unsafety: &mut Option<syn::Token![unsafe]>, toggle: &mut Toggle);
+    }
+    special_cases => {}
+}
+
+fn toggle_unsafe(unsafety: &mut Option<syn::Token![unsafe]>, toggle: &mut Toggle) {
+    toggle.seen += 1;
+    if toggle.strip {
+        *unsafety = None;
+    } else if unsafety.is_none() {
+        *unsafety = Some(Default::default());
+    }
+}
+
+#[test]
+fn token_field_strip_unsafe() {
+    let mut item: syn::Item = parse_quote! {
+        impl Driver {
+            unsafe fn dangerous() {}
+            fn safe() {}
+        }
+    };
+    let mut toggle = Toggle {
+        strip: true,
+        seen: 0,
+    };
+
+    token_field_item_handle(&mut item, &mut toggle);
+
+    let expected: syn::Item = parse_quote! {
+        impl Driver {
+            fn dangerous() {}
+            fn safe() {}
+        }
+    };
+    assert_eq!(
+        item.to_token_stream().to_string(),
+        expected.to_token_stream().to_string()
+    );
+    //`impl` block and both signatures, present or not
+    assert_eq!(toggle.seen, 3);
+}
+
+#[test]
+fn token_field_add_unsafe() {
+    let mut item: syn::Item = parse_quote! {
+        fn dangerous() {}
+    };
+    let mut toggle = Toggle::default();
+
+    token_field_item_handle(&mut item, &mut toggle);
+
+    let expected: syn::Item = parse_quote! {
+        unsafe fn dangerous() {}
+    };
+    assert_eq!(
+        item.to_token_stream().to_string(),
+        expected.to_token_stream().to_string()
+    );
+    assert_eq!(toggle.seen, 1);
+}
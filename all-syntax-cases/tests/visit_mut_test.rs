@@ -0,0 +1,109 @@
+//! Tests for `emit_visit_mut: StructName` setup option
+
+use easy_macros_all_syntax_cases::all_syntax_cases;
+use quote::ToTokens;
+use syn::visit_mut::VisitMut;
+
+#[derive(Default)]
+struct Collected {
+    paths: Vec<String>,
+}
+
+all_syntax_cases! {
+    setup => {
+        generated_fn_prefix: "visitor",
+        additional_input_type: Collected,
+        shared_additional_input: true,
+        emit_visit_mut: PathCollector,
+    }
+    default_cases => {}
+    special_cases => {
+        fn visitor_collect_path(expr_path: &mut syn::ExprPath, collected: &mut Collected);
+    }
+}
+
+fn visitor_collect_path(expr_path: &mut syn::ExprPath, collected: &mut Collected) {
+    collected
+        .paths
+        .push(expr_path.path.to_token_stream().to_string());
+}
+
+all_syntax_cases! {
+    setup => {
+        generated_fn_prefix: "renamer",
+        additional_input_type: String,
+        emit_visit_mut: Renamer,
+    }
+    default_cases => {}
+    special_cases => {
+        fn renamer_rename(expr_path: &mut syn::ExprPath, new_name: String);
+    }
+}
+
+fn renamer_rename(expr_path: &mut syn::ExprPath, new_name: String) {
+    if expr_path.path.is_ident("old") {
+        expr_path.path = syn::Ident::new(&new_name, proc_macro2::Span::call_site()).into();
+    }
+}
+
+#[test]
+fn visit_mut_collects_from_file() {
+    let mut file: syn::File = syn::parse_quote! {
+        fn first() -> u8 {
+            let x = a + b;
+            x
+        }
+
+        mod inner {
+            const C: u8 = d;
+        }
+    };
+
+    let mut collected = Collected::default();
+    PathCollector {
+        additional_input: &mut collected,
+    }
+    .visit_file_mut(&mut file);
+
+    assert_eq!(collected.paths, vec!["a", "b", "x", "d"]);
+}
+
+#[test]
+fn visit_mut_modifies_file() {
+    let mut file: syn::File = syn::parse_quote! {
+        fn f() {
+            call(old, other);
+        }
+    };
+
+    Renamer {
+        additional_input: "new".to_string(),
+    }
+    .visit_file_mut(&mut file);
+
+    let expected: syn::File = syn::parse_quote! {
+        fn f() {
+            call(new, other);
+        }
+    };
+    assert_eq!(
+        file.to_token_stream().to_string(),
+        expected.to_token_stream().to_string()
+    );
+}
+
+#[test]
+fn visit_mut_from_nested_entry_point() {
+    //Visitors are often called on a single node, not the whole file
+    let mut expr: syn::Expr = syn::parse_quote! { if cond { old } else { 1 } };
+
+    Renamer {
+        additional_input: "new".to_string(),
+    }
+    .visit_expr_mut(&mut expr);
+
+    assert_eq!(
+        expr.to_token_stream().to_string(),
+        "if cond { new } else { 1 }"
+    );
+}
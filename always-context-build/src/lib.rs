@@ -78,9 +78,31 @@ fn has_always_context(attrs: &[syn::Attribute]) -> bool {
     }
     false
 }
+///Marker excluding a single function from the build script
+const NO_BUILD_MARKER: &str = "no_always_context_build";
+
+///`#[no_always_context_build]` or `#[cfg_attr(any(), no_always_context_build)]`
+///
+///The marker is only read by the build script, the `cfg_attr` form compiles without anything defining the attribute
+fn has_no_build_marker(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| match &attr.meta {
+        Meta::Path(path) => path.is_ident(NO_BUILD_MARKER),
+        Meta::List(list) if list.path.is_ident("cfg_attr") => {
+            list.tokens.clone().into_iter().any(
+                |token| matches!(token, proc_macro2::TokenTree::Ident(ident) if ident == NO_BUILD_MARKER),
+            )
+        }
+        _ => false,
+    })
+}
+
 ///Returns `true` if the function has `anyhow::Result` return type and does not have `#[always_context]` attribute
+/// (or `#[no_always_context_build]` marker)
 #[always_context]
 fn handle_fn(sig: &syn::Signature, attrs: &[syn::Attribute]) -> anyhow::Result<bool> {
+    if has_no_build_marker(attrs) {
+        return Ok(false);
+    }
     match &sig.output {
         syn::ReturnType::Default => {
             //No anyhow::Result
@@ -110,12 +132,13 @@ fn handle_fn(sig: &syn::Signature, attrs: &[syn::Attribute]) -> anyhow::Result<b
 fn handle_item(item: &syn::Item, file_updates: &mut Option<FileUpdates>) -> anyhow::Result<()> {
     match item {
         syn::Item::Fn(item_fn) => {
-            if handle_fn(
+            let needs_update = handle_fn(
                 #[context(tokens)]
                 &item_fn.sig,
                 #[context(tokens_vec)]
                 &item_fn.attrs,
-            )? {
+            )?;
+            if needs_update {
                 let updates = file_updates.get_or_insert_default();
 
                 updates.updates.push(item_fn.span().start());
@@ -198,21 +221,32 @@ fn handle_item(item: &syn::Item, file_updates: &mut Option<FileUpdates>) -> anyh
     Ok(())
 }
 
+/// Byte offsets of the start of every line in `haystack` (index 0 is line 0)
+///
+/// Lines are split on `\n` (`\r\n` included), same as span line numbers
+fn line_offsets(haystack: &str) -> Vec<usize> {
+    let mut offsets = vec![0];
+    offsets.extend(
+        haystack
+            .bytes()
+            .enumerate()
+            .filter(|(_, b)| *b == b'\n')
+            .map(|(i, _)| i + 1),
+    );
+    offsets
+}
+
 /// # Inputs
+/// `offsets` - result of `line_offsets`
 /// `line` - 0 indexed
 #[always_context]
-fn line_pos(haystack: &str, line: usize) -> anyhow::Result<usize> {
-    let mut regex_str = "^".to_string();
-    for _ in 0..line {
-        regex_str.push_str(r".*((\r\n)|\r|\n)");
-    }
-    let regex = regex::Regex::new(&regex_str)?;
+fn line_pos(offsets: &[usize], line: usize) -> anyhow::Result<usize> {
+    let found = offsets.get(line).copied().with_context(context!(
+        "Finding line failed! | Line count: {}",
+        offsets.len()
+    ))?;
 
-    let found = regex
-        .find_at(haystack, 0)
-        .with_context(context!("Finding line failed! | Regex: {:?}", regex))?;
-
-    Ok(found.end())
+    Ok(found)
 }
 
 /// # handle_file
@@ -252,15 +286,18 @@ fn handle_file(file_path: impl AsRef<Path>) -> anyhow::Result<()> {
     if let Some(updates) = file_updates {
         let mut updates = updates.updates;
         //Sort our lines and reverse them
-        updates.sort_by(|a, b| a.line.cmp(&b.line));
+        updates.sort_by_key(|a| a.line);
         updates.reverse();
 
+        //Insertions go from the last line to the first, so offsets of earlier lines stay valid
+        let offsets = line_offsets(&contents);
+
         //Uses span position info to add #[always_context] to every item on the list
         for start_pos in updates.into_iter() {
             //1 indexed
             let line = start_pos.line;
             //Find position based on line
-            let line_bytes_end = line_pos(&contents, line - 1)?;
+            let line_bytes_end = line_pos(&offsets, line - 1)?;
 
             contents.insert_str(line_bytes_end, "#[always_context]\r\n");
         }
@@ -319,6 +356,11 @@ fn handle_dir(
 ///
 /// `ignore_list` - A list of regex patterns to ignore. The patterns are used on the file path. Path is ignored if match found.
 ///
+/// Single functions are skipped with `#[no_always_context_build]` marker. It isn't a real attribute,
+/// write it as `#[cfg_attr(any(), no_always_context_build)]` so the code still compiles.
+/// Methods with the marker don't make their `impl` or `trait` block receive `#[always_context]`.
+/// When other methods of the block do, `#[always_context]` leaves the marked methods unchanged (like `#[no_context]`).
+///
 pub fn build_result(ignore_list: &[regex::Regex]) -> anyhow::Result<()> {
     // Get the current directory
     let current_dir = std::env::current_dir()?;
@@ -391,3 +433,115 @@ pub fn build_tauri(ignore_list: &[regex::Regex]) {
         panic!("Always Context Build Error: {err:?}");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Previous implementation, kept to compare results
+    fn line_pos_regex(haystack: &str, line: usize) -> usize {
+        let mut regex_str = "^".to_string();
+        for _ in 0..line {
+            regex_str.push_str(r".*((\r\n)|\r|\n)");
+        }
+        let regex = regex::Regex::new(&regex_str).unwrap();
+
+        regex.find_at(haystack, 0).unwrap().end()
+    }
+
+    #[test]
+    fn line_pos_matches_regex() {
+        let mut contents = String::new();
+        for i in 0..2000 {
+            match i % 4 {
+                0 => contents.push_str(&format!("fn item_{i}() -> anyhow::Result<()> {{\n")),
+                1 => contents.push_str(&format!("    let s = \"({i}).*[a-z]+$\";\r\n")),
+                2 => contents.push('\n'),
+                _ => contents.push_str("}\r\n"),
+            }
+        }
+
+        let offsets = line_offsets(&contents);
+        assert_eq!(offsets.len(), 2001);
+
+        //Regex gets too big to compile for far away lines, this is why it was replaced
+        for line in (0..=120).step_by(7) {
+            assert_eq!(
+                line_pos(&offsets, line).unwrap(),
+                line_pos_regex(&contents, line),
+                "line {line}"
+            );
+        }
+
+        //Rest of the file, every line should start right after the previous one
+        let mut expected = 0;
+        for (line, text) in contents.split_inclusive('\n').enumerate() {
+            assert_eq!(line_pos(&offsets, line).unwrap(), expected, "line {line}");
+            expected += text.len();
+        }
+        assert_eq!(line_pos(&offsets, 2000).unwrap(), contents.len());
+    }
+
+    #[test]
+    fn no_build_marker_skips_fn() {
+        let file = syn::parse_file(
+            "fn unmarked() -> anyhow::Result<()> { Ok(()) }\n\
+             #[no_always_context_build]\n\
+             fn marked() -> anyhow::Result<()> { Ok(()) }\n\
+             #[cfg_attr(any(), no_always_context_build)]\n\
+             fn marked_cfg_attr() -> anyhow::Result<()> { Ok(()) }\n\
+             impl S {\n\
+                 #[cfg_attr(any(), no_always_context_build)]\n\
+                 fn method(&self) -> anyhow::Result<()> { Ok(()) }\n\
+             }\n",
+        )
+        .unwrap();
+
+        let mut file_updates = None;
+        for item in file.items.iter() {
+            handle_item(item, &mut file_updates).unwrap();
+        }
+
+        //Only the first (unmarked) function gets `#[always_context]`
+        let lines = file_updates
+            .unwrap()
+            .updates
+            .iter()
+            .map(|pos| pos.line)
+            .collect::<Vec<_>>();
+        assert_eq!(lines, vec![1]);
+    }
+
+    #[test]
+    fn no_build_marker_in_mixed_impl() {
+        let file = syn::parse_file(
+            "impl S {\n\
+                 fn unmarked(&self) -> anyhow::Result<()> { Ok(()) }\n\
+                 #[cfg_attr(any(), no_always_context_build)]\n\
+                 fn marked(&self) -> anyhow::Result<()> { Ok(()) }\n\
+             }\n",
+        )
+        .unwrap();
+
+        let mut file_updates = None;
+        for item in file.items.iter() {
+            handle_item(item, &mut file_updates).unwrap();
+        }
+
+        //`impl` still gets `#[always_context]`, which skips the marked method by itself
+        let lines = file_updates
+            .unwrap()
+            .updates
+            .iter()
+            .map(|pos| pos.line)
+            .collect::<Vec<_>>();
+        assert_eq!(lines, vec![1]);
+    }
+
+    #[test]
+    fn line_pos_out_of_range() {
+        let offsets = line_offsets("fn a() {}\nfn b() {}");
+        assert_eq!(line_pos(&offsets, 1).unwrap(), 10);
+        assert!(line_pos(&offsets, 2).is_err());
+    }
+}
@@ -1,58 +1,173 @@
 mod context_arg;
 
+use std::cell::RefCell;
+
 use all_syntax_cases::all_syntax_cases;
 use context_arg::arg_handle;
+use context_arg::tokens_params;
 use helpers::{CompileErrorProvider, expr_error_wrap, readable_token_stream};
 use proc_macro2::TokenStream;
 use quote::{ToTokens, quote};
 use syn::{Expr, Macro, punctuated::Punctuated, spanned::Spanned};
 
-use crate::context_crate;
+use crate::{context_crate, search::ContextScope};
+
+///How `self` is formatted in context of every `?` inside of a method
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelfFormat {
+    ///`#[context(self)]` or `#[context(self, debug)]`
+    Debug,
+    ///`#[context(self, display)]`
+    Display,
+}
+
+///Receiver of a method with `#[context(self)]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SelfContext {
+    pub format: SelfFormat,
+    ///Receiver is `&self`, context closure can always borrow it
+    ///
+    ///Otherwise (`&mut self`, `self`, ...) `self` is left out of context for `?` whose expression uses `self`,
+    /// the expression could hold a `&mut` borrow of `self` or move it
+    pub shared_receiver: bool,
+}
+
+///Function whose body is currently handled, nested functions get their own
+pub struct FnContext {
+    ///Receiver added to context, set with `#[context(self)]`
+    pub self_context: Option<SelfContext>,
+    ///`#[context(once)]`, `?` inside of a chain already carrying context (`a()?.b()?`) don't get their own
+    pub once: bool,
+    ///Parameters with `syn`/`proc_macro2` types, formatted with `tokens` by default
+    pub tokens_params: Vec<syn::Ident>,
+    ///Names bound by patterns (`let`, closure parameters, match arms, ...) in scopes around the handled node,
+    /// `None` marks start of a scope
+    pub bindings: RefCell<Vec<Option<syn::Ident>>>,
+}
+
+impl FnContext {
+    pub fn new(sig: &syn::Signature, self_context: Option<SelfContext>, once: bool) -> Self {
+        FnContext {
+            self_context,
+            once,
+            tokens_params: tokens_params(sig),
+            bindings: RefCell::new(Vec::new()),
+        }
+    }
+
+    ///`ident` is a parameter with `syn`/`proc_macro2` type, not shadowed by a binding in scope
+    pub fn is_tokens_param(&self, ident: &syn::Ident) -> bool {
+        self.tokens_params.contains(ident)
+            && !self
+                .bindings
+                .borrow()
+                .iter()
+                .any(|binding| binding.as_ref() == Some(ident))
+    }
+}
+
+///`context_internal2!(...)` or the macro set with `#[always_context(macro = ...)]`, creates a context closure
+fn context_macro(
+    question_span: proc_macro2::Span,
+    context_macro_input: proc_macro2::TokenStream,
+    scope: ContextScope,
+) -> syn::Expr {
+    let mac = match scope.args.context_macro.clone() {
+        Some(mut path) => {
+            //`line!()` inside of the user macro should point at the `?` operator
+            for segment in path.segments.iter_mut() {
+                segment.ident.set_span(question_span);
+            }
+            Macro {
+                path,
+                bang_token: syn::parse_quote_spanned! {question_span=> ! },
+                delimiter: syn::MacroDelimiter::Paren(syn::token::Paren(question_span)),
+                tokens: context_macro_input,
+            }
+        }
+        None => {
+            let context_crate = context_crate();
+
+            let line = question_span.span().start().line;
+
+            Macro {
+                path: syn::parse_quote_spanned! {question_span =>
+                    #context_crate::context_internal2
+                },
+                bang_token: Default::default(),
+                delimiter: syn::MacroDelimiter::Paren(Default::default()),
+                tokens: quote! {#line, #context_macro_input},
+            }
+        }
+    };
+
+    Expr::Macro(syn::ExprMacro { attrs: vec![], mac })
+}
 
 fn context_base(
     expr: Box<syn::Expr>,
     question_span: proc_macro2::Span,
     context_macro_input: proc_macro2::TokenStream,
+    scope: ContextScope,
 ) -> Box<syn::Expr> {
     let mut punc: Punctuated<Expr, syn::token::Comma> = Punctuated::new();
 
-    let context_crate = context_crate();
+    let mac = context_macro(question_span, context_macro_input, scope);
 
-    let line = question_span.span().start().line;
-
-    punc.push(Expr::Macro(syn::ExprMacro {
-        attrs: vec![],
-        mac: Macro {
-            path: syn::parse_quote_spanned! {question_span =>
-                #context_crate::context_internal2
-            },
-            bang_token: Default::default(),
-            delimiter: syn::MacroDelimiter::Paren(Default::default()),
-            tokens: quote! {#line, #context_macro_input},
-        },
-    }));
+    //Both built in and user macros create a closure, eager version calls it right away
+    let method = if scope.args.eager {
+        punc.push(syn::parse_quote_spanned! {question_span=> (#mac)() });
+        "context"
+    } else {
+        punc.push(mac);
+        "with_context"
+    };
 
     Box::new(syn::Expr::MethodCall(syn::ExprMethodCall {
         attrs: vec![],
         receiver: expr,
         dot_token: syn::parse_quote_spanned! {question_span=> . },
-        method: quote::format_ident!("with_context", span = question_span),
+        method: quote::format_ident!("{}", method, span = question_span),
         turbofish: None,
         paren_token: Default::default(),
         args: syn::parse_quote_spanned! {question_span=> #punc },
     }))
 }
 
+///`#[context(once)]` version of [`context_no_func_input`], for `?` inside of a chain already carrying context
+///
+///`Result` is left as it is. `Option` has no error which could be propagated, it gets the location as context
+pub fn context_once(
+    expr: Box<syn::Expr>,
+    question_span: proc_macro2::Span,
+    scope: ContextScope,
+) -> Box<syn::Expr> {
+    let context_crate = context_crate();
+    let mac = context_macro(question_span, Default::default(), scope);
+    syn::parse_quote_spanned! {question_span=>
+        #context_crate::ContextOnce::context_once(#expr, #mac)
+    }
+}
+
 pub fn context_no_func_input(
     expr: Box<syn::Expr>,
     question_span: proc_macro2::Span,
+    scope: ContextScope,
 ) -> Box<syn::Expr> {
-    context_base(expr, question_span, Default::default())
+    context_base(expr, question_span, Default::default(), scope)
 }
 
 struct InputFound {
     pub input: TokenStream,
     pub display: bool,
+    ///Shown in the context message instead of `input`
+    pub label: Option<TokenStream>,
+}
+
+impl InputFound {
+    fn label(&self) -> &TokenStream {
+        self.label.as_ref().unwrap_or(&self.input)
+    }
 }
 
 impl quote::ToTokens for InputFound {
@@ -61,7 +176,9 @@ impl quote::ToTokens for InputFound {
     }
 }
 
-struct FoundContextInfo {
+struct FoundContextInfo<'a> {
+    ///Function containing the `?`, its parameters are formatted differently
+    function: Option<&'a FnContext>,
     ///If None show errors on unsupported Expr's
     call_found: Option<TokenStream>,
     ///Contains errors that should be shown by expr_error_wrap with compile_error!()
@@ -73,7 +190,7 @@ struct FoundContextInfo {
     // func_str: Option<String>,
 }
 
-impl CompileErrorProvider for FoundContextInfo {
+impl CompileErrorProvider for FoundContextInfo<'_> {
     fn no_errors(&self) -> bool {
         self.current_errors.is_empty()
     }
@@ -283,8 +400,13 @@ fn context_path_handle(_path: &mut syn::ExprPath, context_info: &mut FoundContex
         .push("Always Context Macro: ExprPath right before '?' is not supported, use `.context` or `.with_context` or `.for_user` or `.with_for_user`\r\n(If you have already used #[no_context] or #[no_context_inputs] ignore this error, this is a little bit buggy but will compile successfully)".to_string());
 }
 
-pub fn context(mut expr: Box<syn::Expr>, question_span: proc_macro2::Span) -> Box<syn::Expr> {
+pub fn context(
+    mut expr: Box<syn::Expr>,
+    question_span: proc_macro2::Span,
+    scope: ContextScope,
+) -> Box<syn::Expr> {
     let mut found_context_info = FoundContextInfo {
+        function: scope.function,
         call_found: None,
         current_errors: vec![],
         inputs_found: vec![],
@@ -296,19 +418,22 @@ pub fn context(mut expr: Box<syn::Expr>, question_span: proc_macro2::Span) -> Bo
     expr_error_wrap(&mut expr, &mut found_context_info);
 
     let mut macro_input = TokenStream::new();
+    let mut call_str = String::new();
+    let mut format_args = Vec::new();
 
     if let Some(call_found) = found_context_info.call_found {
         let inputs_found = found_context_info.inputs_found;
         // This adds default into_token_stream() spaces, which quote!{} macro doesn't do
-        let quote_parsed: syn::Expr = syn::parse_quote! {#call_found(#(#inputs_found),*)};
-        let mut call_str = readable_token_stream(&quote_parsed.into_token_stream().to_string())
+        let labels = inputs_found.iter().map(|input| input.label());
+        let quote_parsed: syn::Expr = syn::parse_quote! {#call_found(#(#labels),*)};
+        call_str = readable_token_stream(&quote_parsed.into_token_stream().to_string())
             .replace('{', "{{")
             .replace('}', "}}");
         if !inputs_found.is_empty() {
             call_str.push_str("\r\n\r\nArguments:\r\n");
             //Add arguments to call_str in format: "argument: {:?}"
             for input in &inputs_found {
-                let formatted = readable_token_stream(&input.to_token_stream().to_string())
+                let formatted = readable_token_stream(&input.label().to_string())
                     .replace('{', "{{")
                     .replace('}', "}}");
 
@@ -320,19 +445,46 @@ pub fn context(mut expr: Box<syn::Expr>, question_span: proc_macro2::Span) -> Bo
             }
         }
 
-        let format_str_arg = syn::LitStr::new(&call_str, question_span);
-        macro_input.extend(quote! { #format_str_arg });
-
-        if !inputs_found.is_empty() {
-            macro_input.extend(quote! { , });
+        format_args.extend(inputs_found.iter().map(|input| input.to_token_stream()));
+    }
 
-            macro_input.extend(quote! { #(#inputs_found),* });
+    //Context closure borrows `self` only when called (on error), `&mut self` borrows or moves of `self`
+    // held by `expr` (`self.items.first_mut()?`) would conflict with it
+    let self_context = scope
+        .function
+        .and_then(|function| function.self_context)
+        .filter(|self_context| {
+            self_context.shared_receiver || !mentions_self(expr.to_token_stream())
+        });
+    if let Some(self_context) = self_context {
+        if !call_str.is_empty() && !call_str.ends_with("\r\n\r\n") {
+            call_str.push_str("\r\n\r\n");
         }
+        call_str.push_str(match self_context.format {
+            SelfFormat::Debug => "self: {:?}",
+            SelfFormat::Display => "self: {}",
+        });
+        format_args.push(quote! { self });
+    }
+
+    if !call_str.is_empty() {
+        let format_str_arg = syn::LitStr::new(&call_str, question_span);
+        macro_input.extend(quote! { #format_str_arg #(, #format_args)* });
     }
 
     context_base(
         expr,
         question_span,
         quote::quote_spanned! {question_span=>#macro_input},
+        scope,
     )
 }
+
+///`self` is used somewhere in `tokens`
+fn mentions_self(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Ident(ident) => ident == "self",
+        proc_macro2::TokenTree::Group(group) => mentions_self(group.stream()),
+        _ => false,
+    })
+}
@@ -1,9 +1,74 @@
 use all_syntax_cases::all_syntax_cases;
+use helpers::find_crate;
 use proc_macro2::TokenStream;
 use quote::{ToTokens, quote, quote_spanned};
 use syn::spanned::Spanned;
 
-use super::{FoundContextInfo, InputFound};
+use super::{FnContext, FoundContextInfo, InputFound};
+
+///Parameters of `sig` with `syn`/`proc_macro2` types, formatted with `tokens` by default
+pub(super) fn tokens_params(sig: &syn::Signature) -> Vec<syn::Ident> {
+    sig.inputs
+        .iter()
+        .filter_map(|input| match input {
+            syn::FnArg::Typed(pat_type) => match &*pat_type.pat {
+                syn::Pat::Ident(pat_ident) if is_tokens_type(&pat_type.ty) => {
+                    Some(pat_ident.ident.clone())
+                }
+                _ => None,
+            },
+            syn::FnArg::Receiver(_) => None,
+        })
+        .collect()
+}
+
+///`syn::...` and `proc_macro2::...` paths implementing `ToTokens` (references included)
+///
+///Only fully qualified paths are detected, `TokenStream` alone could be `proc_macro::TokenStream`
+fn is_tokens_type(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Reference(reference) => is_tokens_type(&reference.elem),
+        syn::Type::Paren(paren) => is_tokens_type(&paren.elem),
+        syn::Type::Path(type_path) if type_path.qself.is_none() => {
+            let segments = &type_path.path.segments;
+            let (Some(first), Some(last)) = (segments.first(), segments.last()) else {
+                return false;
+            };
+            if segments.len() < 2 {
+                return false;
+            }
+            let last = last.ident.to_string();
+            if first.ident == "syn" {
+                //Helper modules and types without `ToTokens`
+                let helper_module = segments.iter().any(|segment| {
+                    matches!(
+                        segment.ident.to_string().as_str(),
+                        "parse" | "buffer" | "spanned" | "visit" | "visit_mut" | "fold" | "ext"
+                    )
+                });
+                !helper_module && last != "Result" && last != "Error"
+            } else if first.ident == "proc_macro2" {
+                matches!(
+                    last.as_str(),
+                    "TokenStream" | "TokenTree" | "Ident" | "Literal" | "Group" | "Punct"
+                )
+            } else {
+                false
+            }
+        }
+        _ => false,
+    }
+}
+
+///`ident`, `&ident` or `&mut ident`
+fn arg_ident(arg: &syn::Expr) -> Option<&syn::Ident> {
+    match arg {
+        syn::Expr::Path(expr_path) if expr_path.qself.is_none() => expr_path.path.get_ident(),
+        syn::Expr::Reference(reference) => arg_ident(&reference.expr),
+        syn::Expr::Paren(paren) => arg_ident(&paren.expr),
+        _ => None,
+    }
+}
 
 struct ArgData {
     display_fn_call: TokenStream,
@@ -14,6 +79,8 @@ struct ArgData {
     duplicate: bool,
     /// Ignore argument
     ignore: bool,
+    ///Formatting was chosen with `#[context(...)]`, automatic `tokens` formatting is not used
+    explicit_format: bool,
 }
 
 all_syntax_cases! {
@@ -71,18 +138,25 @@ fn handle_arg_attrs(attrs: &mut Vec<syn::Attribute>, data: &mut ArgData) {
             match tokens_str_no_space.as_str() {
                 "display" => {
                     data.display = true;
+                    data.explicit_format = true;
+                    to_remove.push(index);
+                }
+                "debug" => {
+                    data.explicit_format = true;
                     to_remove.push(index);
                 }
                 "tokens" => {
                     data.display = true;
                     let tokens_span = tokens.span();
                     data.display_fn_call = quote_spanned! {tokens_span=> .to_token_stream() };
+                    data.explicit_format = true;
                     to_remove.push(index);
                 }
                 "tokens_vec" => {
                     data.display = true;
                     let tokens_span = tokens.span();
                     data.display_fn_call = quote_spanned! {tokens_span=> .iter().map(|el|el.to_token_stream()).collect::<TokenStream>() };
+                    data.explicit_format = true;
                     to_remove.push(index);
                 }
                 #[cfg(feature = "easy-sql")]
@@ -97,6 +171,7 @@ fn handle_arg_attrs(attrs: &mut Vec<syn::Attribute>, data: &mut ArgData) {
                 _ => {
                     if tokens_str_no_space.starts_with(".") {
                         data.display_fn_call = tokens.clone();
+                        data.explicit_format = true;
                         to_remove.push(index);
                     }
                 }
@@ -116,6 +191,7 @@ pub fn arg_handle(arg: &mut syn::Expr, context_info: &mut FoundContextInfo) {
         not_sql: false,
         duplicate: false,
         ignore: false,
+        explicit_format: false,
     };
 
     let mut arg_cloned = arg.clone();
@@ -128,6 +204,16 @@ pub fn arg_handle(arg: &mut syn::Expr, context_info: &mut FoundContextInfo) {
         context_info.inputs_found.push(InputFound {
             input: quote! {"ignored"},
             display: true,
+            label: None,
+        })
+    } else if let Some(quote_crate) =
+        auto_tokens_crate(&arg_cloned, data.explicit_format, context_info.function)
+    {
+        //Fully qualified, `ToTokens` doesn't have to be imported
+        context_info.inputs_found.push(InputFound {
+            input: quote! {#quote_crate::ToTokens::to_token_stream(&(#arg_cloned))},
+            display: true,
+            label: Some(quote! {(#arg_cloned)}),
         })
     } else {
         let display_fn_call = data.display_fn_call;
@@ -135,6 +221,26 @@ pub fn arg_handle(arg: &mut syn::Expr, context_info: &mut FoundContextInfo) {
         context_info.inputs_found.push(InputFound {
             input: quote! {(#arg_cloned) #display_fn_call},
             display: data.display,
+            label: None,
         })
     };
 }
+
+///Path to the `quote` crate if `arg` should be formatted with `tokens` automatically
+///
+///`None` if formatting was chosen explicitly, the argument isn't a `syn`/`proc_macro2` typed parameter
+/// (or the parameter is shadowed, `let sig = 5; f(sig)?`) or `quote` is not in the dependencies
+fn auto_tokens_crate(
+    arg: &syn::Expr,
+    explicit_format: bool,
+    function: Option<&FnContext>,
+) -> Option<TokenStream> {
+    if explicit_format {
+        return None;
+    }
+    let ident = arg_ident(arg)?;
+    if !function?.is_tokens_param(ident) {
+        return None;
+    }
+    find_crate("quote", quote! {})
+}
@@ -1,11 +1,52 @@
 mod context_gen;
 mod search;
 
-use helpers::find_crate_list;
+#[cfg(test)]
+mod tests;
+
+use helpers::find_reexport;
 use proc_macro::TokenStream;
 use quote::{ToTokens, quote};
 use search::item_handle;
 
+///`#[always_context(...)]` arguments
+#[derive(Default)]
+struct AlwaysContextArgs {
+    ///`macro = path`, used instead of the built in context macro
+    context_macro: Option<syn::Path>,
+    ///`eager`, `.context(...)` is generated instead of `.with_context(...)`
+    eager: bool,
+}
+
+impl syn::parse::Parse for AlwaysContextArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut args = AlwaysContextArgs::default();
+
+        while !input.is_empty() {
+            if input.peek(syn::Token![macro]) {
+                let _macro_token: syn::Token![macro] = input.parse()?;
+                let _eq: syn::Token![=] = input.parse()?;
+                args.context_macro = Some(input.parse()?);
+            } else {
+                let ident: syn::Ident = input.parse()?;
+                if ident != "eager" {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "expected `macro = path` or `eager`",
+                    ));
+                }
+                args.eager = true;
+            }
+
+            if !input.is_empty() {
+                let _comma: syn::Token![,] = input.parse()?;
+            }
+        }
+
+        Ok(args)
+    }
+}
+
 fn crate_missing_panic(crate_name: &str, for_macro: &str) -> ! {
     panic!(
         "Using {for_macro} requires `{crate_name}` (or `easy-macros` crate) to be present in dependencies! You can add it with `{crate_name} = \"*\"` in your Cargo.toml dependencies or with `cargo add {crate_name}` command."
@@ -13,10 +54,13 @@ fn crate_missing_panic(crate_name: &str, for_macro: &str) -> ! {
 }
 
 fn context_crate() -> proc_macro2::TokenStream {
-    if let Some(found) = find_crate_list(&[
-        ("easy-macros", quote! {}),
-        ("easy-macros-helpers", quote! {}),
-    ]) {
+    if let Some(found) = find_reexport(
+        &[
+            ("easy-macros", quote! {}),
+            ("easy-macros-helpers", quote! {}),
+        ],
+        "",
+    ) {
         found
     } else {
         crate_missing_panic("easy-macros-helpers", "always_context");
@@ -28,6 +72,7 @@ fn context_crate() -> proc_macro2::TokenStream {
 ///
 /// Transforms `operation()?` into `operation().with_context(context!("operation()"))?`
 /// with function call details, arguments, and file location.
+/// `?` on `Option` is handled the same way (`anyhow::Context` is implemented for `Option` too).
 ///
 /// # Requirements
 ///
@@ -38,26 +83,66 @@ fn context_crate() -> proc_macro2::TokenStream {
 /// ## Function-level
 /// - `#[no_context]` - Disable context generation entirely
 /// - `#[no_context_inputs]` - Add context but exclude function arguments  
-/// - `#[enable_context]` - Re-enable context (useful in macros where auto-disabled)
+/// - `#[enable_context]` - Re-enable context (useful in macros where auto-disabled). Macros whose
+///   input isn't a statement are left unchanged
+///
+/// - `#[instrument]` / `#[tracing::instrument(...)]` - The span already records arguments, so the function is
+///   handled like `#[no_context_inputs]` (location only). `#[enable_context]` brings back full context
+///
+/// - `#[context(once)]` - In chains like `a()?.b()?` add context only to the outermost `?`, inner `Result`s are left
+///   as they are. Inner `Option`s (no error to propagate) get only the location. Also usable on `impl`/`trait`
+///
+/// ## Method-level
+/// - `#[context(self)]` / `#[context(self, debug)]` - Add `self` (formatted with `Debug`) to context of every `?` in the method
+/// - `#[context(self, display)]` - Same, formatted with `Display`
+/// - `#[no_context(self)]` - Don't add `self`, overrides `#[context(self)]` placed on the `impl`/`trait`
+/// - `#[cfg_attr(any(), no_always_context_build)]` - always-context-build marker, the method is left unchanged
+///   like with `#[no_context]` when the `impl`/`trait` gets `#[always_context]` because of other methods
+///
+/// On an `impl`/`trait` block these apply to every method with a receiver. Using `#[context(self)]` on a
+/// method without one is an error. `self` is formatted only when an error occurs. With `&mut self`, `self`
+/// and other non `&self` receivers, `self` is left out of context of a `?` whose expression uses `self`
+/// (like `self.items.first_mut()?`), context borrowing `self` would conflict with the expression.
+///
+/// ## Region-level
+/// - `no_context! { ... }` - Disable context for several statements at once. Statements are inlined
+///   back into the surrounding block (`let` bindings stay visible after the region). A region which doesn't
+///   parse as statements is replaced with a `compile_error!` pointing at the problem
 ///
 /// ## Argument-level
 /// - `#[context(display)]` - Use `Display` instead of `Debug` for formatting
 /// - `#[context(.method())]` - Call method on argument before displaying
 /// - `#[context(tokens)]` - Format as token stream (equivalent to `display` + `.to_token_stream()`)
 /// - `#[context(tokens_vec)]` - Format as token stream collection
+/// - `#[context(debug)]` - Use `Debug` formatting, turns off automatic `tokens` formatting
+///
+/// Arguments which are parameters of the surrounding function typed with a fully qualified `syn::...` or
+/// `proc_macro2::...` type (for example `sig: &syn::Signature`) are formatted with `tokens` automatically,
+/// unless one of the attributes above is used or the parameter is shadowed (`let sig = 5; f(sig)?`).
+/// Requires `quote` in dependencies.
+///
 /// - `#[context(not_sql)]` - Use on `sql!` and `query!` macros if not part of `easy_sql` (requires `easy-sql` feature)
 /// - `#[context(ignore)]` or `#[context(ignored)]` or `#[context(no)]` - Exclude this argument from context
 ///
+/// ## Attribute Arguments
+/// - `#[always_context(macro = my_ctx)]` - Generate `my_ctx!(...)` calls instead of the built in context macro.
+///   The macro receives the same input as [`context!`](https://docs.rs/easy-macros-helpers/latest/easy_macros_helpers/macro.context.html)
+///   (nothing or a format string with arguments) and should return a closure creating the context
+/// - `#[always_context(eager)]` - Generate `.context(context!(...)())` instead of `.with_context(context!(...))`,
+///   the message is formatted even when there is no error. For cheap messages or types implementing only
+///   `.context(...)`. Can be combined with `macro = ...` (`#[always_context(eager, macro = my_ctx)]`)
+///
 /// # Limitations
 ///
 /// These expressions before `?` require manual `.with_context()` or `.context()`:
 /// blocks, control flow (`if`/`match`/`while`/`for`/`loop`), field access, macros.
-pub fn always_context(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn always_context(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = syn::parse_macro_input!(attr as AlwaysContextArgs);
     let mut parsed = syn::parse_macro_input!(item as syn::Item);
     //Adds .with_context(context!()) before all '?' without them
     //Maybe add also function inputs with names into context?
 
-    item_handle(&mut parsed, None);
+    item_handle(&mut parsed, &args);
 
     parsed.into_token_stream().into()
 }
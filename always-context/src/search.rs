@@ -1,8 +1,13 @@
 use all_syntax_cases::all_syntax_cases;
 use quote::ToTokens;
-use syn::{ItemImpl, ItemTrait, PathArguments, TraitItem, Type, spanned::Spanned};
+use syn::{ItemFn, ItemImpl, ItemTrait, PathArguments, TraitItem, Type, spanned::Spanned};
 
-use crate::context_gen::{context, context_no_func_input};
+use crate::{
+    AlwaysContextArgs,
+    context_gen::{
+        FnContext, SelfContext, SelfFormat, context, context_no_func_input, context_once,
+    },
+};
 
 #[derive(Debug, Clone, Copy)]
 pub enum NoContext {
@@ -16,16 +21,58 @@ pub enum NoContext {
     EnableBack,
 }
 
+///State of the `#[always_context]` expansion, passed through the whole traversal
+///
+///Copied for every syntax node, changes made while handling a node (like `#[no_context]`) apply only to its children
+#[derive(Clone, Copy)]
+pub struct ContextScope<'a> {
+    ///`#[always_context(...)]` arguments
+    pub args: &'a AlwaysContextArgs,
+    ///Function whose body is handled, `None` outside of function bodies
+    pub function: Option<&'a FnContext>,
+    pub no_context: Option<NoContext>,
+}
+
+///Marker of always-context-build excluding a function from the build script
+const NO_BUILD_MARKER: &str = "no_always_context_build";
+
+///`#[no_always_context_build]` or `#[cfg_attr(..., no_always_context_build)]`
+fn is_no_build_marker(attr: &syn::Attribute) -> bool {
+    match &attr.meta {
+        syn::Meta::Path(path) => path.is_ident(NO_BUILD_MARKER),
+        syn::Meta::List(list) if list.path.is_ident("cfg_attr") => {
+            list.tokens.clone().into_iter().any(
+                |token| matches!(token, proc_macro2::TokenTree::Ident(ident) if ident == NO_BUILD_MARKER),
+            )
+        }
+        _ => false,
+    }
+}
+
 fn always_context_attr_check(attrs: &mut Vec<syn::Attribute>) -> Option<NoContext> {
     for (index, attr) in attrs.iter().enumerate() {
-        let attr_str = attr.to_token_stream().to_string();
-        if attr_str == "#[no_context]" {
+        if !matches!(attr.style, syn::AttrStyle::Outer) {
+            continue;
+        }
+        //Marked method inside of `impl`/`trait` which got `#[always_context]` because of other methods,
+        // works like `#[no_context]`. Bare marker isn't a real attribute, `cfg_attr` form compiles to nothing
+        if is_no_build_marker(attr) {
+            if matches!(attr.meta, syn::Meta::Path(_)) {
+                attrs.remove(index);
+            }
+            return Some(NoContext::All);
+        }
+        //Only bare outer attributes (`#[no_context]`), compared structurally so spacing doesn't matter
+        let syn::Meta::Path(path) = &attr.meta else {
+            continue;
+        };
+        if path.is_ident("no_context") {
             attrs.remove(index);
             return Some(NoContext::All);
-        } else if attr_str == "#[no_context_inputs]" {
+        } else if path.is_ident("no_context_inputs") {
             attrs.remove(index);
             return Some(NoContext::NoFuncInput);
-        } else if attr_str == "#[enable_context]" {
+        } else if path.is_ident("enable_context") {
             attrs.remove(index);
             return Some(NoContext::EnableBack);
         }
@@ -33,30 +80,289 @@ fn always_context_attr_check(attrs: &mut Vec<syn::Attribute>) -> Option<NoContex
     None
 }
 
+///`#[context(self)]`, `#[context(self, display)]` or `#[no_context(self)]`, removed from `attrs`
+///
+///`Some(None)` means `#[no_context(self)]`
+fn self_context_attr_check(attrs: &mut Vec<syn::Attribute>) -> Option<Option<SelfFormat>> {
+    for (index, attr) in attrs.iter().enumerate() {
+        let syn::Meta::List(list) = &attr.meta else {
+            continue;
+        };
+        let tokens = list
+            .tokens
+            .to_string()
+            .replace(|c: char| c.is_whitespace(), "");
+        let found = if list.path.is_ident("context") {
+            match tokens.as_str() {
+                "self" | "self,debug" => Some(Some(SelfFormat::Debug)),
+                "self,display" => Some(Some(SelfFormat::Display)),
+                _ => None,
+            }
+        } else if list.path.is_ident("no_context") && tokens == "self" {
+            Some(None)
+        } else {
+            None
+        };
+        if let Some(found) = found {
+            attrs.remove(index);
+            return Some(found);
+        }
+    }
+    None
+}
+
+///`#[context(once)]`, removed from `attrs`
+fn context_once_attr_check(attrs: &mut Vec<syn::Attribute>) -> bool {
+    let position = attrs.iter().position(|attr| match &attr.meta {
+        syn::Meta::List(list) => {
+            list.path.is_ident("context") && list.tokens.to_string().trim() == "once"
+        }
+        _ => false,
+    });
+    if let Some(index) = position {
+        attrs.remove(index);
+    }
+    position.is_some()
+}
+
+///Receiver formatting for a method, method attribute overrides the one from `impl`/`trait`
+fn method_self_context(
+    attrs: &mut Vec<syn::Attribute>,
+    sig: &syn::Signature,
+    outer: Option<SelfFormat>,
+) -> Option<SelfContext> {
+    let receiver = sig.receiver();
+    let format = match self_context_attr_check(attrs) {
+        Some(Some(_)) if receiver.is_none() => {
+            panic!(
+                "#[context(self)] used on `{}`, which has no `self` receiver",
+                sig.ident
+            )
+        }
+        Some(self_format) => self_format,
+        //From `impl`/`trait` level, only for methods with a receiver
+        None => outer,
+    };
+    let receiver = receiver?;
+    Some(SelfContext {
+        format: format?,
+        shared_receiver: matches!(
+            &*receiver.ty,
+            syn::Type::Reference(reference) if reference.mutability.is_none()
+        ),
+    })
+}
+
 //always_context syntax cases
 all_syntax_cases! {
     setup => {
         generated_fn_prefix: "always_context",
-        additional_input_type: Option<NoContext>
+        additional_input_type: ContextScope
     }
     default_cases => {
-        fn handle_attributes(attrs: &mut Vec<syn::Attribute>, no_context: &mut Option<NoContext>);
+        fn handle_attributes(attrs: &mut Vec<syn::Attribute>, scope: &mut ContextScope);
+        #[after_system]
+        fn local_bindings(local: &mut syn::Local, scope: ContextScope);
+        #[both_phases]
+        fn block_binding_scope(block: &mut syn::Block, phase: AlwaysContextHandlerPhase, scope: ContextScope);
+        #[both_phases]
+        fn closure_binding_scope(closure: &mut syn::ExprClosure, phase: AlwaysContextHandlerPhase, scope: ContextScope);
+        #[both_phases]
+        fn arm_binding_scope(arm: &mut syn::Arm, phase: AlwaysContextHandlerPhase, scope: ContextScope);
+        #[both_phases]
+        fn for_binding_scope(for_loop: &mut syn::ExprForLoop, phase: AlwaysContextHandlerPhase, scope: ContextScope);
+        #[both_phases]
+        fn if_binding_scope(if_: &mut syn::ExprIf, phase: AlwaysContextHandlerPhase, scope: ContextScope);
+        #[both_phases]
+        fn while_binding_scope(while_: &mut syn::ExprWhile, phase: AlwaysContextHandlerPhase, scope: ContextScope);
     }
     special_cases => {
-        fn always_context_try(expr_try: &mut syn::ExprTry, no_context: Option<NoContext>);
-        fn always_context_macro(macro_: &mut syn::Macro, attrs: &mut Vec<syn::Attribute>);
-        fn always_context_item_trait(item_trait: &mut ItemTrait, no_context: Option<NoContext>);
-        fn always_context_item_impl(item_impl: &mut ItemImpl, no_context: Option<NoContext>);
+        fn always_context_try(expr_try: &mut syn::ExprTry, scope: ContextScope);
+        fn always_context_macro(macro_: &mut syn::Macro, attrs: &mut Vec<syn::Attribute>, scope: ContextScope);
+        fn always_context_item_fn(item_fn: &mut ItemFn, scope: ContextScope);
+        fn always_context_item_trait(item_trait: &mut ItemTrait, scope: ContextScope);
+        fn always_context_item_impl(item_impl: &mut ItemImpl, scope: ContextScope);
     }
 }
 
-fn handle_attributes(attrs: &mut Vec<syn::Attribute>, no_context: &mut Option<NoContext>) {
+fn handle_attributes(attrs: &mut Vec<syn::Attribute>, scope: &mut ContextScope) {
     if let Some(no_c) = always_context_attr_check(attrs) {
-        *no_context = Some(no_c);
+        scope.no_context = Some(no_c);
+    }
+}
+
+///Names bound by `pat`
+fn pat_bindings(pat: &syn::Pat, bindings: &mut Vec<Option<syn::Ident>>) {
+    match pat {
+        syn::Pat::Ident(pat_ident) => {
+            bindings.push(Some(pat_ident.ident.clone()));
+            if let Some((_, subpat)) = &pat_ident.subpat {
+                pat_bindings(subpat, bindings);
+            }
+        }
+        syn::Pat::Or(pat_or) => pat_or
+            .cases
+            .iter()
+            .for_each(|case| pat_bindings(case, bindings)),
+        syn::Pat::Paren(paren) => pat_bindings(&paren.pat, bindings),
+        syn::Pat::Reference(reference) => pat_bindings(&reference.pat, bindings),
+        syn::Pat::Slice(slice) => slice
+            .elems
+            .iter()
+            .for_each(|elem| pat_bindings(elem, bindings)),
+        syn::Pat::Struct(pat_struct) => pat_struct
+            .fields
+            .iter()
+            .for_each(|field| pat_bindings(&field.pat, bindings)),
+        syn::Pat::Tuple(tuple) => tuple
+            .elems
+            .iter()
+            .for_each(|elem| pat_bindings(elem, bindings)),
+        syn::Pat::TupleStruct(tuple_struct) => tuple_struct
+            .elems
+            .iter()
+            .for_each(|elem| pat_bindings(elem, bindings)),
+        syn::Pat::Type(pat_type) => pat_bindings(&pat_type.pat, bindings),
+        _ => {}
+    }
+}
+
+///Names bound by `let` in `if`/`while` conditions (`if let Some(x) = a && let Ok(y) = b`)
+fn cond_bindings(cond: &syn::Expr, bindings: &mut Vec<Option<syn::Ident>>) {
+    match cond {
+        syn::Expr::Let(expr_let) => pat_bindings(&expr_let.pat, bindings),
+        syn::Expr::Binary(binary) if matches!(binary.op, syn::BinOp::And(_)) => {
+            cond_bindings(&binary.left, bindings);
+            cond_bindings(&binary.right, bindings);
+        }
+        syn::Expr::Paren(paren) => cond_bindings(&paren.expr, bindings),
+        _ => {}
+    }
+}
+
+///Names bound by `let` shadow parameters of the function (`let sig = 5; f(sig)?`) until the end of the block
+///
+///After system, so `?` inside of the initializer (`let sig = f(sig)?`) still sees the parameter
+fn local_bindings(local: &mut syn::Local, scope: ContextScope) {
+    if let Some(function) = scope.function {
+        pat_bindings(&local.pat, &mut function.bindings.borrow_mut());
+    }
+}
+
+///Starts a scope with names added by `bind` (before) or removes bindings made inside of it (after)
+///
+///Fields of a node aren't visited in order (match arm body can be handled before its pattern),
+/// so bindings of the scope are added before anything inside of it is handled
+fn binding_scope(
+    phase: AlwaysContextHandlerPhase,
+    scope: ContextScope,
+    bind: impl FnOnce(&mut Vec<Option<syn::Ident>>),
+) {
+    let Some(function) = scope.function else {
+        return;
+    };
+    let mut bindings = function.bindings.borrow_mut();
+    match phase {
+        AlwaysContextHandlerPhase::Before => {
+            bindings.push(None);
+            bind(&mut bindings);
+        }
+        AlwaysContextHandlerPhase::After => while let Some(Some(_)) = bindings.pop() {},
+    }
+}
+
+fn block_binding_scope(
+    _block: &mut syn::Block,
+    phase: AlwaysContextHandlerPhase,
+    scope: ContextScope,
+) {
+    binding_scope(phase, scope, |_| {});
+}
+
+fn closure_binding_scope(
+    closure: &mut syn::ExprClosure,
+    phase: AlwaysContextHandlerPhase,
+    scope: ContextScope,
+) {
+    binding_scope(phase, scope, |bindings| {
+        closure
+            .inputs
+            .iter()
+            .for_each(|pat| pat_bindings(pat, bindings))
+    });
+}
+
+fn arm_binding_scope(arm: &mut syn::Arm, phase: AlwaysContextHandlerPhase, scope: ContextScope) {
+    binding_scope(phase, scope, |bindings| pat_bindings(&arm.pat, bindings));
+}
+
+fn for_binding_scope(
+    for_loop: &mut syn::ExprForLoop,
+    phase: AlwaysContextHandlerPhase,
+    scope: ContextScope,
+) {
+    binding_scope(phase, scope, |bindings| {
+        pat_bindings(&for_loop.pat, bindings)
+    });
+}
+
+fn if_binding_scope(if_: &mut syn::ExprIf, phase: AlwaysContextHandlerPhase, scope: ContextScope) {
+    binding_scope(phase, scope, |bindings| cond_bindings(&if_.cond, bindings));
+}
+
+fn while_binding_scope(
+    while_: &mut syn::ExprWhile,
+    phase: AlwaysContextHandlerPhase,
+    scope: ContextScope,
+) {
+    binding_scope(phase, scope, |bindings| {
+        cond_bindings(&while_.cond, bindings)
+    });
+}
+
+///`#[instrument]` or `#[tracing::instrument(...)]` (any path ending with `instrument`) on a function
+/// already records its arguments in a span, so by default only location is added to the context
+///
+///Explicit `#[no_context]`, `#[no_context_inputs]` or `#[enable_context]` takes precedence
+fn instrumented_check(attrs: &[syn::Attribute], no_context: &mut Option<NoContext>) {
+    if no_context.is_some() {
+        return;
+    }
+    let instrumented = attrs.iter().any(|attr| {
+        matches!(attr.style, syn::AttrStyle::Outer)
+            && attr
+                .path()
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "instrument")
+    });
+    if instrumented {
+        *no_context = Some(NoContext::NoFuncInput);
     }
 }
 
-fn always_context_macro(macro_: &mut syn::Macro, attrs: &mut Vec<syn::Attribute>) {
+fn always_context_macro(
+    macro_: &mut syn::Macro,
+    attrs: &mut Vec<syn::Attribute>,
+    scope: ContextScope,
+) {
+    //`no_context! { ... }` regions are left as they are, inlined later by `inline_no_context_regions`
+    if macro_.path.is_ident("no_context") {
+        //Inlined `let` bindings are visible after the region, handled copy is only used to find them
+        if let Ok(mut stmts) = macro_.parse_body_with(syn::Block::parse_within) {
+            for stmt in stmts.iter_mut() {
+                always_context_stmt_handle(
+                    stmt,
+                    ContextScope {
+                        no_context: Some(NoContext::All),
+                        ..scope
+                    },
+                );
+            }
+        }
+        return;
+    }
+
     //Enable only if we have #[enable_context], support only for stmts (statements)
     let mut no_context = NoContext::All;
     if let Some(no_c) = always_context_attr_check(attrs) {
@@ -67,26 +373,26 @@ fn always_context_macro(macro_: &mut syn::Macro, attrs: &mut Vec<syn::Attribute>
         return;
     }
 
-    let mut parsed = match syn::parse2::<syn::Stmt>(macro_.tokens.clone()) {
-        Ok(parsed) => parsed,
-        Err(e) => {
-            panic!(
-                "Expected Statement, error while parsing: {} | tried to parse: {}",
-                e,
-                macro_.to_token_stream()
-            );
-        }
+    //Not a statement (custom macro syntax), leave the macro as it is instead of failing the whole item
+    let Ok(mut parsed) = syn::parse2::<syn::Stmt>(macro_.tokens.clone()) else {
+        return;
     };
 
-    always_context_stmt_handle(&mut parsed, Some(no_context));
+    always_context_stmt_handle(
+        &mut parsed,
+        ContextScope {
+            no_context: Some(no_context),
+            ..scope
+        },
+    );
 
     macro_.tokens = parsed.into_token_stream();
 }
 
-fn always_context_try(expr: &mut syn::ExprTry, mut no_context: Option<NoContext>) {
-    handle_attributes(&mut expr.attrs, &mut no_context);
+fn always_context_try(expr: &mut syn::ExprTry, mut scope: ContextScope) {
+    handle_attributes(&mut expr.attrs, &mut scope);
 
-    match no_context {
+    match scope.no_context {
         Some(NoContext::All) => {
             //No context, don't do anything
         }
@@ -94,17 +400,42 @@ fn always_context_try(expr: &mut syn::ExprTry, mut no_context: Option<NoContext>
             //Don't put function names and inputs in `context!(...)``
 
             replace_with::replace_with_or_abort(&mut expr.expr, |ex| {
-                context_no_func_input(ex, expr.question_token.span())
+                context_no_func_input(ex, expr.question_token.span(), scope)
             });
         }
         Some(NoContext::EnableBack) | None => {
             //Put all info available into context
 
             replace_with::replace_with_or_abort(&mut expr.expr, |ex| {
-                context(ex, expr.question_token.span())
+                context(ex, expr.question_token.span(), scope)
             });
         }
     }
+
+    //`?` operators inside of `.await?` chains (`a().await?.b().await?`) are hidden in the receiver
+    try_chain_handle(&mut expr.expr, scope);
+}
+
+///Goes through receivers of method calls, awaits and fields looking for `?` operators
+///
+///Called after context was added, so inner `?` don't show up in the outer call description
+fn try_chain_handle(expr: &mut syn::Expr, scope: ContextScope) {
+    match expr {
+        //Enclosing `?` already carries context, only `Option` (without an error) gets the location
+        syn::Expr::Try(expr_try) if scope.function.is_some_and(|function| function.once) => {
+            try_chain_handle(&mut expr_try.expr, scope);
+            if !matches!(scope.no_context, Some(NoContext::All)) {
+                replace_with::replace_with_or_abort(&mut expr_try.expr, |ex| {
+                    context_once(ex, expr_try.question_token.span(), scope)
+                });
+            }
+        }
+        syn::Expr::Try(expr_try) => always_context_try(expr_try, scope),
+        syn::Expr::Await(await_) => try_chain_handle(&mut await_.base, scope),
+        syn::Expr::MethodCall(method_call) => try_chain_handle(&mut method_call.receiver, scope),
+        syn::Expr::Field(field) => try_chain_handle(&mut field.base, scope),
+        _ => {}
+    }
 }
 ///Returns `true` if the type is `anyhow::Result` or `Result<..., UserFriendlyError>`
 fn supported_result_check(ty: &Type) -> bool {
@@ -135,7 +466,37 @@ fn supported_result_check(ty: &Type) -> bool {
     false
 }
 
-fn always_context_item_trait(item_trait: &mut ItemTrait, mut no_context: Option<NoContext>) {
+fn always_context_item_fn(item_fn: &mut ItemFn, mut scope: ContextScope) {
+    let ItemFn {
+        attrs,
+        vis: _,
+        sig,
+        block,
+    } = item_fn;
+
+    handle_attributes(attrs, &mut scope);
+    instrumented_check(attrs, &mut scope.no_context);
+    let once = context_once_attr_check(attrs);
+
+    always_context_signature_handle(
+        sig,
+        ContextScope {
+            function: None,
+            ..scope
+        },
+    );
+    //Parameter types are used for formatting arguments in context, nested functions don't have `self`
+    let function = FnContext::new(sig, None, once);
+    always_context_block_handle(
+        block,
+        ContextScope {
+            function: Some(&function),
+            ..scope
+        },
+    );
+}
+
+fn always_context_item_trait(item_trait: &mut ItemTrait, mut scope: ContextScope) {
     let ItemTrait {
         attrs,
         vis: _,
@@ -151,12 +512,17 @@ fn always_context_item_trait(item_trait: &mut ItemTrait, mut no_context: Option<
         items,
     } = item_trait;
 
-    handle_attributes(attrs, &mut no_context);
+    handle_attributes(attrs, &mut scope);
+    let outer_self_context = self_context_attr_check(attrs).flatten();
+    let outer_once = context_once_attr_check(attrs);
 
     for item in items.iter_mut() {
-        if let TraitItem::Fn(f) = item
-            && let Some(block) = &mut f.default
-        {
+        if let TraitItem::Fn(f) = item {
+            let self_context = method_self_context(&mut f.attrs, &f.sig, outer_self_context);
+            let once = context_once_attr_check(&mut f.attrs) || outer_once;
+            let Some(block) = &mut f.default else {
+                continue;
+            };
             match &mut f.sig.output {
                 syn::ReturnType::Default => {
                     //No return type, don't add ? anywhere
@@ -167,18 +533,26 @@ fn always_context_item_trait(item_trait: &mut ItemTrait, mut no_context: Option<
                         continue;
                     }
                     //Attr check
-                    let mut no_context = no_context;
-                    handle_attributes(&mut f.attrs, &mut no_context);
+                    let mut scope = scope;
+                    handle_attributes(&mut f.attrs, &mut scope);
+                    instrumented_check(&f.attrs, &mut scope.no_context);
 
                     //Add context to block
-                    always_context_block_handle(block, no_context);
+                    let function = FnContext::new(&f.sig, self_context, once);
+                    always_context_block_handle(
+                        block,
+                        ContextScope {
+                            function: Some(&function),
+                            ..scope
+                        },
+                    );
                 }
             }
         }
     }
 }
 
-fn always_context_item_impl(item_impl: &mut ItemImpl, mut no_context: Option<NoContext>) {
+fn always_context_item_impl(item_impl: &mut ItemImpl, mut scope: ContextScope) {
     let ItemImpl {
         attrs,
         defaultness: _,
@@ -191,10 +565,14 @@ fn always_context_item_impl(item_impl: &mut ItemImpl, mut no_context: Option<NoC
         items,
     } = item_impl;
 
-    handle_attributes(attrs, &mut no_context);
+    handle_attributes(attrs, &mut scope);
+    let outer_self_context = self_context_attr_check(attrs).flatten();
+    let outer_once = context_once_attr_check(attrs);
 
     for item in items.iter_mut() {
         if let syn::ImplItem::Fn(m) = item {
+            let self_context = method_self_context(&mut m.attrs, &m.sig, outer_self_context);
+            let once = context_once_attr_check(&mut m.attrs) || outer_once;
             match &mut m.sig.output {
                 syn::ReturnType::Default => {
                     //No return type, don't add ? anywhere
@@ -205,17 +583,77 @@ fn always_context_item_impl(item_impl: &mut ItemImpl, mut no_context: Option<NoC
                         continue;
                     }
                     //Attr check
-                    let mut no_context = no_context;
-                    handle_attributes(&mut m.attrs, &mut no_context);
+                    let mut scope = scope;
+                    handle_attributes(&mut m.attrs, &mut scope);
+                    instrumented_check(&m.attrs, &mut scope.no_context);
 
                     //Add context to block
-                    always_context_block_handle(&mut m.block, no_context);
+                    let function = FnContext::new(&m.sig, self_context, once);
+                    always_context_block_handle(
+                        &mut m.block,
+                        ContextScope {
+                            function: Some(&function),
+                            ..scope
+                        },
+                    );
+                }
+            }
+        }
+    }
+}
+
+//Inlines `no_context! { ... }` regions back after context was added
+all_syntax_cases! {
+    setup => {
+        generated_fn_prefix: "no_context_region",
+        additional_input_type: ()
+    }
+    default_cases => {
+        fn inline_no_context_regions(stmts: &mut Vec<syn::Stmt>, unit: ());
+    }
+    special_cases => {}
+}
+
+///Replaces every `no_context! { ... }` statement with statements inside of it
+///
+///Not wrapped in a block, so `let` bindings inside are visible after the region
+fn inline_no_context_regions(stmts: &mut Vec<syn::Stmt>, _unit: ()) {
+    let has_region = stmts.iter().any(|stmt| {
+        matches!(stmt, syn::Stmt::Macro(stmt_macro) if stmt_macro.mac.path.is_ident("no_context"))
+    });
+    if !has_region {
+        return;
+    }
+
+    for stmt in std::mem::take(stmts) {
+        match stmt {
+            syn::Stmt::Macro(stmt_macro) if stmt_macro.mac.path.is_ident("no_context") => {
+                match stmt_macro.mac.parse_body_with(syn::Block::parse_within) {
+                    Ok(region) => stmts.extend(region),
+                    //Reported at the region instead of panicking, the rest of the item is still handled
+                    Err(e) => {
+                        let error = syn::Error::new(
+                            e.span(),
+                            format!("Expected statements inside of no_context!: {}", e),
+                        )
+                        .to_compile_error();
+                        stmts.push(syn::parse_quote! { #error; });
+                    }
                 }
             }
+            stmt => stmts.push(stmt),
         }
     }
 }
 
-pub fn item_handle(item: &mut syn::Item, no_context: Option<NoContext>) {
-    always_context_item_handle(item, no_context);
+pub fn item_handle(item: &mut syn::Item, args: &AlwaysContextArgs) {
+    always_context_item_handle(
+        item,
+        ContextScope {
+            args,
+            function: None,
+            no_context: None,
+        },
+    );
+    no_context_region_item_handle(item, ());
 }
@@ -0,0 +1,33 @@
+use syn::parse_quote;
+
+use super::expand;
+
+#[test]
+fn await_try_context_after_await() {
+    let result = expand(parse_quote! {
+        async fn load() -> anyhow::Result<()> {
+            some_future().await?;
+            Ok(())
+        }
+    });
+
+    assert!(result.contains("some_future().await.with_context("));
+    assert!(!result.contains("some_future().with_context("));
+    assert_eq!(result.matches(".with_context(").count(), 1);
+}
+
+#[test]
+fn await_try_chain() {
+    let result = expand(parse_quote! {
+        async fn load() -> anyhow::Result<()> {
+            fetch(1).await?.parse().await?;
+            Ok(())
+        }
+    });
+
+    assert!(result.contains("fetch(1).await.with_context("));
+    assert!(result.contains(".parse().await.with_context("));
+    assert_eq!(result.matches(".with_context(").count(), 2);
+    //Outer context describes the original call chain, without generated code
+    assert!(result.contains("\"fetch(1).await?.parse()\""));
+}
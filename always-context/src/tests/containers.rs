@@ -0,0 +1,44 @@
+use syn::parse_quote;
+
+use super::expand;
+
+#[test]
+fn try_in_array_elements() {
+    let result = expand(parse_quote! {
+        fn load() -> anyhow::Result<()> {
+            let values = [a()?, b()?];
+            Ok(())
+        }
+    });
+
+    assert!(result.contains("a().with_context("));
+    assert!(result.contains("b().with_context("));
+    assert_eq!(result.matches(".with_context(").count(), 2);
+}
+
+#[test]
+fn try_in_tuple_elements() {
+    let result = expand(parse_quote! {
+        fn load() -> anyhow::Result<()> {
+            let values = (a()?, b()?);
+            Ok(())
+        }
+    });
+
+    assert!(result.contains("a().with_context("));
+    assert!(result.contains("b().with_context("));
+    assert_eq!(result.matches(".with_context(").count(), 2);
+}
+
+#[test]
+fn try_in_struct_fields_and_rest() {
+    let result = expand(parse_quote! {
+        fn load() -> anyhow::Result<Foo> {
+            Ok(Foo { x: a()?, ..base()? })
+        }
+    });
+
+    assert!(result.contains("a().with_context("));
+    assert!(result.contains("base().with_context("));
+    assert_eq!(result.matches(".with_context(").count(), 2);
+}
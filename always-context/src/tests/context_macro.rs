@@ -0,0 +1,96 @@
+use syn::parse_quote;
+
+use super::{expand, expand_with_args};
+use crate::AlwaysContextArgs;
+
+fn args(context_macro: Option<syn::Path>, eager: bool) -> AlwaysContextArgs {
+    AlwaysContextArgs {
+        context_macro,
+        eager,
+    }
+}
+
+#[test]
+fn renamed_context_macro_used() {
+    let result = expand_with_args(
+        parse_quote! {
+            fn load() -> anyhow::Result<()> {
+                read()?;
+                write(1)?;
+                Ok(())
+            }
+        },
+        &args(Some(parse_quote!(my_ctx)), false),
+    );
+
+    assert!(result.contains("read().with_context(my_ctx!(\"read()\"))"));
+    assert!(result.contains("write(1).with_context(my_ctx!("));
+    assert!(!result.contains("context_internal2"));
+}
+
+#[test]
+fn renamed_context_macro_path_and_reset() {
+    let result = expand_with_args(
+        parse_quote! {
+            fn load() -> anyhow::Result<()> {
+                read()?;
+                Ok(())
+            }
+        },
+        &args(Some(parse_quote!(crate::errors::ctx)), false),
+    );
+    assert!(result.contains("read().with_context(crate::errors::ctx!("));
+
+    //Built in macro is used without `macro = ...`
+    let result = expand(parse_quote! {
+        fn load() -> anyhow::Result<()> {
+            read()?;
+            Ok(())
+        }
+    });
+    assert!(result.contains("context_internal2!("));
+}
+
+#[test]
+fn always_context_args_parse() {
+    let args: AlwaysContextArgs = syn::parse_str("").unwrap();
+    assert!(args.context_macro.is_none());
+
+    let args: AlwaysContextArgs = syn::parse_str("macro = my_ctx").unwrap();
+    assert_eq!(args.context_macro, Some(parse_quote!(my_ctx)));
+
+    assert!(syn::parse_str::<AlwaysContextArgs>("name = my_ctx").is_err());
+
+    let args: AlwaysContextArgs = syn::parse_str("eager, macro = my_ctx").unwrap();
+    assert!(args.eager);
+    assert_eq!(args.context_macro, Some(parse_quote!(my_ctx)));
+    assert!(syn::parse_str::<AlwaysContextArgs>("lazy").is_err());
+}
+
+#[test]
+fn eager_context_method() {
+    let result = expand_with_args(
+        parse_quote! {
+            fn load() -> anyhow::Result<()> {
+                read()?;
+                Ok(())
+            }
+        },
+        &args(None, true),
+    );
+
+    assert!(result.contains("read().context((helpers::context_internal2!("));
+    assert!(!result.contains(".with_context("));
+
+    //With a custom macro, its closure is called right away too
+    let result = expand_with_args(
+        parse_quote! {
+            fn load() -> anyhow::Result<()> {
+                read()?;
+                Ok(())
+            }
+        },
+        &args(Some(parse_quote!(my_ctx)), true),
+    );
+    assert!(result.contains("read().context((my_ctx!(\"read()\"))())"));
+}
@@ -0,0 +1,61 @@
+use syn::parse_quote;
+
+use super::expand;
+
+#[test]
+fn context_once_chain() {
+    let result = expand(parse_quote! {
+        #[context(once)]
+        fn load() -> anyhow::Result<()> {
+            a()?.b()?;
+            Ok(())
+        }
+    });
+
+    assert_eq!(result.matches(".with_context(").count(), 1);
+    //Inner `Result` is left as it is, `ContextOnce` adds context only to `Option`
+    assert!(result.contains("helpers::ContextOnce::context_once(a(),helpers::context_internal2!("));
+    assert!(result.contains("))?.b().with_context("));
+    assert!(!result.contains("#[context"));
+}
+
+#[test]
+fn context_once_option_in_chain() {
+    let result = expand(parse_quote! {
+        #[context(once)]
+        fn first_len(items: &[Vec<u8>]) -> anyhow::Result<usize> {
+            let first = items.first()?.first()?.checked_add(1)?;
+            Ok(first as usize)
+        }
+    });
+
+    //`?` on `Option` inside of the chain compiles, it has no error which could be left as it is
+    assert!(result.contains(
+        "helpers::ContextOnce::context_once(helpers::ContextOnce::context_once(items.first(),"
+    ));
+    assert_eq!(result.matches("ContextOnce::context_once(").count(), 2);
+    assert_eq!(result.matches(".with_context(").count(), 1);
+}
+
+#[test]
+fn context_once_separate_statements() {
+    let result = expand(parse_quote! {
+        impl Service {
+            #[context(once)]
+            fn load(&self) -> anyhow::Result<()> {
+                a()?;
+                b()?;
+                Ok(())
+            }
+
+            //Without `#[context(once)]` every `?` in the chain gets context
+            fn save(&self) -> anyhow::Result<()> {
+                c()?.d()?;
+                Ok(())
+            }
+        }
+    });
+
+    assert_eq!(result.matches(".with_context(").count(), 4);
+    assert!(result.contains("c().with_context("));
+}
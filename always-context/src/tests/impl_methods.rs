@@ -0,0 +1,106 @@
+use syn::parse_quote;
+
+use super::expand;
+
+#[test]
+fn impl_methods_all_wrapped() {
+    let result = expand(parse_quote! {
+        impl Service {
+            fn load(&self, id: u32) -> anyhow::Result<()> {
+                read(id)?;
+                Ok(())
+            }
+
+            async fn fetch(&mut self) -> anyhow::Result<String> {
+                let body = request(self.url()).await?;
+                parse(&body)?;
+                Ok(body)
+            }
+
+            fn save(&self) -> Result<(), UserFriendlyError> {
+                write()?;
+                Ok(())
+            }
+        }
+    });
+
+    assert!(result.contains("read(id).with_context("));
+    assert!(result.contains("request(self.url()).await.with_context("));
+    assert!(result.contains("parse(&body).with_context("));
+    assert!(result.contains("write().with_context("));
+    assert_eq!(result.matches(".with_context(").count(), 4);
+}
+
+#[test]
+fn impl_methods_unsupported_return_ignored() {
+    let result = expand(parse_quote! {
+        impl Service {
+            fn first(&self) -> Option<u8> {
+                let value = self.values.first()?;
+                Some(*value)
+            }
+
+            fn second(&self) -> anyhow::Result<u8> {
+                Ok(self.get(1)?)
+            }
+        }
+    });
+
+    assert!(result.contains("self.values.first()?"));
+    assert_eq!(result.matches(".with_context(").count(), 1);
+}
+
+#[test]
+fn impl_inside_function_body() {
+    let result = expand(parse_quote! {
+        fn outer() -> anyhow::Result<()> {
+            struct Local;
+
+            impl Local {
+                fn run(&self) -> anyhow::Result<()> {
+                    step()?;
+                    Ok(())
+                }
+            }
+
+            Local.run()?;
+            Ok(())
+        }
+    });
+
+    assert!(result.contains("step().with_context("));
+    assert!(result.contains("Local.run().with_context("));
+    assert_eq!(result.matches(".with_context(").count(), 2);
+}
+
+#[test]
+fn impl_method_with_no_build_marker() {
+    //always-context-build adds `#[always_context]` to the `impl` because of `load`
+    let result = expand(parse_quote! {
+        impl Service {
+            fn load(&self) -> anyhow::Result<()> {
+                read()?;
+                Ok(())
+            }
+
+            #[no_always_context_build]
+            fn save(&self) -> anyhow::Result<()> {
+                write()?;
+                Ok(())
+            }
+
+            #[cfg_attr(any(), no_always_context_build)]
+            fn remove(&self) -> anyhow::Result<()> {
+                delete()?;
+                Ok(())
+            }
+        }
+    });
+
+    assert!(result.contains("read().with_context("));
+    assert!(result.contains("write()?"));
+    assert!(result.contains("delete()?"));
+    assert_eq!(result.matches(".with_context(").count(), 1);
+    //Bare marker isn't a real attribute
+    assert!(!result.contains("#[no_always_context_build]"));
+}
@@ -0,0 +1,50 @@
+use syn::parse_quote;
+
+use super::expand;
+
+#[test]
+fn instrumented_fn_location_only() {
+    let result = expand(parse_quote! {
+        #[tracing::instrument(skip(db))]
+        fn load(db: &Db, id: u32) -> anyhow::Result<()> {
+            read(id)?;
+            Ok(())
+        }
+    });
+
+    assert!(result.contains("read(id).with_context(helpers::context_internal2!(1usize,))?"));
+    assert!(!result.contains("Arguments"));
+    //Attribute is left for `tracing`
+    assert!(result.contains("#[tracing::instrument(skip(db))]"));
+}
+
+#[test]
+fn instrumented_method_and_override() {
+    let result = expand(parse_quote! {
+        impl Service {
+            #[instrument]
+            fn load(&self, id: u32) -> anyhow::Result<()> {
+                read(id)?;
+                Ok(())
+            }
+
+            #[instrument]
+            #[enable_context]
+            fn save(&self, id: u32) -> anyhow::Result<()> {
+                write(id)?;
+                Ok(())
+            }
+
+            #[instrument]
+            #[no_context]
+            fn delete(&self, id: u32) -> anyhow::Result<()> {
+                remove(id)?;
+                Ok(())
+            }
+        }
+    });
+
+    assert!(result.contains("read(id).with_context(helpers::context_internal2!(1usize,))?"));
+    assert!(result.contains("write(id).with_context(helpers::context_internal2!(1usize,\"write((id))\\r\\n\\r\\nArguments:"));
+    assert!(result.contains("remove(id)?"));
+}
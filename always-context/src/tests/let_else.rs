@@ -0,0 +1,20 @@
+use syn::parse_quote;
+
+use super::expand;
+
+#[test]
+fn let_else_initializer_and_else_block() {
+    let result = expand(parse_quote! {
+        fn load() -> anyhow::Result<u32> {
+            let Ok(x) = f(parse()?) else {
+                cleanup()?;
+                anyhow::bail!("f failed");
+            };
+            Ok(x)
+        }
+    });
+
+    assert!(result.contains("letOk(x)=f(parse().with_context("));
+    assert!(result.contains("cleanup().with_context("));
+    assert_eq!(result.matches(".with_context(").count(), 2);
+}
@@ -0,0 +1,37 @@
+use syn::parse_quote;
+
+use super::expand;
+
+#[test]
+fn method_chain_every_try_has_context() {
+    let result = expand(parse_quote! {
+        fn load() -> anyhow::Result<()> {
+            a()?.b()?.c()?;
+            Ok(())
+        }
+    });
+
+    assert_eq!(result.matches(".with_context(").count(), 3);
+    //Every `?` describes the chain up to its own position
+    assert!(result.contains("a().with_context("));
+    assert!(result.contains("\"a()\""));
+    assert!(result.contains(".b().with_context("));
+    assert!(result.contains("\"a()?.b()\""));
+    assert!(result.contains(".c().with_context("));
+    assert!(result.contains("\"a()?.b()?.c()\""));
+}
+
+#[test]
+fn method_chain_try_through_fields() {
+    let result = expand(parse_quote! {
+        fn load() -> anyhow::Result<()> {
+            let value = config()?.inner.value()?;
+            Ok(())
+        }
+    });
+
+    assert_eq!(result.matches(".with_context(").count(), 2);
+    assert!(result.contains("config().with_context("));
+    assert!(result.contains(".inner.value().with_context("));
+    assert!(result.contains("\"config()?.inner.value()\""));
+}
@@ -0,0 +1,31 @@
+mod await_try;
+mod containers;
+mod context_macro;
+mod context_once;
+mod impl_methods;
+mod instrument;
+mod let_else;
+mod method_chain_try;
+mod no_context_region;
+mod option_try;
+mod return_break;
+mod self_context;
+mod tokens_args;
+mod unparsable_macro;
+
+use quote::ToTokens;
+
+use crate::{AlwaysContextArgs, search::item_handle};
+
+///Runs `#[always_context]` transformation on the item and returns it as a string without whitespace
+fn expand(item: syn::Item) -> String {
+    expand_with_args(item, &AlwaysContextArgs::default())
+}
+
+///Same as [`expand`], with `#[always_context(...)]` arguments
+fn expand_with_args(mut item: syn::Item, args: &AlwaysContextArgs) -> String {
+    item_handle(&mut item, args);
+    item.into_token_stream()
+        .to_string()
+        .replace(|c: char| c.is_whitespace(), "")
+}
@@ -0,0 +1,44 @@
+use syn::parse_quote;
+
+use super::expand;
+
+#[test]
+fn no_context_region_not_wrapped() {
+    let result = expand(parse_quote! {
+        fn load() -> anyhow::Result<()> {
+            before()?;
+            no_context! {
+                let a = first()?;
+                second(a)?;
+                third()?;
+            }
+            after(a)?;
+            Ok(())
+        }
+    });
+
+    assert!(!result.contains("no_context!"));
+    //Inlined without a block, `a` stays visible for `after(a)`
+    assert!(result.contains("leta=first()?;second(a)?;third()?;"));
+    assert!(result.contains("before().with_context("));
+    assert!(result.contains("after(a).with_context("));
+    assert_eq!(result.matches(".with_context(").count(), 2);
+}
+
+#[test]
+fn malformed_no_context_region_compile_error() {
+    let result = expand(parse_quote! {
+        fn load() -> anyhow::Result<()> {
+            no_context! {
+                let a = ;
+            }
+            after()?;
+            Ok(())
+        }
+    });
+
+    assert!(!result.contains("no_context!{"));
+    assert!(result.contains("compile_error!"));
+    assert!(result.contains("Expectedstatementsinsideofno_context!"));
+    assert!(result.contains("after().with_context("));
+}
@@ -0,0 +1,32 @@
+use syn::parse_quote;
+
+use super::expand;
+
+//`anyhow::Context` is implemented for `Option`, so `?` on it gets context like on `Result`
+#[test]
+fn option_try_context() {
+    let result = expand(parse_quote! {
+        fn lookup(map: &HashMap<u32, u32>, k: u32) -> anyhow::Result<u32> {
+            let x = map.get(&k).copied()?;
+            Ok(x)
+        }
+    });
+
+    assert!(result.contains("map.get(&k).copied().with_context("));
+    assert_eq!(result.matches(".with_context(").count(), 1);
+}
+
+#[test]
+fn option_try_context_in_impl_method() {
+    let result = expand(parse_quote! {
+        impl Lookup {
+            fn lookup(&self, k: u32) -> anyhow::Result<u32> {
+                let x = self.map.get(&k).copied()?;
+                Ok(x)
+            }
+        }
+    });
+
+    assert!(result.contains("self.map.get(&k).copied().with_context("));
+    assert_eq!(result.matches(".with_context(").count(), 1);
+}
@@ -0,0 +1,35 @@
+use syn::parse_quote;
+
+use super::expand;
+
+#[test]
+fn try_as_returned_value() {
+    let result = expand(parse_quote! {
+        fn load() -> anyhow::Result<()> {
+            if ready() {
+                return something()?;
+            }
+            Ok(())
+        }
+    });
+
+    assert!(result.contains("returnsomething().with_context("));
+    assert_eq!(result.matches(".with_context(").count(), 1);
+    assert_eq!(result.matches('?').count(), 1);
+}
+
+#[test]
+fn try_as_break_value_with_label() {
+    let result = expand(parse_quote! {
+        fn load() -> anyhow::Result<u32> {
+            let value = 'search: loop {
+                break 'search something()?;
+            };
+            Ok(value)
+        }
+    });
+
+    assert!(result.contains("break'searchsomething().with_context("));
+    assert_eq!(result.matches(".with_context(").count(), 1);
+    assert_eq!(result.matches('?').count(), 1);
+}
@@ -0,0 +1,110 @@
+use syn::parse_quote;
+
+use super::expand;
+
+#[test]
+fn method_self_context_debug() {
+    let result = expand(parse_quote! {
+        impl Service {
+            #[context(self)]
+            fn load(&self, id: u32) -> anyhow::Result<()> {
+                read(id)?;
+                Ok(())
+            }
+        }
+    });
+
+    assert!(
+        result
+            .contains(r#""read((id))\r\n\r\nArguments:\r\n(id):{:?}\r\n\r\nself:{:?}",(id),self"#)
+    );
+    //Formatted inside of the context closure, only when an error occurs
+    assert!(!result.contains("format!"));
+    assert!(!result.contains("#[context"));
+}
+
+#[test]
+fn impl_self_context_display_and_override() {
+    let result = expand(parse_quote! {
+        #[context(self, display)]
+        impl Service {
+            fn load(&self) -> anyhow::Result<()> {
+                read()?;
+                Ok(())
+            }
+
+            #[no_context(self)]
+            fn save(&self) -> anyhow::Result<()> {
+                write()?;
+                Ok(())
+            }
+
+            //No receiver, `self` is not available
+            fn new() -> anyhow::Result<Self> {
+                create()?;
+                Ok(Service)
+            }
+        }
+    });
+
+    assert!(result.contains(r#""read()\r\n\r\nself:{}",self"#));
+    assert_eq!(result.matches("self:").count(), 1);
+    assert!(!result.contains("context(self"));
+}
+
+#[test]
+fn self_context_not_in_nested_fn() {
+    let result = expand(parse_quote! {
+        impl Service {
+            #[context(self)]
+            fn load(&self) -> anyhow::Result<()> {
+                fn helper() -> anyhow::Result<()> {
+                    inner()?;
+                    Ok(())
+                }
+                helper()?;
+                Ok(())
+            }
+        }
+    });
+
+    assert!(result.contains(r#""helper()\r\n\r\nself:{:?}",self"#));
+    assert_eq!(result.matches("self:").count(), 1);
+}
+
+#[test]
+fn self_left_out_when_mut_borrowed() {
+    let result = expand(parse_quote! {
+        impl Service {
+            #[context(self)]
+            fn first(&mut self, id: u32) -> anyhow::Result<&mut u32> {
+                check(id)?;
+                let first = self.items.first_mut()?;
+                Ok(first)
+            }
+        }
+    });
+
+    //`self` isn't used by `check(id)`, context closure can borrow it
+    assert!(
+        result
+            .contains(r#""check((id))\r\n\r\nArguments:\r\n(id):{:?}\r\n\r\nself:{:?}",(id),self"#)
+    );
+    //`self.items.first_mut()` holds a `&mut self` borrow, closure borrowing `self` wouldn't compile
+    assert!(result.contains("self.items.first_mut().with_context("));
+    assert_eq!(result.matches("self:").count(), 1);
+}
+
+#[test]
+#[should_panic(expected = "which has no `self` receiver")]
+fn self_context_without_receiver() {
+    expand(parse_quote! {
+        impl Service {
+            #[context(self)]
+            fn new() -> anyhow::Result<Self> {
+                create()?;
+                Ok(Service)
+            }
+        }
+    });
+}
@@ -0,0 +1,150 @@
+use syn::parse_quote;
+
+use super::expand;
+
+//`syn`/`proc_macro2` typed parameters are formatted with `tokens` without `#[context(tokens)]`
+#[test]
+fn syn_param_formatted_with_tokens() {
+    let result = expand(parse_quote! {
+        fn handle(sig: &syn::Signature, count: usize) -> anyhow::Result<()> {
+            check_signature(sig, count)?;
+            Ok(())
+        }
+    });
+
+    assert!(result.contains("quote::ToTokens::to_token_stream(&(sig))"));
+    //Other arguments keep `Debug` formatting
+    assert!(!result.contains("to_token_stream(&(count))"));
+}
+
+#[test]
+fn proc_macro2_param_in_impl_method() {
+    let result = expand(parse_quote! {
+        impl Generator {
+            fn generate(&self, tokens: proc_macro2::TokenStream) -> anyhow::Result<()> {
+                self.parse(&tokens)?;
+                Ok(())
+            }
+        }
+    });
+
+    assert!(result.contains("quote::ToTokens::to_token_stream(&(&tokens))"));
+}
+
+#[test]
+fn explicit_format_overrides_tokens() {
+    let result = expand(parse_quote! {
+        fn handle(item: syn::Item) -> anyhow::Result<()> {
+            first(#[context(debug)] item)?;
+            second(#[context(display)] item)?;
+            Ok(())
+        }
+    });
+
+    assert!(!result.contains("to_token_stream"));
+}
+
+#[test]
+fn not_tokens_types_ignored() {
+    let result = expand(parse_quote! {
+        fn handle(
+            result: syn::Result<u8>,
+            span: proc_macro2::Span,
+            stream: TokenStream,
+        ) -> anyhow::Result<()> {
+            check(result, span, stream)?;
+            Ok(())
+        }
+    });
+
+    assert!(!result.contains("to_token_stream"));
+}
+
+//Parameters of the outer function are not visible in nested ones
+#[test]
+fn nested_fn_params_scoped() {
+    let result = expand(parse_quote! {
+        fn outer(expr: syn::Expr) -> anyhow::Result<()> {
+            fn inner(expr: u8) -> anyhow::Result<()> {
+                check(expr)?;
+                Ok(())
+            }
+            check(expr)?;
+            Ok(())
+        }
+    });
+
+    let (inner, outer) = result.split_once("Ok(())}").unwrap();
+    assert!(!inner.contains("to_token_stream"));
+    assert!(outer.contains(",quote::ToTokens::to_token_stream(&(expr))))?;"));
+}
+
+#[test]
+fn shadowed_param_not_formatted_with_tokens() {
+    let result = expand(parse_quote! {
+        fn handle(sig: &syn::Signature, items: Vec<u32>) -> anyhow::Result<()> {
+            before(sig)?;
+            {
+                let sig = 5;
+                in_block(sig)?;
+            }
+            //Shadowing inside of the block ended
+            after_block(sig)?;
+            items.iter().map(|sig| closure(sig)).count();
+            after_closure(sig)?;
+            match items.first() {
+                Some(sig) => in_arm(sig)?,
+                None => {}
+            }
+            if let Some(sig) = items.first() {
+                in_if_let(sig)?;
+            }
+            for sig in items.iter() {
+                in_for(sig)?;
+            }
+            after_loops(sig)?;
+            let sig = from_param(sig)?;
+            shadowed(sig)?;
+            Ok(())
+        }
+    });
+
+    for call in [
+        "before",
+        "after_block",
+        "after_closure",
+        "after_loops",
+        "from_param",
+    ] {
+        assert!(
+            result.contains(&format!(
+                "{call}((sig))\\r\\n\\r\\nArguments:\\r\\n(sig):{{}}\\r\\n\\r\\n\",quote::ToTokens::to_token_stream(&(sig))"
+            )),
+            "{call}"
+        );
+    }
+    //Shadowed, plain `Debug` is used
+    for call in ["in_block", "in_arm", "in_if_let", "in_for", "shadowed"] {
+        assert!(
+            result.contains(&format!(
+                "{call}((sig))\\r\\n\\r\\nArguments:\\r\\n(sig):{{:?}}\\r\\n\\r\\n\",(sig)"
+            )),
+            "{call}"
+        );
+    }
+}
+
+#[test]
+fn param_shadowed_in_no_context_region() {
+    let result = expand(parse_quote! {
+        fn handle(sig: &syn::Signature) -> anyhow::Result<()> {
+            no_context! {
+                let sig = sig.ident.to_string();
+            }
+            check(sig)?;
+            Ok(())
+        }
+    });
+
+    assert!(!result.contains("to_token_stream"));
+}
@@ -0,0 +1,23 @@
+use syn::parse_quote;
+
+use super::expand;
+
+#[test]
+fn unparsable_macro_left_unchanged() {
+    let result = expand(parse_quote! {
+        fn load() -> anyhow::Result<()> {
+            #[enable_context]
+            my_dsl!(select * from users where id => ?);
+            #[enable_context]
+            my_macro!(let a = value()?;);
+            other()?;
+            Ok(())
+        }
+    });
+
+    assert!(result.contains("my_dsl!(select*fromuserswhereid=>?);"));
+    //Other macros and statements are still handled
+    assert!(result.contains("my_macro!(leta=value().with_context("));
+    assert!(result.contains("other().with_context("));
+    assert!(!result.contains("enable_context"));
+}
@@ -1,6 +1,4 @@
 #!/bin/bash
 
-# No tests yet, run build for every feature now
-
-cargo build --no-default-features
-cargo build --features easy-sql
\ No newline at end of file
+cargo test --no-default-features
+cargo test --features easy-sql
@@ -1,54 +1,126 @@
-use helpers::find_crate;
+use helpers::{Resolution, find_crate_resolution};
 use proc_macro::TokenStream;
 use quote::{ToTokens, quote};
 
+#[cfg(test)]
+mod tests;
+
 fn crate_missing_panic(crate_name: &str) -> ! {
     panic!(
         "Using anyhow-result requires `{crate_name}` crate to be present in dependencies! You can add it with `{crate_name} = \"*\"` in your Cargo.toml dependencies or with `cargo add {crate_name}` command."
     );
 }
 
-fn quote_crate() -> proc_macro2::TokenStream {
-    if let Some(found) = find_crate("quote", quote! {}) {
-        found
-    } else {
-        crate_missing_panic("quote");
+///Path to `crate_name`, for optional dependencies also adds a `compile_error!` to `feature_checks`
+///which triggers when none of the features enabling it are active
+fn required_crate(
+    crate_name: &str,
+    feature_checks: &mut proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let resolution = find_crate_resolution(crate_name, quote! {});
+    feature_checks.extend(resolution.feature_check(&format!(
+        "Using anyhow-result requires `{crate_name}` crate, which is an optional dependency."
+    )));
+    match resolution {
+        Resolution::Found(path) | Resolution::Optional { path, .. } => path,
+        Resolution::Absent => crate_missing_panic(crate_name),
     }
 }
 
-fn syn_crate() -> proc_macro2::TokenStream {
-    if let Some(found) = find_crate("syn", quote! {}) {
-        found
-    } else {
-        crate_missing_panic("syn");
+///`{:?}` formatting of an `anyhow::Error` stored in `err`, prints the whole chain (including `context!` locations)
+fn formatted_error(err: &syn::Ident) -> proc_macro2::TokenStream {
+    quote! { format!("{:?}", #err) }
+}
+
+///Environment variable enabling [`debug_error_log`] output
+const DEBUG_ENV_VAR: &str = "EASY_MACROS_DEBUG";
+
+///Prints the whole error chain of `err` to stderr (shown by cargo during compilation),
+///only when `EASY_MACROS_DEBUG` is set to something other than `0` or an empty string
+///
+///Checked every time the macro fails, nothing changes when the macro succeeds
+fn debug_error_log(err: &syn::Ident, macro_name: &syn::Ident) -> proc_macro2::TokenStream {
+    let header = format!("`{macro_name}` macro failed ({DEBUG_ENV_VAR} is set):");
+    quote! {
+        if ::std::env::var(#DEBUG_ENV_VAR).is_ok_and(|value| !value.is_empty() && value != "0") {
+            eprintln!("{}\n{:?}", #header, #err);
+        }
     }
 }
 
-fn proc_macro2_crate() -> proc_macro2::TokenStream {
-    if let Some(found) = find_crate("proc-macro2", quote! {}) {
-        found
-    } else {
-        crate_missing_panic("proc-macro2");
+fn is_token_stream(ty: &syn::Type) -> bool {
+    let ty_str = ty
+        .to_token_stream()
+        .to_string()
+        .replace(|c: char| c.is_whitespace(), "");
+    matches!(
+        ty_str.as_str(),
+        "TokenStream" | "proc_macro::TokenStream" | "::proc_macro::TokenStream"
+    )
+}
+
+///Checks that macro inputs are `proc_macro::TokenStream`, both of them for attribute macros
+fn check_inputs(sig: &syn::Signature, attribute_macro: bool) -> Result<(), String> {
+    let func_name = &sig.ident;
+    let expected = if attribute_macro { 2 } else { 1 };
+    let inputs = sig.inputs.iter().collect::<Vec<_>>();
+    if inputs.len() < expected {
+        return Err(if attribute_macro {
+            format!(
+                "`{func_name}` must take two arguments: `(attr: TokenStream, item: TokenStream)`"
+            )
+        } else {
+            format!("`{func_name}` must take a `TokenStream` argument: `(item: TokenStream)`")
+        });
+    }
+
+    for (index, arg) in inputs.into_iter().take(expected).enumerate() {
+        let syn::FnArg::Typed(arg) = arg else {
+            return Err("Self arguments shouldn't be supported on procedural macros".to_string());
+        };
+        if !is_token_stream(&arg.ty) {
+            let position = if index == 0 { "First" } else { "Second" };
+            let found = arg
+                .ty
+                .to_token_stream()
+                .to_string()
+                .replace(|c: char| c.is_whitespace(), "");
+            return Err(format!(
+                "{position} argument of `{func_name}` must be `proc_macro::TokenStream`, found `{found}`. \
+                Take a `TokenStream` and parse it inside of the function instead, for example: `let input = syn::parse::<{found}>(item)?;`"
+            ));
+        }
     }
+    Ok(())
 }
 
-fn anyhow_crate() -> proc_macro2::TokenStream {
-    if let Some(found) = find_crate("anyhow", quote! {}) {
-        found
-    } else {
-        crate_missing_panic("anyhow");
+///Option of `#[anyhow_result(...)]`, returns the original item before generated code on success
+const KEEP_ITEM: &str = "keep_item";
+
+///Parses `#[anyhow_result(...)]` options, returns `true` if `keep_item` is present
+fn parse_options(attr: proc_macro2::TokenStream) -> Result<bool, String> {
+    if attr.is_empty() {
+        return Ok(false);
+    }
+    match syn::parse2::<syn::Ident>(attr.clone()) {
+        Ok(ident) if ident == KEEP_ITEM => Ok(true),
+        _ => Err(format!(
+            "Unknown `anyhow_result` option `{attr}`, only `{KEEP_ITEM}` is supported"
+        )),
     }
 }
 
 #[proc_macro_attribute]
-pub fn anyhow_result(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn anyhow_result(attr: TokenStream, item: TokenStream) -> TokenStream {
     let mut our_func = syn::parse_macro_input!(item as syn::ItemFn);
+    let keep_item = parse_options(attr.into()).unwrap_or_else(|msg| panic!("{msg}"));
 
     // let parent_crate = external_crate_parent();
-    let quote_crate = quote_crate();
-    let syn_crate = syn_crate();
-    let proc_macro2_crate = proc_macro2_crate();
-    let anyhow_crate = anyhow_crate()
+    let mut feature_checks = proc_macro2::TokenStream::new();
+    let quote_crate = required_crate("quote", &mut feature_checks);
+    let syn_crate = required_crate("syn", &mut feature_checks);
+    let proc_macro2_crate = required_crate("proc-macro2", &mut feature_checks);
+    let anyhow_crate = required_crate("anyhow", &mut feature_checks)
         .to_string()
         .replace(|c: char| c.is_whitespace(), "");
 
@@ -73,6 +145,10 @@ pub fn anyhow_result(_attr: TokenStream, item: TokenStream) -> TokenStream {
 
     let func_name = &our_func.sig.ident;
 
+    let macro_err = quote::format_ident!("___macro_err");
+    let formatted_error = formatted_error(&macro_err);
+    let debug_error_log = debug_error_log(&macro_err, func_name);
+
     let inputs = &our_func.sig.inputs;
     //inputs as arguments to function call
     let inputs_passed_in = inputs.iter().enumerate().map(|(index, arg)| match arg {
@@ -104,7 +180,7 @@ pub fn anyhow_result(_attr: TokenStream, item: TokenStream) -> TokenStream {
             let attr_name = attr.path().to_token_stream().to_string();
             if attr_name == "proc_macro" || attr_name == "proc_macro_derive" {
                 err_result = Some(quote::quote! {
-                let formatted_error = format!("{:?}", ___macro_err);
+                let formatted_error = #formatted_error;
                 let mut result=#quote_crate::quote! {compile_error!};
 
                 //Adds (formatted_error) to the end of the result
@@ -130,7 +206,7 @@ pub fn anyhow_result(_attr: TokenStream, item: TokenStream) -> TokenStream {
                     panic!("proc_macro_attribute function must have two arguments");
                 };
                 err_result = Some(quote::quote! {
-                    let formatted_error= format!("{:?}", ___macro_err);
+                    let formatted_error = #formatted_error;
                     let mut result = #quote_crate::quote! {compile_error!};
 
                     //Adds (formatted_error) to the end of the result
@@ -159,6 +235,29 @@ pub fn anyhow_result(_attr: TokenStream, item: TokenStream) -> TokenStream {
         }
     };
 
+    let attribute_macro = macro_attr.path().is_ident("proc_macro_attribute");
+    if let Err(msg) = check_inputs(&our_func.sig, attribute_macro) {
+        panic!("{msg}");
+    }
+    if keep_item && !attribute_macro {
+        panic!("`{KEEP_ITEM}` option can only be used with #[proc_macro_attribute]");
+    }
+
+    //With `keep_item` original item (second argument) is returned before generated code, like on error
+    let ok_result = if keep_item {
+        let second_input_arg = match inputs.iter().nth(1) {
+            Some(syn::FnArg::Typed(arg)) => &arg.pat,
+            _ => panic!("Expected a typed argument"),
+        };
+        quote::quote! {
+            let mut result = #second_input_arg;
+            result.extend(value);
+            result
+        }
+    } else {
+        quote::quote! { value }
+    };
+
     our_func.attrs.remove(attr_index);
 
     // Extract doc comments and other attributes to copy to the wrapper, excluding the proc_macro attribute
@@ -168,6 +267,8 @@ pub fn anyhow_result(_attr: TokenStream, item: TokenStream) -> TokenStream {
     });
 
     let result = quote::quote! {
+        #feature_checks
+
         #(#wrapper_attrs)*
         #macro_attr
         pub fn #func_name(#inputs) -> proc_macro::TokenStream {
@@ -177,8 +278,11 @@ pub fn anyhow_result(_attr: TokenStream, item: TokenStream) -> TokenStream {
             #our_func
 
             match #func_name(#(#inputs_passed_in)*) {
-                Ok(value) => value,
-                Err(___macro_err) => {#err_result .into()},
+                Ok(value) => { #ok_result },
+                Err(___macro_err) => {
+                    #debug_error_log
+                    #err_result .into()
+                },
             }
         }
     };
@@ -187,3 +291,86 @@ pub fn anyhow_result(_attr: TokenStream, item: TokenStream) -> TokenStream {
 
     result.into()
 }
+
+///Option of `#[anyhow_main(...)]`, process exit code used on error
+const EXIT_CODE: &str = "exit_code";
+
+///Parses `#[anyhow_main(...)]` options, returns the exit code (`1` if `exit_code = ...` is not present)
+fn parse_main_options(attr: proc_macro2::TokenStream) -> Result<i32, String> {
+    if attr.is_empty() {
+        return Ok(1);
+    }
+    let unknown = || {
+        format!("Unknown `anyhow_main` option `{attr}`, only `{EXIT_CODE} = <i32>` is supported")
+    };
+    let option = syn::parse2::<syn::MetaNameValue>(attr.clone()).map_err(|_| unknown())?;
+    if !option.path.is_ident(EXIT_CODE) {
+        return Err(unknown());
+    }
+    match &option.value {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(code),
+            ..
+        }) => code
+            .base10_parse::<i32>()
+            .map_err(|err| format!("Invalid `{EXIT_CODE}`: {err}")),
+        _ => Err(unknown()),
+    }
+}
+
+///Wraps `fn main() -> anyhow::Result<()>`, on error prints the full `{:?}` chain to stderr and exits
+///with code 1, `#[anyhow_main(exit_code = 2)]` sets a different code (std `Termination` always uses 1)
+#[proc_macro_attribute]
+pub fn anyhow_main(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut our_func = syn::parse_macro_input!(item as syn::ItemFn);
+    let exit_code = parse_main_options(attr.into()).unwrap_or_else(|msg| panic!("{msg}"));
+
+    let mut feature_checks = proc_macro2::TokenStream::new();
+    let anyhow_crate = required_crate("anyhow", &mut feature_checks)
+        .to_string()
+        .replace(|c: char| c.is_whitespace(), "");
+
+    //Check if output of our function is a anyhow::Result<()>
+    match &our_func.sig.output {
+        syn::ReturnType::Default => {
+            panic!("Function must return a {anyhow_crate}::Result<()>")
+        }
+        syn::ReturnType::Type(_, ty) => {
+            let ty_str = ty
+                .to_token_stream()
+                .to_string()
+                .replace(|c: char| c.is_whitespace(), "");
+            if ty_str != format!("{anyhow_crate}::Result<()>") {
+                panic!("Function must return a {anyhow_crate}::Result<()>");
+            }
+        }
+    }
+    if !our_func.sig.inputs.is_empty() {
+        panic!("Function wrapped with #[anyhow_main] can't have arguments");
+    }
+
+    let func_name = &our_func.sig.ident;
+    let vis = &our_func.vis;
+    //Moved to the wrapper
+    let attrs = std::mem::take(&mut our_func.attrs);
+
+    let main_err = quote::format_ident!("___main_err");
+    let formatted_error = formatted_error(&main_err);
+
+    let result = quote! {
+        #feature_checks
+
+        #(#attrs)*
+        #vis fn #func_name() {
+            #our_func
+
+            if let Err(#main_err) = #func_name() {
+                let formatted_error = #formatted_error;
+                eprintln!("Error: {}", formatted_error);
+                ::std::process::exit(#exit_code);
+            }
+        }
+    };
+
+    result.into()
+}
@@ -0,0 +1,120 @@
+use quote::ToTokens;
+
+use super::{check_inputs, debug_error_log, parse_main_options, parse_options};
+
+fn check(sig: &str, attribute_macro: bool) -> Result<(), String> {
+    let sig: syn::Signature = syn::parse_str(sig).unwrap();
+    check_inputs(&sig, attribute_macro)
+}
+
+#[test]
+fn token_stream_inputs_accepted() {
+    assert!(
+        check(
+            "fn my_macro(item: TokenStream) -> anyhow::Result<TokenStream>",
+            false
+        )
+        .is_ok()
+    );
+    assert!(
+        check(
+            "fn my_macro(item: proc_macro::TokenStream) -> anyhow::Result<TokenStream>",
+            false
+        )
+        .is_ok()
+    );
+    assert!(
+        check(
+            "fn my_attr(attr: TokenStream, item: ::proc_macro::TokenStream) -> anyhow::Result<TokenStream>",
+            true
+        )
+        .is_ok()
+    );
+}
+
+#[test]
+fn wrong_first_argument_type() {
+    let err = check(
+        "fn my_macro(input: DeriveInput) -> anyhow::Result<TokenStream>",
+        false,
+    )
+    .unwrap_err();
+    assert!(err.contains("First argument of `my_macro`"), "{err}");
+    assert!(err.contains("found `DeriveInput`"), "{err}");
+    assert!(err.contains("syn::parse::<DeriveInput>(item)?"), "{err}");
+}
+
+#[test]
+fn wrong_second_argument_type_in_attribute_macro() {
+    let err = check(
+        "fn my_attr(attr: TokenStream, item: syn::ItemFn) -> anyhow::Result<TokenStream>",
+        true,
+    )
+    .unwrap_err();
+    assert!(err.contains("Second argument of `my_attr`"), "{err}");
+    assert!(err.contains("found `syn::ItemFn`"), "{err}");
+}
+
+#[test]
+fn missing_arguments() {
+    let err = check("fn my_macro() -> anyhow::Result<TokenStream>", false).unwrap_err();
+    assert!(err.contains("must take a `TokenStream` argument"), "{err}");
+
+    let err = check(
+        "fn my_attr(item: TokenStream) -> anyhow::Result<TokenStream>",
+        true,
+    )
+    .unwrap_err();
+    assert!(err.contains("must take two arguments"), "{err}");
+}
+
+#[test]
+fn debug_error_log_gated_by_env_var() {
+    let err = quote::format_ident!("___macro_err");
+    let macro_name = quote::format_ident!("my_macro");
+    let log: syn::ExprIf = syn::parse2(debug_error_log(&err, &macro_name)).unwrap();
+
+    // Printing happens only inside of the `if`, nothing else is generated
+    let cond = log.cond.to_token_stream().to_string();
+    assert!(cond.contains("\"EASY_MACROS_DEBUG\""), "{cond}");
+    assert!(cond.contains("value != \"0\""), "{cond}");
+    assert!(log.else_branch.is_none());
+
+    let body = log.then_branch.to_token_stream().to_string();
+    assert!(body.contains("eprintln !"), "{body}");
+    assert!(body.contains("`my_macro` macro failed"), "{body}");
+    assert!(body.contains("___macro_err"), "{body}");
+}
+
+#[test]
+fn keep_item_option() {
+    assert_eq!(parse_options(quote::quote! {}), Ok(false));
+    assert_eq!(parse_options(quote::quote! { keep_item }), Ok(true));
+
+    let err = parse_options(quote::quote! { keep_items }).unwrap_err();
+    assert!(
+        err.contains("Unknown `anyhow_result` option `keep_items`"),
+        "{err}"
+    );
+    let err = parse_options(quote::quote! { keep_item, other }).unwrap_err();
+    assert!(err.contains("only `keep_item` is supported"), "{err}");
+}
+
+#[test]
+fn exit_code_option() {
+    assert_eq!(parse_main_options(quote::quote! {}), Ok(1));
+    assert_eq!(parse_main_options(quote::quote! { exit_code = 3 }), Ok(3));
+
+    let err = parse_main_options(quote::quote! { exit = 3 }).unwrap_err();
+    assert!(
+        err.contains("Unknown `anyhow_main` option `exit = 3`"),
+        "{err}"
+    );
+    let err = parse_main_options(quote::quote! { exit_code = "3" }).unwrap_err();
+    assert!(
+        err.contains("only `exit_code = <i32>` is supported"),
+        "{err}"
+    );
+    let err = parse_main_options(quote::quote! { exit_code = 3000000000 }).unwrap_err();
+    assert!(err.contains("Invalid `exit_code`"), "{err}");
+}
@@ -0,0 +1,83 @@
+//! `#[anyhow_main]` exits the process, so the failing main runs in a child process (this test binary started again)
+
+use std::process::Command;
+
+use anyhow::Context;
+use easy_macros_anyhow_result::anyhow_main;
+
+const CHILD_ENV: &str = "ANYHOW_MAIN_TEST_CHILD";
+
+fn read_port() -> anyhow::Result<u16> {
+    "not a number"
+        .parse::<u16>()
+        .context("Reading port from configuration")
+}
+
+#[anyhow_main]
+fn failing_main() -> anyhow::Result<()> {
+    read_port().context("Starting server")?;
+    Ok(())
+}
+
+#[anyhow_main(exit_code = 3)]
+fn failing_main_with_code() -> anyhow::Result<()> {
+    read_port()?;
+    Ok(())
+}
+
+#[anyhow_main]
+fn passing_main() -> anyhow::Result<()> {
+    Ok(())
+}
+
+#[test]
+fn anyhow_main_error_exit() {
+    if std::env::var(CHILD_ENV).is_ok() {
+        failing_main();
+        unreachable!("failing_main should exit the process");
+    }
+
+    let output = Command::new(std::env::current_exe().unwrap())
+        .args(["anyhow_main_error_exit", "--exact", "--nocapture"])
+        .env(CHILD_ENV, "1")
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    //Whole chain, not only the outermost context
+    assert!(
+        stderr.contains(
+            "Error: Starting server\n\nCaused by:\n    0: Reading port from configuration\n    1: invalid digit found in string"
+        ),
+        "stderr: {stderr}"
+    );
+}
+
+#[test]
+fn anyhow_main_custom_exit_code() {
+    if std::env::var(CHILD_ENV).is_ok() {
+        failing_main_with_code();
+        unreachable!("failing_main_with_code should exit the process");
+    }
+
+    let output = Command::new(std::env::current_exe().unwrap())
+        .args(["anyhow_main_custom_exit_code", "--exact", "--nocapture"])
+        .env(CHILD_ENV, "1")
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(3));
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Error: Reading port from configuration"),
+        "stderr: {stderr}"
+    );
+}
+
+#[test]
+fn anyhow_main_ok_returns() {
+    passing_main();
+}
@@ -0,0 +1,115 @@
+//! `EASY_MACROS_DEBUG` output is printed while the macro runs inside of the compiler,
+//! so a fixture crate using an `#[anyhow_result]` macro is built with cargo
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const MACROS_LIB: &str = r#"
+use anyhow::Context;
+use proc_macro::TokenStream;
+
+#[proc_macro]
+#[anyhow_result::anyhow_result]
+pub fn checked_number(item: TokenStream) -> anyhow::Result<TokenStream> {
+    let number: u32 = item
+        .to_string()
+        .parse()
+        .context("Parsing number for checked_number!")?;
+    Ok(quote::quote! { #number }.into())
+}
+"#;
+
+const USER_LIB: &str = r#"
+pub const VALID: u32 = macros::checked_number!(5);
+
+#[cfg(feature = "fail")]
+pub const INVALID: u32 = macros::checked_number!(five);
+"#;
+
+const DEBUG_HEADER: &str = "`checked_number` macro failed (EASY_MACROS_DEBUG is set):";
+
+///Writes the fixture workspace (once per test binary run), returns its root
+fn fixture() -> PathBuf {
+    let root = Path::new(env!("CARGO_TARGET_TMPDIR")).join("debug_error_log_fixture");
+    let anyhow_result = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+    let files = [
+        (
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"macros\", \"user\"]\nresolver = \"2\"\n".to_string(),
+        ),
+        (
+            "macros/Cargo.toml",
+            format!(
+                "[package]\nname = \"macros\"\nversion = \"0.1.0\"\nedition = \"2024\"\n\n[lib]\nproc-macro = true\n\n\
+                 [dependencies]\nanyhow-result = {{ package = \"easy-macros-anyhow-result\", path = {:?} }}\n\
+                 anyhow = \"1\"\nsyn = {{ version = \"2.0\", features = [\"full\"] }}\nquote = \"1.0\"\nproc-macro2 = \"1.0\"\n",
+                anyhow_result.display().to_string()
+            ),
+        ),
+        ("macros/src/lib.rs", MACROS_LIB.to_string()),
+        (
+            "user/Cargo.toml",
+            "[package]\nname = \"user\"\nversion = \"0.1.0\"\nedition = \"2024\"\n\n[features]\nfail = []\n\n\
+             [dependencies]\nmacros = { path = \"../macros\" }\n"
+                .to_string(),
+        ),
+        ("user/src/lib.rs", USER_LIB.to_string()),
+    ];
+    for (path, content) in files {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+    root
+}
+
+///Builds the fixture, returns `(success, stderr)`
+fn build(root: &Path, debug: bool, fail: bool) -> (bool, String) {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let mut command = Command::new(cargo);
+    command
+        .current_dir(root)
+        .args(["build", "-p", "user"])
+        .env_remove("EASY_MACROS_DEBUG");
+    if debug {
+        command.env("EASY_MACROS_DEBUG", "1");
+    }
+    if fail {
+        command.args(["--features", "fail"]);
+    }
+    let output = command.output().unwrap();
+    (
+        output.status.success(),
+        String::from_utf8_lossy(&output.stderr).into_owned(),
+    )
+}
+
+#[test]
+fn debug_error_log_ok_and_err_paths() {
+    let root = fixture();
+
+    // Ok path, nothing is printed even with the variable set
+    let (success, stderr) = build(&root, true, false);
+    assert!(success, "stderr: {stderr}");
+    assert!(!stderr.contains(DEBUG_HEADER), "stderr: {stderr}");
+
+    // Err path, whole chain is printed next to the `compile_error!`
+    let (success, stderr) = build(&root, true, true);
+    assert!(!success, "stderr: {stderr}");
+    assert!(
+        stderr.contains(&format!(
+            "{DEBUG_HEADER}\nParsing number for checked_number!\n\nCaused by:\n    invalid digit found in string"
+        )),
+        "stderr: {stderr}"
+    );
+
+    // Err path without the variable, only the `compile_error!`
+    let (success, stderr) = build(&root, false, true);
+    assert!(!success, "stderr: {stderr}");
+    assert!(!stderr.contains(DEBUG_HEADER), "stderr: {stderr}");
+    assert!(
+        stderr.contains("Parsing number for checked_number!"),
+        "stderr: {stderr}"
+    );
+}
@@ -2,11 +2,13 @@ use always_context::always_context;
 use anyhow::Context;
 use helpers::context;
 use lazy_static::lazy_static;
-use proc_macro2::TokenTree;
+use proc_macro2::{Group, TokenStream, TokenTree};
 use quote::ToTokens;
 
 lazy_static! {
     static ref UNKNOWN: &'static str = "__unknown__";
+    ///Escaped unknown, matches literal `__unknown__` text instead of being a placeholder
+    static ref UNKNOWN_LIT: &'static str = "__unknown_lit__";
     static ref UNKNOWN_REGEX: regex::Regex =
         regex::Regex::new(&regex::escape(*UNKNOWN)).expect("Failed to create regex for unknown");
 }
@@ -43,8 +45,8 @@ impl AttrWithUnknown {
         let string = stream.to_string();
         if let Some(pos) = string.find(*UNKNOWN) {
             //Get before and after unknown
-            let before_unknown = string[..pos].to_string();
-            let after_unknown = string[pos + UNKNOWN.len()..].to_string();
+            let before_unknown = string[..pos].replace(*UNKNOWN_LIT, *UNKNOWN);
+            let after_unknown = string[pos + UNKNOWN.len()..].replace(*UNKNOWN_LIT, *UNKNOWN);
             //Get all tokens, coordinates, and tokens after unknown
             //later remove last coordinate and use it as `unknown_coordinate`
             let mut unknown_group_coordinates = vec![];
@@ -164,3 +166,50 @@ impl AttrWithUnknown {
         Ok(None)
     }
 }
+
+///Replaces every `__unknown_lit__` (inside of idents and literals) with `__unknown__`
+///
+///Used on attributes without unknown, so they can be compared with real attributes
+#[always_context]
+pub fn unknown_lit_unescape(stream: TokenStream) -> anyhow::Result<TokenStream> {
+    let mut result = TokenStream::new();
+
+    for token in stream.into_iter() {
+        let token = match token {
+            TokenTree::Group(group) => {
+                let mut new_group =
+                    Group::new(group.delimiter(), unknown_lit_unescape(group.stream())?);
+                new_group.set_span(group.span());
+                TokenTree::Group(new_group)
+            }
+            TokenTree::Ident(ident) => {
+                let ident_str = ident.to_string();
+                if ident_str.contains(*UNKNOWN_LIT) {
+                    TokenTree::Ident(proc_macro2::Ident::new(
+                        &ident_str.replace(*UNKNOWN_LIT, *UNKNOWN),
+                        ident.span(),
+                    ))
+                } else {
+                    TokenTree::Ident(ident)
+                }
+            }
+            TokenTree::Literal(literal) => {
+                let literal_str = literal.to_string();
+                if literal_str.contains(*UNKNOWN_LIT) {
+                    let mut new_literal = literal_str
+                        .replace(*UNKNOWN_LIT, *UNKNOWN)
+                        .parse::<proc_macro2::Literal>()
+                        .map_err(|e| anyhow::anyhow!("{e}"))?;
+                    new_literal.set_span(literal.span());
+                    TokenTree::Literal(new_literal)
+                } else {
+                    TokenTree::Literal(literal)
+                }
+            }
+            punct @ TokenTree::Punct(_) => punct,
+        };
+        result.extend([token]);
+    }
+
+    Ok(result)
+}
@@ -1,5 +1,33 @@
 use always_context::always_context;
 
+///`syn::Attribute::parse_outer` (or `parse_inner` if `inner`), with a clear error when something else than attributes is left
+fn parse_attributes(
+    input: syn::parse::ParseStream,
+    inner: bool,
+) -> syn::Result<Vec<syn::Attribute>> {
+    if inner {
+        let attributes = syn::Attribute::parse_inner(input)?;
+
+        if !input.is_empty() {
+            return Err(input.error(
+                "expected inner attributes (for example `#![allow(dead_code)]`), did you forget to wrap it in `#![...]`?",
+            ));
+        }
+
+        return Ok(attributes);
+    }
+
+    let attributes = syn::Attribute::parse_outer(input)?;
+
+    if !input.is_empty() {
+        return Err(input.error(
+            "expected outer attributes (for example `#[derive(Debug)]`), did you forget to wrap it in `#[...]`?",
+        ));
+    }
+
+    Ok(attributes)
+}
+
 pub struct HandleAttrsInput {
     pub operate_on: syn::Expr,
     _comma: syn::token::Comma,
@@ -7,11 +35,11 @@ pub struct HandleAttrsInput {
 }
 
 #[always_context]
-impl syn::parse::Parse for HandleAttrsInput {
-    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+impl HandleAttrsInput {
+    fn parse_with(input: syn::parse::ParseStream, inner: bool) -> syn::Result<Self> {
         let operate_on = input.parse()?;
         let _comma = input.parse()?;
-        let attributes = syn::Attribute::parse_outer(input)?;
+        let attributes = parse_attributes(input, inner)?;
 
         Ok(HandleAttrsInput {
             operate_on,
@@ -19,6 +47,92 @@ impl syn::parse::Parse for HandleAttrsInput {
             attributes,
         })
     }
+
+    ///Same as `parse`, but attributes to search for are inner attributes (`#![...]`)
+    pub fn parse_inner(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        Self::parse_with(input, true)
+    }
+
+    ///Attributes to search in, `operate_on` is used as an attribute slice/vec directly if `attrs_directly`
+    pub fn attrs_tokens(&self, attrs_directly: bool) -> proc_macro2::TokenStream {
+        let operate_on = &self.operate_on;
+        if attrs_directly {
+            quote::quote! { (#operate_on) }
+        } else {
+            quote::quote! { #operate_on.attrs }
+        }
+    }
+}
+
+#[always_context]
+impl syn::parse::Parse for HandleAttrsInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        Self::parse_with(input, false)
+    }
+}
+
+///`operate_on, [#[pattern1] #[pattern2] ...]`
+pub struct HandleAttrsManyInput {
+    pub operate_on: syn::Expr,
+    _comma: syn::token::Comma,
+    _bracket: syn::token::Bracket,
+    pub patterns: Vec<syn::Attribute>,
+}
+
+#[always_context]
+impl syn::parse::Parse for HandleAttrsManyInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let operate_on = input.parse()?;
+        let _comma = input.parse()?;
+        let content;
+        let _bracket = syn::bracketed!(content in input);
+        //Separating commas are optional
+        let mut patterns = Vec::new();
+        while !content.is_empty() {
+            patterns.extend(syn::Attribute::parse_outer(&content)?);
+            if content.is_empty() {
+                break;
+            }
+            if content.peek(syn::Token![,]) {
+                content.parse::<syn::Token![,]>()?;
+            } else {
+                return Err(content.error(
+                    "expected outer attributes (for example `#[derive(Debug)]`), did you forget to wrap it in `#[...]`?",
+                ));
+            }
+        }
+        if !input.is_empty() {
+            return Err(input.error("unexpected tokens after `[...]` with patterns"));
+        }
+
+        Ok(HandleAttrsManyInput {
+            operate_on,
+            _comma,
+            _bracket,
+            patterns,
+        })
+    }
+}
+
+pub struct HandleAttrPathInput {
+    pub operate_on: syn::Expr,
+    _comma: syn::token::Comma,
+    pub path: syn::Path,
+}
+
+#[always_context]
+impl syn::parse::Parse for HandleAttrPathInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let operate_on = input.parse()?;
+        let _comma = input.parse()?;
+        let path = input.parse()?;
+
+        Ok(HandleAttrPathInput {
+            operate_on,
+            _comma,
+            path,
+        })
+    }
 }
 
 pub enum Reference {
@@ -49,7 +163,7 @@ impl syn::parse::Parse for HandleMaybeRefAttrsInput {
         };
         let operate_on = input.parse()?;
         let _comma = input.parse()?;
-        let attributes = syn::Attribute::parse_outer(input)?;
+        let attributes = parse_attributes(input, false)?;
 
         Ok(HandleMaybeRefAttrsInput {
             reference,
@@ -59,3 +173,62 @@ impl syn::parse::Parse for HandleMaybeRefAttrsInput {
         })
     }
 }
+
+#[test]
+fn attributes_without_hash_error() {
+    let error = match syn::parse_str::<HandleAttrsInput>("input, derive(Debug)") {
+        Ok(_) => panic!("attributes without `#[...]` should not parse"),
+        Err(error) => error,
+    };
+    assert!(
+        error
+            .to_string()
+            .contains("did you forget to wrap it in `#[...]`")
+    );
+
+    let error = match syn::parse_str::<HandleMaybeRefAttrsInput>("&input, #[serde] skip") {
+        Ok(_) => panic!("tokens after attributes should not parse"),
+        Err(error) => error,
+    };
+    assert!(error.to_string().contains("expected outer attributes"));
+
+    let parsed = syn::parse_str::<HandleAttrsInput>("input, #[derive(Debug)] #[serde]").unwrap();
+    assert_eq!(parsed.attributes.len(), 2);
+}
+
+#[test]
+fn inner_attributes_parse() {
+    let parsed = syn::parse_str::<HandleAttrsInput>("input, #![allow(dead_code)]");
+    assert!(parsed.is_err());
+
+    let parsed = syn::parse::Parser::parse_str(
+        HandleAttrsInput::parse_inner,
+        "input, #![allow(dead_code)] #![doc = \"x\"]",
+    )
+    .unwrap();
+    assert_eq!(parsed.attributes.len(), 2);
+
+    let error = match syn::parse::Parser::parse_str(
+        HandleAttrsInput::parse_inner,
+        "input, #[allow(dead_code)]",
+    ) {
+        Ok(_) => panic!("outer attributes should not parse as inner"),
+        Err(error) => error,
+    };
+    assert!(error.to_string().contains("expected inner attributes"));
+}
+
+#[test]
+fn many_patterns_parse() {
+    let parsed = syn::parse_str::<HandleAttrsManyInput>(
+        "input, [#[a(__unknown__)], #[b(__unknown__)] #[c = __unknown__]]",
+    )
+    .unwrap();
+    assert_eq!(parsed.patterns.len(), 3);
+
+    let parsed = syn::parse_str::<HandleAttrsManyInput>("input, []").unwrap();
+    assert!(parsed.patterns.is_empty());
+
+    assert!(syn::parse_str::<HandleAttrsManyInput>("input, [a(__unknown__)]").is_err());
+    assert!(syn::parse_str::<HandleAttrsManyInput>("input, #[a(__unknown__)]").is_err());
+}
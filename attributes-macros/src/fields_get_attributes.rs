@@ -4,7 +4,7 @@ use quote::quote;
 
 use crate::{
     context_crate,
-    data::{HandleMaybeRefAttrsInput, Reference},
+    data::{AttrWithUnknown, HandleMaybeRefAttrsInput, Reference},
     root_macros_crate,
 };
 
@@ -47,9 +47,62 @@ fn anyhow_crate() -> proc_macro2::TokenStream {
 #[always_context]
 pub fn fields_get_attributes(
     item: proc_macro::TokenStream,
+) -> anyhow::Result<proc_macro::TokenStream> {
+    fields_get_attributes_base(item, false)
+}
+
+#[always_context]
+pub fn fields_get_attributes_full(
+    item: proc_macro::TokenStream,
+) -> anyhow::Result<proc_macro::TokenStream> {
+    fields_get_attributes_base(item, true)
+}
+
+///Pattern has to contain exactly one `__unknown__`, error points at the pattern if it doesn't
+fn check_pattern(attributes: &[syn::Attribute], macro_name: &str) -> syn::Result<()> {
+    let message = format!("`{macro_name}!` pattern must contain exactly one `__unknown__`");
+    let mut found = None;
+    for attr in attributes.iter() {
+        let with_unknown = AttrWithUnknown::new(attr)
+            .map_err(|err| syn::Error::new_spanned(attr, format!("{message}: {err}")))?;
+        if with_unknown.is_some() {
+            if found.is_some() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    format!("{message}, found another one here"),
+                ));
+            }
+            found = Some(attr);
+        }
+    }
+    if found.is_none() {
+        let pattern = quote! { #(#attributes)* };
+        return Err(if pattern.is_empty() {
+            syn::Error::new(proc_macro2::Span::call_site(), message)
+        } else {
+            syn::Error::new_spanned(pattern, message)
+        });
+    }
+    Ok(())
+}
+
+///`full` - also return path of every matched attribute (uses `get_attributes_full!`)
+#[always_context]
+fn fields_get_attributes_base(
+    item: proc_macro::TokenStream,
+    full: bool,
 ) -> anyhow::Result<proc_macro::TokenStream> {
     let parsed = parse_macro_input!(item as HandleMaybeRefAttrsInput);
 
+    let macro_name = if full {
+        "fields_get_attributes_full"
+    } else {
+        "fields_get_attributes"
+    };
+    if let Err(err) = check_pattern(&parsed.attributes, macro_name) {
+        return Ok(err.to_compile_error().into());
+    }
+
     let operate_on = parsed.operate_on;
     let attributes = parsed.attributes;
     let mut result = TokensBuilder::default();
@@ -65,6 +118,18 @@ pub fn fields_get_attributes(
         None => (quote! { .into_iter() }, quote! {}),
     };
 
+    let (get_attributes_macro, found_ty) = if full {
+        (
+            quote! { get_attributes_full },
+            quote! { (#syn_crate::Path, #proc_macro2_crate::TokenStream) },
+        )
+    } else {
+        (
+            quote! { get_attributes },
+            quote! { #proc_macro2_crate::TokenStream },
+        )
+    };
+
     let crate_root = root_macros_crate("fields_get_attributes");
     let context_crate = context_crate("fields_get_attributes");
 
@@ -85,9 +150,9 @@ pub fn fields_get_attributes(
 
             let mut errors: Vec<(#anyhow_crate::Result<()>, #ref_state #syn_crate::Field)> = Vec::new();
 
-            let filtered: Vec<(usize,#ref_state #syn_crate::Field, Vec<#proc_macro2_crate::TokenStream>)> = fields.into_iter().flatten() .enumerate().filter_map(|(index, field)|{
-                fn get_attrs(field:& #syn_crate::Field)->#anyhow_crate::Result<Vec<#proc_macro2_crate::TokenStream>>{
-                    Ok(#crate_root::get_attributes!(field,#(#attributes)*))
+            let filtered: Vec<(usize,#ref_state #syn_crate::Field, Vec<#found_ty>)> = fields.into_iter().flatten() .enumerate().filter_map(|(index, field)|{
+                fn get_attrs(field:& #syn_crate::Field)->#anyhow_crate::Result<Vec<#found_ty>>{
+                    Ok(#crate_root::#get_attributes_macro!(field,#(#attributes)*))
                 }
 
                 let unknowns=get_attrs(&field);
@@ -118,3 +183,26 @@ pub fn fields_get_attributes(
 
     Ok(result.finalize().into())
 }
+
+#[test]
+fn pattern_without_unknown() {
+    let parsed =
+        syn::parse_str::<HandleMaybeRefAttrsInput>("&input, #[serde(skip)] #[rename]").unwrap();
+    let error = check_pattern(&parsed.attributes, "fields_get_attributes").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "`fields_get_attributes!` pattern must contain exactly one `__unknown__`"
+    );
+
+    let parsed = syn::parse_str::<HandleMaybeRefAttrsInput>(
+        "&input, #[serde(__unknown__)] #[rename(__unknown__)]",
+    )
+    .unwrap();
+    let error = check_pattern(&parsed.attributes, "fields_get_attributes_full").unwrap_err();
+    assert!(error.to_string().contains("found another one here"));
+
+    let parsed =
+        syn::parse_str::<HandleMaybeRefAttrsInput>("&input, #[serde(__unknown__)] #[rename]")
+            .unwrap();
+    assert!(check_pattern(&parsed.attributes, "fields_get_attributes").is_ok());
+}
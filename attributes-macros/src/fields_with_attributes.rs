@@ -71,3 +71,54 @@ pub fn fields_with_attributes(
 
     Ok(result.finalize().into())
 }
+
+#[always_context]
+pub fn fields_partition_attributes(
+    item: proc_macro::TokenStream,
+) -> anyhow::Result<proc_macro::TokenStream> {
+    let parsed = parse_macro_input!(item as HandleMaybeRefAttrsInput);
+
+    let syn_crate = syn_crate();
+
+    let operate_on = parsed.operate_on;
+    let attributes = parsed.attributes;
+    let mut result = TokensBuilder::default();
+
+    let (iter, reference) = match parsed.reference {
+        Some(Reference::Ref) => (quote! { .iter() }, quote! { & }),
+        Some(Reference::RefMut) => (quote! { .iter_mut() }, quote! { &mut }),
+        None => (quote! { .into_iter() }, quote! {}),
+    };
+
+    let crate_root = root_macros_crate("fields_partition_attributes");
+
+    //Single traversal, every field goes either to matched or unmatched
+    result.add(quote! {
+        {
+            let fields=match #reference #operate_on.fields{
+                #syn_crate::Fields::Named(fields) => {
+                    Some(#reference fields.named)
+                }
+                #syn_crate::Fields::Unnamed(fields) => {
+                    Some(#reference fields.unnamed)
+                }
+                #syn_crate::Fields::Unit => {
+                    None
+                }
+            };
+
+            let mut matched = Vec::new();
+            let mut unmatched = Vec::new();
+            for (index, field) in fields.into_iter().flat_map(|f| f #iter .enumerate()) {
+                if #crate_root::has_attributes!(field,#(#attributes)*) {
+                    matched.push((index, field));
+                } else {
+                    unmatched.push((index, field));
+                }
+            }
+            (matched, unmatched)
+        }
+    });
+
+    Ok(result.finalize().into())
+}
@@ -2,11 +2,11 @@ use always_context::always_context;
 use anyhow::Context;
 use helpers::{TokensBuilder, find_crate, indexed_name, parse_macro_input};
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{ToTokens, quote};
 
 use crate::{
     context_crate,
-    data::{AttrWithUnknown, HandleAttrsInput},
+    data::{AttrWithUnknown, HandleAttrsInput, HandleAttrsManyInput, unknown_lit_unescape},
     root_macros_crate,
 };
 
@@ -30,16 +30,64 @@ fn quote_crate() -> proc_macro2::TokenStream {
     }
 }
 
+///What is returned for attributes matching the `__unknown__` pattern
+#[derive(Clone, Copy)]
+enum Output {
+    ///`Vec<TokenStream>` of unknown values
+    Values,
+    ///`Vec<(syn::Path, TokenStream)>`, also path of every matched attribute
+    Full,
+    ///`usize`, only number of matched attributes
+    Count,
+}
+
 #[always_context]
 pub fn get_attributes(item: TokenStream) -> anyhow::Result<TokenStream> {
     let parsed = parse_macro_input!(item as HandleAttrsInput);
+    get_attributes_base(parsed, Output::Values, false)
+}
+
+///Same as `get_attributes`, but the first argument is an attribute slice/vec instead of an item with `.attrs`
+#[always_context]
+pub fn get_attributes_from(item: TokenStream) -> anyhow::Result<TokenStream> {
+    let parsed = parse_macro_input!(item as HandleAttrsInput);
+    get_attributes_base(parsed, Output::Values, true)
+}
+
+#[always_context]
+pub fn get_attributes_full(item: TokenStream) -> anyhow::Result<TokenStream> {
+    let parsed = parse_macro_input!(item as HandleAttrsInput);
+    get_attributes_base(parsed, Output::Full, false)
+}
+
+///Same as `get_attributes`, but with inner attributes (`#![...]`)
+#[always_context]
+pub fn get_inner_attributes(item: TokenStream) -> anyhow::Result<TokenStream> {
+    let parsed = parse_macro_input!(item with HandleAttrsInput::parse_inner);
+    get_attributes_base(parsed, Output::Values, false)
+}
+
+///Same as `get_attributes`, but returns only number of attributes matching the pattern
+#[always_context]
+pub fn count_attributes(item: TokenStream) -> anyhow::Result<TokenStream> {
+    let parsed = parse_macro_input!(item as HandleAttrsInput);
+    get_attributes_base(parsed, Output::Count, false)
+}
+
+///`attrs_directly` - `operate_on` is an attribute slice/vec, not an item with `.attrs`
+#[always_context]
+fn get_attributes_base(
+    parsed: HandleAttrsInput,
+    output: Output,
+    attrs_directly: bool,
+) -> anyhow::Result<TokenStream> {
     //The easiest way would be just turning attributes into a string and then parsing it
     //We would have to parse some parts into string anyway and this isn't performance critical
 
     let syn_crate = syn_crate();
     let quote_crate = quote_crate();
 
-    let operate_on = parsed.operate_on;
+    let attrs = parsed.attrs_tokens(attrs_directly);
     let mut attributes = parsed.attributes;
     let mut result = TokensBuilder::default();
 
@@ -67,6 +115,12 @@ pub fn get_attributes(item: TokenStream) -> anyhow::Result<TokenStream> {
         }
     };
 
+    //`__unknown_lit__` in attributes without unknown should match literal `__unknown__` text
+    let attributes = attributes
+        .into_iter()
+        .map(|attr| unknown_lit_unescape(attr.to_token_stream()))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
     let attributes_len = attributes.len();
     let attr_to_find_vars = indexed_name(quote::format_ident!("attr_to_find"), attributes_len);
     let found_vars = indexed_name(quote::format_ident!("found_vars"), attributes_len);
@@ -88,7 +142,7 @@ pub fn get_attributes(item: TokenStream) -> anyhow::Result<TokenStream> {
                     };
                     let mut #found_vars = false;
                 )*
-                for attr in #operate_on.attrs.iter() {
+                for attr in #attrs.iter() {
                     #(
                         if attr == &#attr_to_find_vars {
                             #found_vars = true;
@@ -107,6 +161,25 @@ pub fn get_attributes(item: TokenStream) -> anyhow::Result<TokenStream> {
         });
     }
 
+    //Matched value binding, what is done with it and value returned when nothing matches
+    let (found_pattern, push_unknown, empty) = match output {
+        Output::Values => (
+            quote! { Some(u) },
+            quote! { unknown_replacers.push(u); },
+            quote! { Vec::new() },
+        ),
+        Output::Full => (
+            quote! { Some(u) },
+            quote! { unknown_replacers.push((attr.path().clone(), u)); },
+            quote! { Vec::new() },
+        ),
+        Output::Count => (
+            quote! { Some(_) },
+            quote! { unknown_replacers += 1; },
+            quote! { 0usize },
+        ),
+    };
+
     let crate_root = root_macros_crate("get_attributes");
     let context_crate = context_crate("get_attributes");
 
@@ -119,10 +192,10 @@ pub fn get_attributes(item: TokenStream) -> anyhow::Result<TokenStream> {
             .with_context(#context_crate::context!("(generated by get_attributes macro, this error should be unreachable)\r\nAttrWithUnknown::new(#unknown_attr)\r\n\r\nunknown_attr: {}",u_attr.to_token_stream()))?
             .with_context(#context_crate::context!("(generated by get_attributes macro, this error should be unreachable)\r\nSome Expected, got None\r\nAttrWithUnknown::new(#unknown_attr)\r\n\r\nunknown_attr: {}",u_attr.to_token_stream()))?;
 
-            let mut unknown_replacers = Vec::new();
-            for attr in #operate_on.attrs.iter() {
-                if let Some(u) = unknown.get_unknown(attr).with_context(#context_crate::context!("unknown.get_unknown(attr)\r\n\r\nattr: {}\r\n\r\nunknown: {:?}",attr.to_token_stream(),unknown))?{
-                    unknown_replacers.push(u);
+            let mut unknown_replacers = #empty;
+            for attr in #attrs.iter() {
+                if let #found_pattern = unknown.get_unknown(attr).with_context(#context_crate::context!("unknown.get_unknown(attr)\r\n\r\nattr: {}\r\n\r\nunknown: {:?}",attr.to_token_stream(),unknown))?{
+                    #push_unknown
                 }
             }
             unknown_replacers
@@ -132,7 +205,7 @@ pub fn get_attributes(item: TokenStream) -> anyhow::Result<TokenStream> {
     if attributes_len > 0 {
         result.add(quote! {
             else {
-                vec![]
+                #empty
             }
         });
         result.braced();
@@ -142,3 +215,57 @@ pub fn get_attributes(item: TokenStream) -> anyhow::Result<TokenStream> {
 
     Ok(result.finalize().into())
 }
+
+///Every pattern is searched for in a single pass over the attributes, returns one `Vec` per pattern
+#[always_context]
+pub fn get_attributes_many(item: TokenStream) -> anyhow::Result<TokenStream> {
+    let parsed = parse_macro_input!(item as HandleAttrsManyInput);
+
+    let syn_crate = syn_crate();
+    let quote_crate = quote_crate();
+    let crate_root = root_macros_crate("get_attributes_many");
+    let context_crate = context_crate("get_attributes_many");
+
+    if parsed.patterns.is_empty() {
+        anyhow::bail!("No patterns to search for, expected `[#[a(__unknown__)], ...]`!");
+    }
+    for pattern in parsed.patterns.iter() {
+        if AttrWithUnknown::new(pattern)?.is_none() {
+            anyhow::bail!(
+                "No unknown found in pattern `{}`, every pattern needs exactly one `__unknown__`!",
+                pattern.to_token_stream()
+            );
+        }
+    }
+
+    let operate_on = &parsed.operate_on;
+    let patterns = &parsed.patterns;
+    let patterns_len = patterns.len();
+    let u_attr_vars = indexed_name(quote::format_ident!("u_attr"), patterns_len);
+    let unknown_vars = indexed_name(quote::format_ident!("unknown"), patterns_len);
+    let found_vars = indexed_name(quote::format_ident!("found"), patterns_len);
+
+    Ok(quote! {
+        {
+            use #quote_crate::ToTokens;
+            #(
+                let #u_attr_vars: #syn_crate::Attribute = #syn_crate::parse_quote!{#patterns};
+                let #unknown_vars = #crate_root::AttrWithUnknown::new(&#u_attr_vars)
+                .with_context(#context_crate::context!("(generated by get_attributes_many macro, this error should be unreachable)\r\nAttrWithUnknown::new(#patterns)\r\n\r\nunknown_attr: {}",#u_attr_vars.to_token_stream()))?
+                .with_context(#context_crate::context!("(generated by get_attributes_many macro, this error should be unreachable)\r\nSome Expected, got None\r\nAttrWithUnknown::new(#patterns)\r\n\r\nunknown_attr: {}",#u_attr_vars.to_token_stream()))?;
+                let mut #found_vars = Vec::new();
+            )*
+
+            for attr in #operate_on.attrs.iter() {
+                #(
+                    if let Some(u) = #unknown_vars.get_unknown(attr).with_context(#context_crate::context!("unknown.get_unknown(attr)\r\n\r\nattr: {}\r\n\r\nunknown: {:?}",attr.to_token_stream(),#unknown_vars))?{
+                        #found_vars.push(u);
+                    }
+                )*
+            }
+
+            vec![#(#found_vars),*]
+        }
+    }
+    .into())
+}
@@ -0,0 +1,39 @@
+use super::data::HandleAttrPathInput;
+use always_context::always_context;
+use helpers::{find_crate, parse_macro_input};
+use proc_macro::TokenStream;
+use quote::quote;
+
+fn crate_missing_panic(crate_name: &str) -> ! {
+    panic!(
+        "Using has_attribute_path requires `{crate_name}` crate to be present in dependencies! You can add it with `{crate_name} = \"*\"` in your Cargo.toml dependencies or with `cargo add {crate_name}` command."
+    );
+}
+fn syn_crate() -> proc_macro2::TokenStream {
+    if let Some(found) = find_crate("syn", quote! {}) {
+        found
+    } else {
+        crate_missing_panic("syn");
+    }
+}
+
+#[always_context]
+///Returns true if any attribute of the passed in item has the passed in path (arguments are ignored)
+pub fn has_attribute_path(item: TokenStream) -> anyhow::Result<TokenStream> {
+    let parsed = parse_macro_input!(item as HandleAttrPathInput);
+
+    let syn_crate = syn_crate();
+
+    let operate_on = parsed.operate_on;
+    let path = parsed.path;
+
+    Ok(quote! {
+        {
+            let path_to_find: #syn_crate::Path = #syn_crate::parse_quote! {
+                #path
+            };
+            #operate_on.attrs.iter().any(|attr| attr.path() == &path_to_find)
+        }
+    }
+    .into())
+}
@@ -1,8 +1,8 @@
-use super::data::HandleAttrsInput;
+use super::{data::HandleAttrsInput, root_macros_crate};
 use always_context::always_context;
 use helpers::{TokensBuilder, find_crate, indexed_name, parse_macro_input};
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{ToTokens, quote};
 
 fn crate_missing_panic(crate_name: &str) -> ! {
     panic!(
@@ -21,10 +21,43 @@ fn syn_crate() -> proc_macro2::TokenStream {
 ///Returns true if the passed in item has all passed in attributes (one or more)
 pub fn has_attributes(item: TokenStream) -> anyhow::Result<TokenStream> {
     let parsed = parse_macro_input!(item as HandleAttrsInput);
+    has_attributes_base(parsed, false)
+}
+
+#[always_context]
+///Same as `has_attributes`, but the first argument is an attribute slice/vec instead of an item with `.attrs`
+pub fn has_attributes_from(item: TokenStream) -> anyhow::Result<TokenStream> {
+    let parsed = parse_macro_input!(item as HandleAttrsInput);
+    has_attributes_base(parsed, true)
+}
+
+#[always_context]
+///Same as `has_attributes`, but with inner attributes (`#![...]`)
+pub fn has_inner_attributes(item: TokenStream) -> anyhow::Result<TokenStream> {
+    let parsed = parse_macro_input!(item with HandleAttrsInput::parse_inner);
+    has_attributes_base(parsed, false)
+}
+
+///Wildcard of patterns, matches any single token or group (`#[serde(rename = _)]`)
+const WILDCARD: &str = "_";
+
+///Checks if `tokens` contain a `_` wildcard (also inside of groups)
+fn contains_wildcard(tokens: proc_macro2::TokenStream) -> bool {
+    tokens.into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Ident(ident) => ident == WILDCARD,
+        proc_macro2::TokenTree::Group(group) => contains_wildcard(group.stream()),
+        _ => false,
+    })
+}
 
+#[always_context]
+fn has_attributes_base(
+    parsed: HandleAttrsInput,
+    attrs_directly: bool,
+) -> anyhow::Result<TokenStream> {
     let syn_crate = syn_crate();
 
-    let operate_on = parsed.operate_on;
+    let attrs = parsed.attrs_tokens(attrs_directly);
     let attributes = parsed.attributes;
     let mut result = TokensBuilder::default();
 
@@ -33,6 +66,20 @@ pub fn has_attributes(item: TokenStream) -> anyhow::Result<TokenStream> {
     let attr_to_find_vars = indexed_name(quote::format_ident!("attr_to_find"), attributes_len);
     let found_vars = indexed_name(quote::format_ident!("found_vars"), attributes_len);
 
+    //Patterns with `_` are compared token by token, others with `==`
+    let matches = attributes
+        .iter()
+        .zip(attr_to_find_vars.iter())
+        .map(|(attribute, attr_to_find)| {
+            if contains_wildcard(attribute.to_token_stream()) {
+                let crate_root = root_macros_crate("has_attributes");
+                quote! { #crate_root::attr_matches_wildcard(&#attr_to_find, attr) }
+            } else {
+                quote! { attr == &#attr_to_find }
+            }
+        })
+        .collect::<Vec<_>>();
+
     let mut maybe_break = quote! {};
     //Add break; if only one attribute is passed in
     if attributes_len == 1 {
@@ -49,9 +96,9 @@ pub fn has_attributes(item: TokenStream) -> anyhow::Result<TokenStream> {
                 };
                 let mut #found_vars = false;
             )*
-            for attr in #operate_on.attrs.iter() {
+            for attr in #attrs.iter() {
                 #(
-                    if attr == &#attr_to_find_vars {
+                    if #matches {
                         #found_vars = true;
                         #maybe_break
                     }
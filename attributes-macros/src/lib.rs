@@ -2,11 +2,12 @@ mod data;
 mod fields_get_attributes;
 mod fields_with_attributes;
 mod get_attributes;
+mod has_attribute_path;
 mod has_attributes;
 
 use always_context::always_context;
 use anyhow_result::anyhow_result;
-use helpers::find_crate_list;
+use helpers::find_reexport;
 use proc_macro::TokenStream;
 use quote::quote;
 
@@ -17,11 +18,14 @@ fn crate_missing_panic(crate_name: &str, for_macro: &str) -> ! {
 }
 
 fn root_macros_crate(required_by: &str) -> proc_macro2::TokenStream {
-    if let Some(found) = find_crate_list(&[
-        ("easy-macros", quote! {}),
-        ("easy-macros-attributes", quote! {}),
-        ("easy-macros-attributes-macros", quote! {}),
-    ]) {
+    if let Some(found) = find_reexport(
+        &[
+            ("easy-macros", quote! {}),
+            ("easy-macros-attributes", quote! {}),
+            ("easy-macros-attributes-macros", quote! {}),
+        ],
+        "",
+    ) {
         found
     } else {
         crate_missing_panic("easy-macros-attributes", required_by);
@@ -29,11 +33,14 @@ fn root_macros_crate(required_by: &str) -> proc_macro2::TokenStream {
 }
 
 fn context_crate(required_by: &str) -> proc_macro2::TokenStream {
-    if let Some(found) = find_crate_list(&[
-        ("easy-macros", quote! {}),
-        ("easy-macros-helpers", quote! {}),
-        ("easy-macros-attributes", quote! {::helpers}),
-    ]) {
+    if let Some(found) = find_reexport(
+        &[
+            ("easy-macros", quote! {}),
+            ("easy-macros-helpers", quote! {}),
+            ("easy-macros-attributes", quote! {::helpers}),
+        ],
+        "",
+    ) {
         found
     } else {
         crate_missing_panic("easy-macros-helpers", required_by);
@@ -47,6 +54,27 @@ pub fn has_attributes(item: TokenStream) -> anyhow::Result<TokenStream> {
     has_attributes::has_attributes(item)
 }
 
+#[always_context]
+#[proc_macro]
+#[anyhow_result]
+pub fn has_attributes_from(item: TokenStream) -> anyhow::Result<TokenStream> {
+    has_attributes::has_attributes_from(item)
+}
+
+#[always_context]
+#[proc_macro]
+#[anyhow_result]
+pub fn has_inner_attributes(item: TokenStream) -> anyhow::Result<TokenStream> {
+    has_attributes::has_inner_attributes(item)
+}
+
+#[always_context]
+#[proc_macro]
+#[anyhow_result]
+pub fn has_attribute_path(item: TokenStream) -> anyhow::Result<TokenStream> {
+    has_attribute_path::has_attribute_path(item)
+}
+
 // fn find_unknown(attr_template:&syn::Attribute,attr:syn::)
 
 //Allow for only one unknown inside of attribute
@@ -65,6 +93,43 @@ pub fn get_attributes(item: TokenStream) -> anyhow::Result<TokenStream> {
     get_attributes::get_attributes(item)
 }
 
+#[always_context]
+#[proc_macro]
+#[anyhow_result]
+pub fn get_attributes_from(item: TokenStream) -> anyhow::Result<TokenStream> {
+    get_attributes::get_attributes_from(item)
+}
+
+#[always_context]
+#[proc_macro]
+#[anyhow_result]
+pub fn get_attributes_many(item: TokenStream) -> anyhow::Result<TokenStream> {
+    get_attributes::get_attributes_many(item)
+}
+
+#[always_context]
+#[proc_macro]
+#[anyhow_result]
+pub fn count_attributes(item: TokenStream) -> anyhow::Result<TokenStream> {
+    get_attributes::count_attributes(item)
+}
+
+#[always_context]
+#[proc_macro]
+#[anyhow_result]
+pub fn get_inner_attributes(item: TokenStream) -> anyhow::Result<TokenStream> {
+    get_attributes::get_inner_attributes(item)
+}
+
+#[always_context]
+#[proc_macro]
+#[anyhow_result]
+/// Same as `get_attributes!`, but also returns path of every matched attribute.
+#[doc(hidden)]
+pub fn get_attributes_full(item: TokenStream) -> anyhow::Result<TokenStream> {
+    get_attributes::get_attributes_full(item)
+}
+
 #[always_context]
 #[proc_macro]
 #[anyhow_result]
@@ -72,6 +137,13 @@ pub fn fields_with_attributes(item: TokenStream) -> anyhow::Result<TokenStream>
     fields_with_attributes::fields_with_attributes(item)
 }
 
+#[always_context]
+#[proc_macro]
+#[anyhow_result]
+pub fn fields_partition_attributes(item: TokenStream) -> anyhow::Result<TokenStream> {
+    fields_with_attributes::fields_partition_attributes(item)
+}
+
 #[always_context]
 #[no_context]
 #[proc_macro]
@@ -100,3 +172,10 @@ pub fn fields_get_attributes_debug(item: TokenStream) -> anyhow::Result<TokenStr
     let result = fields_get_attributes::fields_get_attributes(item)?;
     panic!("{result}",);
 }
+
+#[always_context]
+#[proc_macro]
+#[anyhow_result]
+pub fn fields_get_attributes_full(item: TokenStream) -> anyhow::Result<TokenStream> {
+    fields_get_attributes::fields_get_attributes_full(item)
+}
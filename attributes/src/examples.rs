@@ -7,7 +7,10 @@
 
 use anyhow::Context;
 use attributes_macros::{
-    fields_get_attributes, fields_with_attributes, get_attributes, has_attributes,
+    count_attributes, fields_get_attributes, fields_get_attributes_full,
+    fields_partition_attributes, fields_with_attributes, get_attributes, get_attributes_from,
+    get_attributes_many, get_inner_attributes, has_attribute_path, has_attributes,
+    has_attributes_from, has_inner_attributes,
 };
 
 // ============================================================================
@@ -121,6 +124,210 @@ fn has_attributes_exact_matching() {
     assert!(debug_clone);
 }
 
+#[docify::export_content]
+#[test]
+fn has_attributes_wildcard() {
+    use syn::parse_quote;
+
+    let input: syn::ItemStruct = parse_quote! {
+        struct User {
+            #[serde(rename = "user_id")]
+            id: u64,
+            #[serde(rename = "user_name", default)]
+            name: String,
+            age: u8,
+        }
+    };
+    let fields: Vec<&syn::Field> = input.fields.iter().collect();
+
+    // Any value of `rename`, but nothing more
+    assert!(has_attributes!(fields[0], #[serde(rename = _)]));
+    assert!(!has_attributes!(fields[1], #[serde(rename = _)]));
+    assert!(has_attributes!(fields[1], #[serde(rename = _, default)]));
+    assert!(!has_attributes!(fields[2], #[serde(rename = _)]));
+
+    // Every `_` matches one token
+    assert!(has_attributes!(fields[1], #[serde(_ = _, _)]));
+    // Mixed with exact patterns, all of them must match
+    assert!(!has_attributes!(fields[0], #[serde(rename = _)] #[serde(default)]));
+}
+
+#[docify::export_content]
+#[test]
+fn has_attribute_path_basic_usage() {
+    use syn::parse_quote;
+
+    let input: syn::ItemStruct = parse_quote! {
+        #[serde]
+        struct User {
+            #[serde(skip)]
+            id: u64,
+            #[validate(length(min = 1))]
+            name: String,
+        }
+    };
+
+    // Matches attributes with and without arguments
+    assert!(has_attribute_path!(input, serde));
+
+    let fields: Vec<&syn::Field> = input.fields.iter().collect();
+    assert!(has_attribute_path!(fields[0], serde));
+    assert!(!has_attribute_path!(fields[1], serde));
+    assert!(has_attribute_path!(fields[1], validate));
+}
+
+#[docify::export_content]
+#[test]
+fn inner_attributes_module() -> Result<(), Box<dyn std::error::Error>> {
+    use syn::parse_quote;
+
+    let input: syn::ItemMod = parse_quote! {
+        #[cfg(test)]
+        mod tests {
+            #![allow(dead_code)]
+            #![doc = "Module tests"]
+        }
+    };
+
+    assert!(has_inner_attributes!(input, #![allow(dead_code)]));
+    assert!(has_inner_attributes!(input, #![allow(dead_code)] #![doc = "Module tests"]));
+    // Outer and inner attributes are not mixed up
+    assert!(!has_inner_attributes!(input, #![cfg(test)]));
+    assert!(!has_attributes!(input, #[allow(dead_code)]));
+
+    let allowed: Vec<proc_macro2::TokenStream> =
+        get_inner_attributes!(input, #![allow(__unknown__)]);
+    assert_eq!(allowed.len(), 1);
+    assert_eq!(allowed[0].to_string(), "dead_code");
+
+    Ok(())
+}
+
+#[docify::export_content]
+#[test]
+fn attributes_from_vec() -> Result<(), Box<dyn std::error::Error>> {
+    use syn::parse_quote;
+
+    // Attributes parsed without an item, e.g. inside of a custom parse struct
+    let attrs: Vec<syn::Attribute> = parse_quote! {
+        #[route(GET, "/users")]
+        #[auth]
+    };
+
+    assert!(has_attributes_from!(attrs, #[auth]));
+    assert!(!has_attributes_from!(attrs, #[auth] #[admin]));
+
+    let paths: Vec<proc_macro2::TokenStream> =
+        get_attributes_from!(attrs, #[route(GET, __unknown__)]);
+    assert_eq!(paths.len(), 1);
+    assert_eq!(paths[0].to_string(), "\"/users\"");
+
+    Ok(())
+}
+
+#[test]
+fn attributes_from_slice() -> Result<(), Box<dyn std::error::Error>> {
+    use syn::parse_quote;
+
+    let input: syn::ItemStruct = parse_quote! {
+        #[first]
+        #[second(value)]
+        struct Item;
+    };
+    let slice: &[syn::Attribute] = &input.attrs[1..];
+
+    assert!(!has_attributes_from!(slice, #[first]));
+    assert!(has_attributes_from!(slice, #[second(value)]));
+
+    let values: Vec<proc_macro2::TokenStream> =
+        get_attributes_from!(&input.attrs, #[second(__unknown__)]);
+    assert_eq!(values[0].to_string(), "value");
+
+    Ok(())
+}
+
+#[docify::export_content]
+#[test]
+fn count_attributes_example() -> Result<(), Box<dyn std::error::Error>> {
+    use syn::parse_quote;
+
+    let input: syn::ItemFn = parse_quote! {
+        #[route(GET, "/")]
+        #[route(GET, "/index")]
+        #[route(POST, "/submit")]
+        #[inline]
+        fn handler() {}
+    };
+
+    let routes: usize = count_attributes!(input, #[route(__unknown__)]);
+    assert_eq!(routes, 3);
+
+    // With an additional required attribute which is missing
+    let routes: usize = count_attributes!(input, #[deprecated] #[route(__unknown__)]);
+    assert_eq!(routes, 0);
+
+    Ok(())
+}
+
+#[docify::export_content]
+#[test]
+fn get_attributes_many_example() -> Result<(), Box<dyn std::error::Error>> {
+    use syn::parse_quote;
+
+    let input: syn::ItemStruct = parse_quote! {
+        #[table("users")]
+        #[index(email)]
+        #[index(name)]
+        struct User;
+    };
+
+    let found = get_attributes_many!(input, [#[table(__unknown__)], #[index(__unknown__)]]);
+    assert_eq!(found.len(), 2);
+
+    assert_eq!(found[0].len(), 1);
+    assert_eq!(found[0][0].to_string(), "\"users\"");
+
+    let indexes: Vec<String> = found[1].iter().map(|i| i.to_string()).collect();
+    assert_eq!(indexes, vec!["email", "name"]);
+
+    Ok(())
+}
+
+#[test]
+fn get_attributes_many_same_as_separate_calls() -> Result<(), Box<dyn std::error::Error>> {
+    use syn::parse_quote;
+
+    let input: syn::ItemStruct = parse_quote! {
+        #[a(first)]
+        #[b = 1]
+        #[c]
+        #[a(second)]
+        struct Item;
+    };
+
+    let found =
+        get_attributes_many!(input, [#[a(__unknown__)] #[b = __unknown__] #[d(__unknown__)]]);
+    let found: Vec<Vec<String>> = found
+        .iter()
+        .map(|values| values.iter().map(|v| v.to_string()).collect())
+        .collect();
+
+    let separate: Vec<Vec<String>> = [
+        get_attributes!(input, #[a(__unknown__)]),
+        get_attributes!(input, #[b = __unknown__]),
+        get_attributes!(input, #[d(__unknown__)]),
+    ]
+    .iter()
+    .map(|values: &Vec<proc_macro2::TokenStream>| values.iter().map(|v| v.to_string()).collect())
+    .collect();
+
+    assert_eq!(found, separate);
+    assert_eq!(found[0], vec!["first", "second"]);
+    assert!(found[2].is_empty());
+
+    Ok(())
+}
+
 // ============================================================================
 // get_attributes! examples
 // ============================================================================
@@ -150,6 +357,69 @@ fn get_attributes_basic_value_extraction() -> Result<(), Box<dyn std::error::Err
     Ok(())
 }
 
+#[test]
+fn get_attributes_spacing_ignored() -> Result<(), Box<dyn std::error::Error>> {
+    // Differently spaced, but the same tokens
+    let input: syn::ItemStruct = syn::parse_str(
+        r#"
+        #[cfg(feature="x")]
+        #[cfg( feature = "y" )]
+        #[cfg  (feature
+            =   "z")]
+        #[cfg(not(feature = "w"))]
+        struct Gated;
+        "#,
+    )?;
+
+    let features: Vec<proc_macro2::TokenStream> = get_attributes!(
+        input,
+        #[cfg(feature = __unknown__)]
+    );
+
+    assert_eq!(features.len(), 3);
+    assert_eq!(features[0].to_string(), "\"x\"");
+    assert_eq!(features[1].to_string(), "\"y\"");
+    assert_eq!(features[2].to_string(), "\"z\"");
+
+    let input: syn::ItemStruct = syn::parse_str(r#"#[serde(rename="id",default)] struct User;"#)?;
+    let renames: Vec<proc_macro2::TokenStream> = get_attributes!(
+        input,
+        #[serde(rename = __unknown__, default)]
+    );
+    assert_eq!(renames.len(), 1);
+    assert_eq!(renames[0].to_string(), "\"id\"");
+    Ok(())
+}
+
+#[test]
+fn get_attributes_adjacent_tokens_not_joined() -> Result<(), Box<dyn std::error::Error>> {
+    // `x y` are two tokens, they shouldn't match the `xy` token of the pattern
+    let input: syn::ItemStruct = syn::parse_str("#[bar(x y z)] struct Split;")?;
+    let found: Vec<proc_macro2::TokenStream> = get_attributes!(
+        input,
+        #[bar(xy __unknown__)]
+    );
+    assert!(found.is_empty());
+
+    // `ab` is one token, it shouldn't match `a` followed by the unknown
+    let input: syn::ItemStruct = syn::parse_str("#[foo(ab)] struct Joined;")?;
+    let found: Vec<proc_macro2::TokenStream> = get_attributes!(
+        input,
+        #[foo(a __unknown__)]
+    );
+    assert!(found.is_empty());
+
+    // Still matches when tokens are the same
+    let input: syn::ItemStruct = syn::parse_str("#[foo(a b)] struct Separate;")?;
+    let found: Vec<proc_macro2::TokenStream> = get_attributes!(
+        input,
+        #[foo(a __unknown__)]
+    );
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].to_string(), "b");
+    Ok(())
+}
+
 #[docify::export_content]
 #[test]
 fn get_attributes_partial_identifier_matching() -> Result<(), Box<dyn std::error::Error>> {
@@ -288,6 +558,33 @@ fn get_attributes_exact_matching_required() -> Result<(), Box<dyn std::error::Er
     Ok(())
 }
 
+#[docify::export_content]
+#[test]
+fn get_attributes_unknown_literal() -> Result<(), Box<dyn std::error::Error>> {
+    use syn::parse_quote;
+
+    let input: syn::ItemStruct = parse_quote! {
+        #[marker(__unknown__, "first")]
+        #[marker(other, "second")]
+        #[doc_marker = "__unknown__"]
+        struct Placeholders;
+    };
+
+    // `__unknown_lit__` matches the literal `__unknown__` text
+    let values = get_attributes!(input, #[marker(__unknown_lit__, __unknown__)]);
+    assert_eq!(values.len(), 1);
+    assert_eq!(values[0].to_string(), "\"first\"");
+
+    // Works inside of literals and in conditional attributes too
+    let values = get_attributes!(
+        input,
+        #[doc_marker = "__unknown_lit__"] #[marker(other, __unknown__)]
+    );
+    assert_eq!(values.len(), 1);
+    assert_eq!(values[0].to_string(), "\"second\"");
+    Ok(())
+}
+
 // ============================================================================
 // fields_with_attributes! examples
 // ============================================================================
@@ -390,6 +687,116 @@ fn fields_with_attributes_borrowing() {
     assert_eq!(mutable_fields.len(), 1);
 }
 
+#[docify::export_content]
+#[test]
+fn fields_partition_attributes_basic() {
+    use syn::parse_quote;
+
+    let input: syn::ItemStruct = parse_quote! {
+        struct User {
+            id: u64,
+            #[skip]
+            cache: String,
+            name: String,
+        }
+    };
+
+    // Single pass, fields without #[skip] are returned too
+    let (skipped, kept) = fields_partition_attributes!(
+        &input,
+        #[skip]
+    );
+
+    assert_eq!(skipped.len(), 1);
+    assert_eq!(skipped[0].0, 1);
+    let kept_indices: Vec<usize> = kept.iter().map(|(index, _)| *index).collect();
+    assert_eq!(kept_indices, vec![0, 2]);
+}
+
+#[test]
+fn fields_partition_attributes_covers_all_fields() {
+    use syn::parse_quote;
+
+    let mut input: syn::ItemStruct = parse_quote! {
+        struct Tuple(#[skip] u8, u16, #[skip] u32, #[skip(x)] u64);
+    };
+
+    fn indices<T>(matched: &[(usize, T)], unmatched: &[(usize, T)]) -> Vec<usize> {
+        let mut all: Vec<usize> = matched.iter().chain(unmatched).map(|(i, _)| *i).collect();
+        all.sort();
+        all
+    }
+
+    let (matched, unmatched) = fields_partition_attributes!(&input, #[skip]);
+    assert_eq!(
+        matched.iter().map(|(i, _)| *i).collect::<Vec<_>>(),
+        vec![0, 2]
+    );
+    assert_eq!(indices(&matched, &unmatched), vec![0, 1, 2, 3]);
+
+    let (matched, unmatched) = fields_partition_attributes!(&mut input, #[skip]);
+    assert_eq!(indices(&matched, &unmatched), vec![0, 1, 2, 3]);
+
+    let (matched, unmatched) = fields_partition_attributes!(input, #[skip]);
+    assert_eq!(matched.len(), 2);
+    assert_eq!(indices(&matched, &unmatched), vec![0, 1, 2, 3]);
+
+    let unit: syn::ItemStruct = parse_quote! { struct Unit; };
+    let (matched, unmatched) = fields_partition_attributes!(unit, #[skip]);
+    assert!(matched.is_empty() && unmatched.is_empty());
+}
+
+#[test]
+fn fields_with_attributes_generic_struct() -> Result<(), Box<dyn std::error::Error>> {
+    use quote::ToTokens;
+    use syn::parse_quote;
+
+    let input: syn::ItemStruct = parse_quote! {
+        struct Foo<'a, T: Bound, const N: usize> where T: Other {
+            #[skip]
+            x: T,
+            y: &'a [T; N],
+            #[skip]
+            #[route(GET)]
+            z: Vec<Option<T>>,
+        }
+    };
+
+    let skipped: Vec<(usize, &syn::Field)> = fields_with_attributes!(&input, #[skip]).collect();
+    assert_eq!(skipped.len(), 2);
+    assert_eq!(skipped[0].0, 0);
+    assert_eq!(skipped[0].1.ident.as_ref().unwrap(), "x");
+    assert_eq!(skipped[0].1.ty.to_token_stream().to_string(), "T");
+    assert_eq!(skipped[1].0, 2);
+    assert_eq!(
+        skipped[1].1.ty.to_token_stream().to_string(),
+        "Vec < Option < T > >"
+    );
+
+    let (matched, unmatched) = fields_partition_attributes!(&input, #[skip]);
+    assert_eq!(matched.len(), 2);
+    assert_eq!(
+        unmatched[0].1.ty.to_token_stream().to_string(),
+        "& 'a [T ; N]"
+    );
+
+    let routes = fields_get_attributes!(&input, #[route(__unknown__)]);
+    assert_eq!(routes.len(), 1);
+    assert_eq!(routes[0].0, 2);
+    assert_eq!(routes[0].2[0].to_string(), "GET");
+
+    // Generic tuple struct
+    let input: syn::ItemStruct = parse_quote! {
+        struct Wrapper<T>(#[skip] T, std::marker::PhantomData<T>) where T: Clone;
+    };
+    let skipped: Vec<(usize, syn::Field)> = fields_with_attributes!(input, #[skip]).collect();
+    assert_eq!(skipped.len(), 1);
+    assert!(skipped[0].1.ident.is_none());
+    assert_eq!(skipped[0].1.ty.to_token_stream().to_string(), "T");
+
+    Ok(())
+}
+
 // ============================================================================
 // fields_get_attributes! examples
 // ============================================================================
@@ -429,6 +836,92 @@ fn fields_get_attributes_route_extraction() -> Result<(), Box<dyn std::error::Er
     Ok(())
 }
 
+#[docify::export_content]
+#[test]
+fn fields_get_attributes_doc_comments() -> Result<(), Box<dyn std::error::Error>> {
+    use syn::parse_quote;
+
+    let input: syn::ItemStruct = parse_quote! {
+        struct Args {
+            /// Path of the input file
+            input: String,
+
+            /// Print more details
+            /// (can be repeated)
+            verbose: u8,
+
+            undocumented: bool,
+        }
+    };
+
+    // Doc comments are `#[doc = r"..."]` attributes, the (raw) string literal is returned
+    let docs = fields_get_attributes!(input, #[doc = __unknown__]);
+
+    assert_eq!(docs.len(), 2);
+    assert_eq!(docs[0].1.ident.as_ref().unwrap(), "input");
+    assert_eq!(docs[0].2[0].to_string(), "r\" Path of the input file\"");
+
+    // One attribute per line, `syn::LitStr` gives the text
+    assert_eq!(docs[1].2.len(), 2);
+    let text: Vec<String> = docs[1]
+        .2
+        .iter()
+        .map(|doc| syn::parse2::<syn::LitStr>(doc.clone()).map(|lit| lit.value()))
+        .collect::<Result<_, _>>()?;
+    assert_eq!(text, [" Print more details", " (can be repeated)"]);
+
+    // Written as attributes, matched the same way
+    let input: syn::ItemStruct = parse_quote! {
+        struct Args {
+            #[doc = "Output directory"]
+            output: String,
+        }
+    };
+    let docs = fields_get_attributes!(input, #[doc = __unknown__]);
+    assert_eq!(docs[0].2[0].to_string(), "\"Output directory\"");
+
+    Ok(())
+}
+
+#[docify::export_content]
+#[test]
+fn fields_get_attributes_full_paths() -> Result<(), Box<dyn std::error::Error>> {
+    use syn::parse_quote;
+
+    let input: syn::ItemStruct = parse_quote! {
+        struct User {
+            #[serde(skip)]
+            id: u64,
+
+            #[other(skip)]
+            name: String,
+
+            #[serde(skip)]
+            #[other(skip)]
+            email: String,
+
+            #[serde(rename = "user_age")]
+            age: u8,
+        }
+    };
+
+    // Unknown attribute name, matched path tells `serde` and `other` apart
+    // Vec<(usize, syn::Field, Vec<(syn::Path, proc_macro2::TokenStream)>)>
+    let skipped = fields_get_attributes_full!(input, #[__unknown__(skip)]);
+
+    assert_eq!(skipped.len(), 3); // id, name and email
+    assert!(skipped[0].2[0].0.is_ident("serde"));
+    assert!(skipped[1].2[0].0.is_ident("other"));
+
+    // Both matches on email, in attribute order
+    assert_eq!(skipped[2].0, 2);
+    assert!(skipped[2].2[0].0.is_ident("serde"));
+    assert!(skipped[2].2[1].0.is_ident("other"));
+    assert_eq!(skipped[2].2[1].1.to_string(), "other");
+
+    Ok(())
+}
+
 #[docify::export_content]
 #[test]
 fn fields_get_attributes_database_columns() -> Result<(), Box<dyn std::error::Error>> {
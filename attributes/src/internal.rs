@@ -5,7 +5,7 @@
 
 use always_context::always_context;
 use anyhow::Context;
-use helpers::context;
+use helpers::{context, token_stream_to_consistent_string};
 use lazy_static::lazy_static;
 use proc_macro2::TokenTree;
 use quote::ToTokens;
@@ -13,6 +13,8 @@ use syn::{Ident, LitStr};
 
 lazy_static! {
     pub(crate) static ref UNKNOWN: &'static str = "__unknown__";
+    ///Escaped unknown, matches literal `__unknown__` text instead of being a placeholder
+    pub(crate) static ref UNKNOWN_LIT: &'static str = "__unknown_lit__";
     pub(crate) static ref UNKNOWN_REGEX: regex::Regex =
         regex::Regex::new(&regex::escape(*UNKNOWN)).expect("Failed to create regex for unknown");
 }
@@ -35,25 +37,113 @@ pub struct AttrWithUnknown {
     ///
     /// In reverse order (right to left)
     tokens_after_unknown: Vec<proc_macro2::TokenTree>,
+    ///Separated consistent string (see `separated_consistent_string`) of the pattern before the unknown
     before_unknown: String,
+    ///Separated consistent string (see `separated_consistent_string`) of the pattern after the unknown
     after_unknown: String,
 }
 
+///Wildcard of `has_attributes!` patterns, matches any single token or group without capturing it
+pub(crate) const WILDCARD: &str = "_";
+
+/// Checks if `attr` matches `pattern`, where every `_` in the pattern matches any single token or group.
+///
+/// **You should not use this function directly.** Use [`has_attributes!`](crate::has_attributes) with `_` in the pattern instead.
+///
+/// Other tokens are compared like in [`token_stream_to_consistent_string`], so whitespace doesn't matter.
+pub fn attr_matches_wildcard(pattern: &syn::Attribute, attr: &syn::Attribute) -> bool {
+    tokens_match_wildcard(pattern.to_token_stream(), attr.to_token_stream())
+}
+
+///Token by token comparison, `_` in `pattern` matches any single token tree
+fn tokens_match_wildcard(
+    pattern: proc_macro2::TokenStream,
+    tokens: proc_macro2::TokenStream,
+) -> bool {
+    let mut pattern = pattern.into_iter();
+    let mut tokens = tokens.into_iter();
+    loop {
+        match (pattern.next(), tokens.next()) {
+            (None, None) => return true,
+            (Some(TokenTree::Ident(ident)), Some(_)) if ident == WILDCARD => {}
+            (Some(TokenTree::Group(pattern_group)), Some(TokenTree::Group(group))) => {
+                if pattern_group.delimiter() != group.delimiter()
+                    || !tokens_match_wildcard(pattern_group.stream(), group.stream())
+                {
+                    return false;
+                }
+            }
+            (Some(pattern_token), Some(token)) => {
+                if token_stream_to_consistent_string(pattern_token.into())
+                    != token_stream_to_consistent_string(token.into())
+                {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+    }
+}
+
+///Like [`token_stream_to_consistent_string`], but adjacent idents and literals are separated by a space
+///
+///Without the separator `x y` and `xy` would give the same string, so a pattern prefix could match
+///in the middle of a different token
+fn separated_consistent_string(tokens: proc_macro2::TokenStream) -> String {
+    let mut result_str = String::new();
+    write_separated_consistent_string(tokens, &mut result_str);
+    result_str
+}
+
+fn write_separated_consistent_string(tokens: proc_macro2::TokenStream, result_str: &mut String) {
+    let mut previous_is_word = false;
+    for token in tokens.into_iter() {
+        let is_word = matches!(token, TokenTree::Ident(_) | TokenTree::Literal(_));
+        if is_word && previous_is_word {
+            result_str.push(' ');
+        }
+        previous_is_word = is_word;
+
+        match token {
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    proc_macro2::Delimiter::Parenthesis => (Some('('), Some(')')),
+                    proc_macro2::Delimiter::Brace => (Some('{'), Some('}')),
+                    proc_macro2::Delimiter::Bracket => (Some('['), Some(']')),
+                    proc_macro2::Delimiter::None => (None, None),
+                };
+                result_str.extend(open);
+                write_separated_consistent_string(group.stream(), result_str);
+                result_str.extend(close);
+            }
+            token => result_str.push_str(&token_stream_to_consistent_string(token.into())),
+        }
+    }
+}
+
 #[derive(Debug)]
 struct PartialUnknownPos {
     skip_start: usize,
     skip_end: usize,
 }
 
+///Length of the text after replacing `__unknown_lit__` with `__unknown__`
+fn unescaped_len(text: &str) -> usize {
+    text.len() - text.matches(*UNKNOWN_LIT).count() * (UNKNOWN_LIT.len() - UNKNOWN.len())
+}
+
 #[always_context]
 impl AttrWithUnknown {
     pub fn new(attr: &syn::Attribute) -> anyhow::Result<Option<AttrWithUnknown>> {
         let stream = attr.to_token_stream();
-        let string = stream.to_string();
+        //Spacing of the pattern doesn't matter, `get_unknown` compares separated consistent strings too
+        let string = separated_consistent_string(stream.clone());
         if let Some(pos) = string.find(*UNKNOWN) {
             //Get before and after unknown
-            let before_unknown = string.get(..pos)?.to_string();
-            let after_unknown = string.get(pos + UNKNOWN.len()..)?.to_string();
+            let before_unknown = string.get(..pos)?.replace(*UNKNOWN_LIT, *UNKNOWN);
+            let after_unknown = string
+                .get(pos + UNKNOWN.len()..)?
+                .replace(*UNKNOWN_LIT, *UNKNOWN);
 
             //Get all tokens, coordinates, and tokens after unknown
             //later remove last coordinate and use it as `unknown_coordinate`
@@ -101,8 +191,8 @@ impl AttrWithUnknown {
                                     tokens_after = Some(vec![]);
                                     unknown_group_coordinates.push(index);
                                     partial_unknown_cords = Some(PartialUnknownPos {
-                                        skip_start: u_pos.start(),
-                                        skip_end: ident_str.len() - u_pos.end(),
+                                        skip_start: unescaped_len(&ident_str[..u_pos.start()]),
+                                        skip_end: unescaped_len(&ident_str[u_pos.end()..]),
                                     });
                                 }
                             }
@@ -116,8 +206,8 @@ impl AttrWithUnknown {
                                     tokens_after = Some(vec![]);
                                     unknown_group_coordinates.push(index);
                                     partial_unknown_cords = Some(PartialUnknownPos {
-                                        skip_start: u_pos.start(),
-                                        skip_end: literal_str.len() - u_pos.end(),
+                                        skip_start: unescaped_len(&literal_str[..u_pos.start()]),
+                                        skip_end: unescaped_len(&literal_str[u_pos.end()..]),
                                     });
                                 }
                             }
@@ -180,7 +270,8 @@ impl AttrWithUnknown {
     ) -> anyhow::Result<Option<proc_macro2::TokenStream>> {
         //Check if start and end aligns with before and after unknown
         let attr_tokens = attr.to_token_stream();
-        let attr_str = attr_tokens.to_string();
+        //`#[cfg(feature="x")]` and `#[cfg(feature = "x")]` give the same string
+        let attr_str = separated_consistent_string(attr_tokens.clone());
 
         //Speed up the process, check if the string starts and ends with tokens before and after the unknown
         if !(attr_str.starts_with(&self.before_unknown) && attr_str.ends_with(&self.after_unknown))
@@ -15,7 +15,7 @@ docify::compile_markdown!("README.docify.md", "README.md");
 
 // Re-export for use by proc-macro crate (but hidden in internal module)
 #[doc(hidden)]
-pub use internal::AttrWithUnknown;
+pub use internal::{AttrWithUnknown, attr_matches_wildcard};
 
 /// Checks if an item has all specified attributes.
 ///
@@ -42,6 +42,15 @@ pub use internal::AttrWithUnknown;
 /// - `#[serde(rename = "x")]` matches ONLY that exact attribute with that exact value
 /// - Token structure must match exactly (whitespace is normalized and doesn't need to match)
 ///
+/// # Wildcards
+/// `_` in a pattern matches any single token or group (`"x"`, `x`, `(a, b)`, ...) without capturing it,
+/// answering "is the attribute there at all" when the value doesn't matter. Use [`get_attributes!`]
+/// with `__unknown__` when the value is needed.
+///
+/// - `#[serde(rename = _)]` matches `#[serde(rename = "x")]` and `#[serde(rename = "y")]`
+/// - `#[serde(rename = _)]` does NOT match `#[serde(rename = "x", default)]` (the rest must still match exactly)
+/// - `#[derive(_)]` matches `#[derive(Debug)]`, but not `#[derive(Debug, Clone)]` (`Debug, Clone` is three tokens)
+///
 /// # Examples
 ///
 /// ## Basic Usage
@@ -53,10 +62,14 @@ pub use internal::AttrWithUnknown;
 /// ## Exact Matching Gotchas
 #[doc = docify::embed!("src/examples.rs", has_attributes_exact_matching)]
 ///
+/// ## Wildcards
+#[doc = docify::embed!("src/examples.rs", has_attributes_wildcard)]
+///
 /// # Error Handling
 /// This macro performs attribute parsing at compile time and will produce compile errors if:
 /// - The `item` parameter doesn't have an `.attrs` field (e.g., not a valid syntax node)
 /// - The attribute syntax is malformed (invalid Rust attribute syntax)
+/// - Attributes are not wrapped in `#[...]` (e.g. `derive(Debug)` instead of `#[derive(Debug)]`)
 /// - No attributes are provided to check for
 ///
 ///
@@ -67,6 +80,62 @@ pub use internal::AttrWithUnknown;
 /// - Guard clauses to ensure required attributes exist
 pub use attributes_macros::has_attributes;
 
+/// Checks if an item has any attribute with the specified path, ignoring its arguments.
+///
+/// # Syntax
+/// ```rust,ignore
+/// has_attribute_path!(item, path)
+/// ```
+///
+/// # Arguments
+/// * `item` - Any syntax node that has an `.attrs` field (e.g., struct, enum, function, field)
+/// * `path` - Attribute path to look for (e.g., `serde`, `my_crate::config`)
+///
+/// # Return Value
+/// Returns a boolean expression that evaluates to `true` if at least one attribute
+/// on the item has the specified path, `false` otherwise.
+///
+/// # Matching Behavior
+/// Only the attribute path is compared, unlike [`has_attributes!`] which requires an exact match.
+///
+/// - `serde` matches `#[serde]`, `#[serde(skip)]` and `#[serde = "value"]`
+/// - `serde` does NOT match `#[serde_with(...)]` or `#[my_crate::serde]`
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", has_attribute_path_basic_usage)]
+pub use attributes_macros::has_attribute_path;
+
+/// Same as [`has_attributes!`], but checks inner attributes (`#![...]`).
+///
+/// Useful for items that can carry inner attributes, like `syn::ItemMod` or `syn::File`.
+/// Outer attributes (`#[...]`) on the same item are not matched.
+///
+/// # Syntax
+/// ```rust,ignore
+/// has_inner_attributes!(item, #![attribute1] #![attribute2] ... #![attributeN])
+/// ```
+///
+/// # Examples
+#[doc = docify::embed!("src/examples.rs", inner_attributes_module)]
+///
+pub use attributes_macros::has_inner_attributes;
+
+/// Same as [`has_attributes!`], but the first argument is the attribute slice/vec itself.
+///
+/// Useful when attributes are already separated from their item, for example in custom parse structs.
+/// Anything with `.iter()` over `syn::Attribute` works (`Vec<Attribute>`, `&[Attribute]`, ...).
+///
+/// # Syntax
+/// ```rust,ignore
+/// has_attributes_from!(attrs, #[attribute1] #[attribute2] ... #[attributeN])
+/// ```
+///
+/// # Examples
+#[doc = docify::embed!("src/examples.rs", attributes_from_vec)]
+///
+pub use attributes_macros::has_attributes_from;
+
 /// Extracts dynamic values from attributes using `__unknown__` placeholders.
 ///
 /// This macro allows pattern matching against attributes where some parts are unknown
@@ -95,7 +164,8 @@ pub use attributes_macros::has_attributes;
 /// 1. **Exactly one per pattern**: Only one `__unknown__` is allowed per attribute pattern
 /// 2. **Flexible positioning**: Can appear anywhere in the attribute
 /// 3. **Partial matching**: Can match parts of identifiers or literals
-/// 4. **Requires exact match**: All non-unknown parts must match exactly
+/// 4. **Requires exact match**: All non-unknown parts must match exactly (whitespace is normalized and doesn't need to match)
+/// 5. **Escaping**: Use `__unknown_lit__` to match the literal `__unknown__` text
 ///
 /// # Examples
 ///
@@ -114,6 +184,9 @@ pub use attributes_macros::has_attributes;
 /// ## Conditional Extraction with Multiple Attributes
 #[doc = docify::embed!("src/examples.rs", get_attributes_conditional_extraction)]
 ///
+/// ## Matching Literal `__unknown__` Text
+#[doc = docify::embed!("src/examples.rs", get_attributes_unknown_literal)]
+///
 /// # Error Handling
 /// - **Compile Error**: if no `__unknown__` placeholder is found in any attribute
 /// - **Compile Error**: if multiple `__unknown__` placeholders are used in a single pattern  
@@ -169,6 +242,61 @@ pub use attributes_macros::has_attributes;
 /// - Creating domain-specific languages in attributes
 pub use attributes_macros::get_attributes;
 
+/// Same as [`get_attributes!`], but searches inner attributes (`#![...]`).
+///
+/// Useful for items that can carry inner attributes, like `syn::ItemMod` or `syn::File`.
+/// See [`has_inner_attributes!`] for an example.
+///
+/// # Syntax
+/// ```rust,ignore
+/// get_inner_attributes!(item, #![pattern_with___unknown__] #![other_required]...)
+/// ```
+pub use attributes_macros::get_inner_attributes;
+
+/// Extracts values for several patterns at once, in a single pass over `input.attrs`.
+///
+/// Every pattern works like the `__unknown__` attribute of [`get_attributes!`] (there are no
+/// additional required attributes). Returns `Vec<Vec<proc_macro2::TokenStream>>`, one inner `Vec`
+/// per pattern, in the order the patterns were written. Commas between patterns are optional.
+///
+/// Useful for derive macros reading many kinds of attributes, the attribute list is scanned only once.
+///
+/// # Syntax
+/// ```rust,ignore
+/// get_attributes_many!(input, [#[a(__unknown__)], #[b = __unknown__]])
+/// ```
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", get_attributes_many_example)]
+pub use attributes_macros::get_attributes_many;
+
+/// Same as [`get_attributes!`], but returns only the number of attributes matching the `__unknown__` pattern.
+///
+/// Useful for attributes which can appear multiple times, when the values themselves aren't needed.
+/// Additional required attributes work the same way, if any of them is missing `0` is returned.
+///
+/// # Syntax
+/// ```rust,ignore
+/// count_attributes!(input, #[other_required]... #[pattern_with___unknown__])
+/// ```
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", count_attributes_example)]
+pub use attributes_macros::count_attributes;
+
+/// Same as [`get_attributes!`], but the first argument is the attribute slice/vec itself.
+///
+/// Anything with `.iter()` over `syn::Attribute` works (`Vec<Attribute>`, `&[Attribute]`, ...).
+/// See [`has_attributes_from!`] for an example.
+///
+/// # Syntax
+/// ```rust,ignore
+/// get_attributes_from!(attrs, #[pattern_with___unknown__] #[other_required]...)
+/// ```
+pub use attributes_macros::get_attributes_from;
+
 /// Filters struct/enum fields by their attributes.
 ///
 /// This macro examines the fields of a struct and returns an iterator over
@@ -223,6 +351,28 @@ pub use attributes_macros::get_attributes;
 ///
 pub use attributes_macros::fields_with_attributes;
 
+/// Splits struct/enum fields into the ones that have the specified attributes and the ones that don't.
+///
+/// Same matching and borrowing rules as [`fields_with_attributes!`], but every field is visited
+/// once and ends up in exactly one of the two returned vectors.
+///
+/// # Syntax
+/// ```rust,ignore
+/// fields_partition_attributes!(item, #[attr1] #[attr2] ... #[attrN])
+/// fields_partition_attributes!(&item, #[attr1] #[attr2])      // immutable borrow
+/// fields_partition_attributes!(&mut item, #[attr1] #[attr2])  // mutable borrow
+/// ```
+///
+/// # Return Value
+/// Returns `(matched, unmatched)`, both `Vec<(usize, Field)>`, where:
+/// - `usize` is the original 0-based index of the field
+/// - `Field` is `syn::Field`, `&syn::Field`, or `&mut syn::Field` depending on borrowing
+///
+/// # Examples
+#[doc = docify::embed!("src/examples.rs", fields_partition_attributes_basic)]
+///
+pub use attributes_macros::fields_partition_attributes;
+
 /// Extracts dynamic values from field attributes using `__unknown__` placeholders.
 ///
 /// This macro combines field filtering with attribute pattern extraction. It examines
@@ -277,6 +427,11 @@ pub use attributes_macros::fields_with_attributes;
 /// ## Complex Pattern Matching
 #[doc = docify::embed!("src/examples.rs", fields_get_attributes_complex_pattern)]
 ///
+/// ## Doc Comments
+/// `/// text` is a `#[doc = r" text"]` attribute, `#[doc = __unknown__]` returns the string literal
+/// (one per line, raw for doc comments). Parse it with `syn::LitStr` to get the text.
+#[doc = docify::embed!("src/examples.rs", fields_get_attributes_doc_comments)]
+///
 /// # Error Handling
 /// - **Compile Error**: if no `__unknown__` placeholder is found in the pattern
 /// - **Compile Error**: if multiple `__unknown__` placeholders are used  
@@ -296,3 +451,30 @@ pub use attributes_macros::fields_with_attributes;
 /// - **Serialization customization**: Process field-level serialization directives
 ///
 pub use attributes_macros::fields_get_attributes;
+
+/// Same as [`fields_get_attributes!`], but also returns the path of every matched attribute.
+///
+/// Useful when the `__unknown__` placeholder is part of the attribute name, so a single
+/// pattern can match attributes with different paths (for example `#[serde(...)]` and `#[other(...)]`).
+///
+/// # Syntax
+/// ```rust,ignore
+/// fields_get_attributes_full!(item, #[pattern_with___unknown__])
+/// fields_get_attributes_full!(&item, #[pattern_with___unknown__])      // immutable borrow
+/// fields_get_attributes_full!(&mut item, #[pattern_with___unknown__])  // mutable borrow
+/// ```
+///
+/// # Return Value
+/// Returns `Vec<(usize, Field, Vec<(syn::Path, proc_macro2::TokenStream)>)>` where every
+/// `(syn::Path, proc_macro2::TokenStream)` is the matched attribute's path (`attr.path()`)
+/// and the unknown replacement found in it.
+///
+/// Matching and error handling are the same as in [`fields_get_attributes!`].
+///
+/// # Examples
+#[doc = docify::embed!("src/examples.rs", fields_get_attributes_full_paths)]
+///
+pub use attributes_macros::fields_get_attributes_full;
+
+#[doc(hidden)]
+pub use attributes_macros::get_attributes_full;
@@ -1,13 +1,66 @@
 use proc_macro::TokenStream;
 use syn::{Expr, Token, punctuated::Punctuated, token::Comma};
 
-///Same input as format! macro
+///Separator between `file:line` and the message
+const SEPARATOR: &str = "\r\n";
+
+///`@...` options placed before the format! input
+#[derive(Default)]
+struct ContextOptions {
+    ///`@sep = "..."`
+    sep: Option<syn::LitStr>,
+    ///`@thread` - adds the current thread name after `file:line`
+    thread: bool,
+    ///`@oneline` - `message (at file:line)`, without new lines between location and the message
+    oneline: bool,
+    ///`@fn_name` - adds `__FN_NAME` const (from `#[with_fn_name]`) after `file:line`
+    fn_name: bool,
+}
+
+///Same input as format! macro, optionally starting with `@sep = "...",` and/or `@thread,`
 struct ContextInternalInput {
+    options: ContextOptions,
     str: syn::LitStr,
     _comma: Option<Token![,]>,
     args: syn::punctuated::Punctuated<syn::Expr, Token![,]>,
 }
 
+///`@sep = "..."`, `@thread`, `@oneline` and `@fn_name` in any order, each with optional trailing comma
+fn parse_options(input: syn::parse::ParseStream) -> syn::Result<ContextOptions> {
+    let mut options = ContextOptions::default();
+    while input.peek(Token![@]) {
+        input.parse::<Token![@]>()?;
+        let ident: syn::Ident = input.parse()?;
+        if ident == "sep" {
+            input.parse::<Token![=]>()?;
+            options.sep = Some(input.parse()?);
+        } else if ident == "thread" {
+            options.thread = true;
+        } else if ident == "oneline" {
+            options.oneline = true;
+        } else if ident == "fn_name" {
+            options.fn_name = true;
+        } else {
+            return Err(syn::Error::new(
+                ident.span(),
+                "expected `sep`, `thread`, `oneline` or `fn_name`",
+            ));
+        }
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+    }
+    if options.oneline
+        && let Some(sep) = &options.sep
+    {
+        return Err(syn::Error::new(
+            sep.span(),
+            "`@sep` can't be used together with `@oneline`",
+        ));
+    }
+    Ok(options)
+}
+
 enum ContextInternalMaybeInput {
     Yes(ContextInternalInput),
     No,
@@ -15,9 +68,11 @@ enum ContextInternalMaybeInput {
 
 impl syn::parse::Parse for ContextInternalInput {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let options = parse_options(input)?;
         //Handle no input
         if input.is_empty() {
             return Ok(ContextInternalInput {
+                options,
                 str: syn::LitStr::new("", proc_macro2::Span::call_site()),
                 _comma: None,
                 args: syn::punctuated::Punctuated::new(),
@@ -27,9 +82,15 @@ impl syn::parse::Parse for ContextInternalInput {
         if !input.is_empty() {
             let _comma = input.parse()?;
             let args = input.parse_terminated(syn::Expr::parse, Token![,])?;
-            Ok(ContextInternalInput { str, _comma, args })
+            Ok(ContextInternalInput {
+                options,
+                str,
+                _comma,
+                args,
+            })
         } else {
             Ok(ContextInternalInput {
+                options,
                 str,
                 _comma: None,
                 args: syn::punctuated::Punctuated::new(),
@@ -68,29 +129,80 @@ impl syn::parse::Parse for ContextInternalInput2 {
     }
 }
 
+///Error code and format! input
+struct ContextCodeInput {
+    code: syn::Expr,
+    deeper: Option<ContextInternalInput>,
+}
+
+impl syn::parse::Parse for ContextCodeInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let code = input.parse()?;
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            let deeper = input.parse()?;
+            Ok(ContextCodeInput {
+                code,
+                deeper: Some(deeper),
+            })
+        } else {
+            Ok(ContextCodeInput { code, deeper: None })
+        }
+    }
+}
+
+///`options.sep` - separator between `file:line` and the message, [`SEPARATOR`] if `None`
 fn context_base(
     mut passed_in_str: String,
     mut passed_in_args: Punctuated<Expr, Comma>,
+    options: ContextOptions,
     line: Expr,
     closure: bool,
+    code: Option<Expr>,
 ) -> TokenStream {
-    if passed_in_str.is_empty() {
-        passed_in_str = "{}:{}".to_owned();
+    //Message without the location, used in release builds with `strip-location-in-release` feature
+    let mut message_str = passed_in_str.clone();
+    let mut message_args = passed_in_args.clone();
+
+    if options.oneline {
+        oneline_layout(
+            &mut passed_in_str,
+            &mut passed_in_args,
+            options.thread,
+            options.fn_name,
+            line,
+        );
     } else {
-        passed_in_str = format!("{{}}:{{}}\r\n{}", passed_in_str);
+        default_layout(
+            &mut passed_in_str,
+            &mut passed_in_args,
+            options.sep,
+            options.thread,
+            options.fn_name,
+            line,
+        );
     }
-    passed_in_args.insert(
-        0,
-        syn::parse_quote! {
-            file!()
-        },
-    );
 
-    passed_in_args.insert(1, line);
+    //`[code=...] ` prefix, read back by `extract_code` in easy_macros_helpers crate
+    if let Some(code) = code {
+        passed_in_str = format!("[code={{}}] {}", passed_in_str);
+        message_str = if message_str.is_empty() {
+            "[code={}]".to_owned()
+        } else {
+            format!("[code={{}}] {}", message_str)
+        };
+        passed_in_args.insert(0, code.clone());
+        message_args.insert(0, code);
+    }
 
-    let result = if closure {
+    //`debug_assertions` of the crate using the macro, not of this one
+    let formatted = if cfg!(feature = "strip-location-in-release") {
         quote::quote! {
-            ||{format!(#passed_in_str, #passed_in_args)}
+            if cfg!(debug_assertions) {
+                format!(#passed_in_str, #passed_in_args)
+            } else {
+                format!(#message_str, #message_args)
+            }
         }
     } else {
         quote::quote! {
@@ -98,11 +210,111 @@ fn context_base(
         }
     };
 
+    let result = if closure {
+        quote::quote! {
+            ||{#formatted}
+        }
+    } else {
+        formatted
+    };
+
     // panic!("{}", result.to_string());
 
     result.into()
 }
 
+///`file:line{sep}message`, location arguments are inserted before the message arguments
+fn default_layout(
+    passed_in_str: &mut String,
+    passed_in_args: &mut Punctuated<Expr, Comma>,
+    sep: Option<syn::LitStr>,
+    thread: bool,
+    fn_name: bool,
+    line: Expr,
+) {
+    let location = match (fn_name, thread) {
+        (true, true) => "{}:{} (fn: {}, thread: {})",
+        (true, false) => "{}:{} (fn: {})",
+        (false, true) => "{}:{} (thread: {})",
+        (false, false) => "{}:{}",
+    };
+    if passed_in_str.is_empty() {
+        *passed_in_str = location.to_owned();
+    } else {
+        let sep = match sep {
+            //Separator is a part of the format string
+            Some(sep) => sep.value().replace('{', "{{").replace('}', "}}"),
+            None => SEPARATOR.to_owned(),
+        };
+        *passed_in_str = format!("{location}{sep}{passed_in_str}");
+    }
+    passed_in_args.insert(
+        0,
+        syn::parse_quote! {
+            file!()
+        },
+    );
+
+    passed_in_args.insert(1, line);
+
+    let mut index = 2;
+    if fn_name {
+        passed_in_args.insert(index, fn_name_arg());
+        index += 1;
+    }
+    if thread {
+        passed_in_args.insert(
+            index,
+            syn::parse_quote! {
+                ::std::thread::current().name().unwrap_or("unnamed")
+            },
+        );
+    }
+}
+
+///Const generated by `#[with_fn_name]`, resolved at the `context!` call site
+fn fn_name_arg() -> Expr {
+    let name = syn::Ident::new(FN_NAME_CONST, proc_macro2::Span::call_site());
+    syn::parse_quote! { #name }
+}
+
+///`message (at file:line)`
+///
+///Message comes first, so location is passed with named arguments after the message arguments
+fn oneline_layout(
+    passed_in_str: &mut String,
+    passed_in_args: &mut Punctuated<Expr, Comma>,
+    thread: bool,
+    fn_name: bool,
+    line: Expr,
+) {
+    let location = match (fn_name, thread) {
+        (true, true) => {
+            "{__context_file}:{__context_line}, fn: {__context_fn}, thread: {__context_thread}"
+        }
+        (true, false) => "{__context_file}:{__context_line}, fn: {__context_fn}",
+        (false, true) => "{__context_file}:{__context_line}, thread: {__context_thread}",
+        (false, false) => "{__context_file}:{__context_line}",
+    };
+    if passed_in_str.is_empty() {
+        *passed_in_str = location.to_owned();
+    } else {
+        *passed_in_str = format!("{passed_in_str} (at {location})");
+    }
+
+    passed_in_args.push(syn::parse_quote! { __context_file = file!() });
+    passed_in_args.push(syn::parse_quote! { __context_line = #line });
+    if fn_name {
+        let fn_name = fn_name_arg();
+        passed_in_args.push(syn::parse_quote! { __context_fn = #fn_name });
+    }
+    if thread {
+        passed_in_args.push(syn::parse_quote! {
+            __context_thread = ::std::thread::current().name().unwrap_or("unnamed")
+        });
+    }
+}
+
 #[proc_macro]
 /// Macro used by `context!` macro in easy_macros_helpers crate
 ///
@@ -110,21 +322,28 @@ fn context_base(
 pub fn context_internal(item: TokenStream) -> TokenStream {
     let parsed = syn::parse_macro_input!(item as ContextInternalMaybeInput);
 
-    let (passed_in_str, passed_in_args) = match parsed {
+    let (passed_in_str, passed_in_args, options) = match parsed {
         ContextInternalMaybeInput::Yes(context_internal_input) => (
             context_internal_input.str.value(),
             context_internal_input.args,
+            context_internal_input.options,
+        ),
+        ContextInternalMaybeInput::No => (
+            String::new(),
+            syn::punctuated::Punctuated::new(),
+            ContextOptions::default(),
         ),
-        ContextInternalMaybeInput::No => (String::new(), syn::punctuated::Punctuated::new()),
     };
 
     context_base(
         passed_in_str,
         passed_in_args,
+        options,
         syn::parse_quote! {
             line!()
         },
         false,
+        None,
     )
 }
 
@@ -135,15 +354,163 @@ pub fn context_internal(item: TokenStream) -> TokenStream {
 pub fn context_internal2(item: TokenStream) -> TokenStream {
     let parsed = syn::parse_macro_input!(item as ContextInternalInput2);
 
-    let (passed_in_str, passed_in_args) = match parsed.deeper {
+    let (passed_in_str, passed_in_args, options) = match parsed.deeper {
         Some(context_internal_input) => (
             context_internal_input.str.value(),
             context_internal_input.args,
+            context_internal_input.options,
+        ),
+        None => (
+            String::new(),
+            syn::punctuated::Punctuated::new(),
+            ContextOptions::default(),
         ),
-        None => (String::new(), syn::punctuated::Punctuated::new()),
     };
 
-    context_base(passed_in_str, passed_in_args, parsed.line, true)
+    context_base(
+        passed_in_str,
+        passed_in_args,
+        options,
+        parsed.line,
+        true,
+        None,
+    )
+}
+
+#[proc_macro]
+/// Macro used by `context_code!` macro in easy_macros_helpers crate
+///
+/// Use context_code! macro from helpers crate instead
+pub fn context_code_internal(item: TokenStream) -> TokenStream {
+    let parsed = syn::parse_macro_input!(item as ContextCodeInput);
+
+    let (passed_in_str, passed_in_args, options) = match parsed.deeper {
+        Some(context_internal_input) => (
+            context_internal_input.str.value(),
+            context_internal_input.args,
+            context_internal_input.options,
+        ),
+        None => (
+            String::new(),
+            syn::punctuated::Punctuated::new(),
+            ContextOptions::default(),
+        ),
+    };
+
+    context_base(
+        passed_in_str,
+        passed_in_args,
+        options,
+        syn::parse_quote! {
+            line!()
+        },
+        false,
+        Some(parsed.code),
+    )
+}
+
+///Name of the const holding the function name, generated by `#[with_fn_name]`
+const FN_NAME_CONST: &str = "__FN_NAME";
+
+///Macros getting `@fn_name` option added inside of `#[with_fn_name]` functions
+const FN_NAME_MACROS: [&str; 2] = ["context", "context_str"];
+
+#[proc_macro_attribute]
+/// Attribute used by `with_fn_name` in easy_macros_helpers crate
+///
+/// Use it through easy_macros_helpers crate (documented there)
+pub fn with_fn_name(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "#[with_fn_name] doesn't take any arguments",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let mut item_fn = syn::parse_macro_input!(item as syn::ItemFn);
+
+    syn::visit_mut::VisitMut::visit_block_mut(&mut FnNameMacros, &mut item_fn.block);
+
+    let name = item_fn.sig.ident.to_string();
+    let fn_name_const = syn::Ident::new(FN_NAME_CONST, proc_macro2::Span::call_site());
+    item_fn.block.stmts.insert(
+        0,
+        syn::parse_quote! {
+            #[allow(dead_code)]
+            const #fn_name_const: &str = #name;
+        },
+    );
+
+    quote::ToTokens::into_token_stream(item_fn).into()
+}
+
+///Adds `@fn_name` to `context!` and `context_str!` calls, nested items are skipped (they have their own names)
+struct FnNameMacros;
+
+impl syn::visit_mut::VisitMut for FnNameMacros {
+    fn visit_item_mut(&mut self, _item: &mut syn::Item) {}
+
+    fn visit_macro_mut(&mut self, mac: &mut syn::Macro) {
+        let is_context = mac
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| FN_NAME_MACROS.iter().any(|name| segment.ident == name));
+        mac.tokens = if is_context {
+            add_fn_name_option(std::mem::take(&mut mac.tokens))
+        } else {
+            //Other macros (`println!`, `vec!`, ...) can have `context!` calls inside
+            fn_name_in_tokens(std::mem::take(&mut mac.tokens))
+        };
+    }
+}
+
+///`@fn_name` in front of `context!` input, the rest is checked for nested calls too
+fn add_fn_name_option(tokens: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let tokens = fn_name_in_tokens(tokens);
+    if tokens.is_empty() {
+        quote::quote! { @fn_name }
+    } else {
+        quote::quote! { @fn_name, #tokens }
+    }
+}
+
+///Finds `context!(...)` (and `context_str!`) calls in unparsed tokens
+fn fn_name_in_tokens(tokens: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    use proc_macro2::TokenTree;
+
+    let mut result = Vec::new();
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Group(group) => {
+                let mut new_group =
+                    proc_macro2::Group::new(group.delimiter(), fn_name_in_tokens(group.stream()));
+                new_group.set_span(group.span());
+                result.push(TokenTree::Group(new_group));
+            }
+            TokenTree::Ident(ident) if FN_NAME_MACROS.iter().any(|name| ident == name) => {
+                result.push(TokenTree::Ident(ident));
+                if let Some(TokenTree::Punct(punct)) = tokens.peek()
+                    && punct.as_char() == '!'
+                {
+                    result.push(tokens.next().unwrap());
+                    if let Some(TokenTree::Group(group)) = tokens.peek() {
+                        let mut new_group = proc_macro2::Group::new(
+                            group.delimiter(),
+                            add_fn_name_option(group.stream()),
+                        );
+                        new_group.set_span(group.span());
+                        tokens.next();
+                        result.push(TokenTree::Group(new_group));
+                    }
+                }
+            }
+            token => result.push(token),
+        }
+    }
+    result.into_iter().collect()
 }
 
 #[test]
@@ -1,5 +1,28 @@
 #[doc(hidden)]
-pub use context_internal::{context_internal, context_internal2};
+pub use context_internal::{context_code_internal, context_internal, context_internal2};
+
+/// Makes [`context!`] and [`context_str!`] calls inside of the function include the function name.
+///
+/// Injects a hidden `const __FN_NAME: &str = "function_name";` at the start of the function body and adds
+/// the `@fn_name` option to every `context!(...)` and `context_str!(...)` written in it, output becomes
+/// `"src/file.rs:42 (fn: function_name)\r\nmessage"` (`"message (at src/file.rs:42, fn: function_name)"` with `@oneline`).
+///
+/// # Scoping
+///
+/// - Calls anywhere in the body are changed, including closures, nested blocks and arguments of other macros (`vec![...]`, `println!(...)`)
+/// - Items nested in the body (inner `fn`, `impl`, `mod`) are skipped, annotate inner functions separately
+/// - Calls generated by other macros (like `#[always_context]`) and the other context macros
+///   ([`bail_ctx!`], [`context_code!`], ...) don't get the function name, the prefix set with
+///   [`set_context_prefix`] is still added to all of them except [`context_code!`]
+/// - `@fn_name` can also be written by hand in any scope containing a `__FN_NAME` const
+/// - A custom formatter ([`set_context_formatter`]) receives the name in [`ContextOptions::fn_name`]
+///
+/// Works on free functions, methods and trait default methods.
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", with_fn_name_example)]
+pub use context_internal::with_fn_name;
 
 #[macro_export]
 /// Creates a closure that generates context strings for error handling with automatic file and line information.
@@ -20,6 +43,11 @@ pub use context_internal::{context_internal, context_internal2};
 /// context!("format {}", arg)          // Formatted message with file:line
 /// context!("multiple {} {}", a, b)    // Multiple format arguments
 /// context!("multiple {a} {b}")        // All things that format! supports are supported here too
+/// context!(@sep = "\n", "message")    // Custom separator between file:line and the message
+/// context!(@thread, "message")        // Current thread name added after file:line
+/// context!(@oneline, "message")       // Single line: "message (at file:line)"
+/// context!(@fn_name, "message")       // Function name added after file:line, see `#[with_fn_name]`
+/// context!(@json, "message", id = 5)  // JSON object, requires `context-json` feature
 /// ```
 ///
 /// # Returns
@@ -27,6 +55,15 @@ pub use context_internal::{context_internal, context_internal2};
 /// Returns a closure of type `impl FnOnce() -> String` that can be passed directly to
 /// anyhow's `.with_context()` method or called manually to get the formatted context string.
 ///
+/// # Laziness
+///
+/// Nothing is formatted until the returned closure is called. `.with_context(context!(...))`
+/// calls it only when the result is an error, so format arguments are never rendered on the `Ok` path.
+/// Arguments are borrowed by the closure (not moved), the same way `format!` would use them.
+///
+/// Calling the closure right away (`context!(...)()`, or [`context_str!`]) formats the message immediately,
+/// avoid it on hot paths.
+///
 /// # Output Format
 ///
 /// The context macro produces strings in the following exact formats:
@@ -39,7 +76,32 @@ pub use context_internal::{context_internal, context_internal2};
 ///
 /// The file path includes the `src/` prefix and the line number is automatically determined
 /// at compile time using the [`file!`] and [`line!`] macros. Messages are separated from
-/// the location info with a carriage return + line feed (`\r\n`) sequence.
+/// the location info with a carriage return + line feed (`\r\n`) sequence, use
+/// `context!(@sep = "\n", ...)` for a different separator (e.g. plain `\n` for Unix terminals).
+///
+/// - **With `@thread`:** `"src/file.rs:line_number (thread: worker-1)\r\nYour custom message here"`  
+///   Threads without a name are shown as `unnamed`. Opt-in, the thread is not looked up otherwise.
+///   `@sep` and `@thread` can be combined in any order.
+///
+/// - **With `@oneline`:** `"Your custom message here (at src/file.rs:line_number)"`  
+///   No new line between the message and the location, for log backends expecting one line per event.
+///   Can be combined with `@thread` (`"... (at src/file.rs:line_number, thread: worker-1)"`), not with `@sep`.
+///   New lines inside of the message itself are kept.
+///
+/// - **With `@fn_name`:** `"src/file.rs:line_number (fn: load_config)\r\nYour custom message here"`  
+///   Added automatically inside of functions marked with [`#[with_fn_name]`](with_fn_name), which define the `__FN_NAME` const it reads.
+///   Combined with `@thread`: `(fn: load_config, thread: worker-1)`.
+///
+/// - **With `@json`** (`context-json` feature): `{"file":"src/file.rs","id":5,"line":42,"msg":"Your custom message here"}`  
+///   For structured loggers. Input is `@json`, an optional message literal (inline `{arg}` captures only)
+///   and `key = value` fields, values have to implement `serde::Serialize`. Key order isn't specified (it depends on
+///   `serde_json` features enabled in the dependency graph), a field named `file`, `line` or `msg` replaces the generated one. Can't be combined with other options,
+///   [`set_context_prefix`] and [`set_context_formatter`] don't apply to it.
+///
+/// - **With `strip-location-in-release` feature:** `"Your custom message here"` in release builds  
+///   Decided by `debug_assertions` of the crate calling the macro. Options adding to the location
+///   (`@thread`, `@fn_name`) are dropped with it, `[code=...]` and [`set_context_prefix`] are kept,
+///   [`set_context_formatter`] and the `file`/`line` fields of `@json` are skipped.
 ///
 /// # Examples
 ///
@@ -63,24 +125,751 @@ pub use context_internal::{context_internal, context_internal2};
 ///
 #[doc = docify::embed!("src/examples.rs", context_manual_generation_example)]
 ///
+/// ## Custom Separator
+///
+#[doc = docify::embed!("src/examples.rs", context_custom_separator_example)]
+///
+/// ## Thread Name
+///
+#[doc = docify::embed!("src/examples.rs", context_thread_name_example)]
+///
+/// ## Single Line
+///
+#[doc = docify::embed!("src/examples.rs", context_oneline_example)]
+///
+/// ## JSON
+///
+#[doc = docify::embed!("src/examples.rs", context_json_example)]
+///
 /// # See Also
 ///
 /// - [`anyhow::Context`](https://docs.rs/anyhow/latest/anyhow/trait.Context.html) - The trait that provides the `.with_context()` method
 /// - [`format!`] - The standard formatting macro that this macro's syntax is based on
 /// - [`file!`] and [`line!`] - The macros used internally to get location information
+/// - `context_map_err!` (`std-only` feature) - Version of this macro for crates without `anyhow`
+/// - [`set_context_prefix`] - Ambient prefix (like a request ID) added to every context created on the current thread
+/// - [`set_context_formatter`] - Custom layout of the location and the message
 macro_rules! context {
     () => {
         || {
-            $crate::context_internal!()
+            $crate::context_formatted!()
+        }
+    };
+    ($($arg:tt)*) => {
+        || {
+            $crate::context_format_check!($($arg)*);
+            $crate::context_formatted!($($arg)*)
+        }
+    };
+}
+
+#[cfg(feature = "std-only")]
+#[macro_export]
+/// Version of [`context!`] without `anyhow`, creates a closure for `map_err` of any error type.
+///
+/// Returns `impl FnOnce(E) -> String` (`E: Display`), the string starts with the same context as
+/// [`context!`], followed by the original error: `"src/file.rs:42\r\nmessage\r\nCaused by: error"`.
+/// Usable with `Result<T, String>`, `Result<T, Box<dyn std::error::Error>>` (`map_err(...)?` converts the string) and others.
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", std_only_context_example)]
+macro_rules! context_map_err {
+    () => {
+        |err| $crate::context_caused_by($crate::context_formatted!(), &err)
+    };
+    ($($arg:tt)*) => {
+        |err| {
+            $crate::context_format_check!($($arg)*);
+            $crate::context_caused_by($crate::context_formatted!($($arg)*), &err)
+        }
+    };
+}
+
+#[macro_export]
+/// Same as `context!(...)()`, evaluates to the context `String` directly instead of a closure.
+///
+/// For code paths without `?` or `.with_context()`, like building error messages by hand.
+/// Supports the same syntax as [`context!`] (including `@...` options), the string is formatted immediately.
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", context_str_example)]
+macro_rules! context_str {
+    ($($arg:tt)*) => {{
+        $crate::context_format_check!($($arg)*);
+        $crate::context_formatted!($($arg)*)
+    }};
+}
+
+thread_local! {
+    ///Set with [`set_context_prefix`]
+    static CONTEXT_PREFIX: std::cell::RefCell<Option<String>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Sets a prefix added to the start of every [`context!`] string created on the current thread.
+///
+/// Useful for ambient information set once per task, like a request ID or a service name,
+/// so `.with_context(context!())` carries it automatically. Output becomes `"{prefix} src/file.rs:42\r\nmessage"`.
+///
+/// The prefix is **thread-local**: other threads (including ones spawned from this one) don't see it,
+/// and async tasks moved between threads by a multi-threaded runtime can lose it or pick up
+/// a prefix of another task. Set it again at the start of every task, or use [`clear_context_prefix`] when the task ends.
+///
+/// Read when the context closure is called (on error), not when it is created.
+/// Macros built on [`context!`] ([`context_str!`], [`bail_ctx!`], [`ensure_ctx!`], [`wrap_ctx!`], [`context_err!`]
+/// and their `_std` versions) add the prefix too, only [`context_code!`] output still starts with `[code=...]`.
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", context_prefix_example)]
+pub fn set_context_prefix(prefix: String) {
+    CONTEXT_PREFIX.with(|current| *current.borrow_mut() = Some(prefix));
+}
+
+/// Removes the prefix set with [`set_context_prefix`] on the current thread.
+pub fn clear_context_prefix() {
+    CONTEXT_PREFIX.with(|current| *current.borrow_mut() = None);
+}
+
+#[doc(hidden)]
+///Adds the prefix set with [`set_context_prefix`] (if any) to the context
+pub fn with_context_prefix(context: String) -> String {
+    CONTEXT_PREFIX.with(|current| match current.borrow().as_deref() {
+        Some(prefix) => format!("{prefix} {context}"),
+        None => context,
+    })
+}
+
+#[doc(hidden)]
+#[cfg(feature = "std-only")]
+///Context created by [`context_map_err!`], followed by the original error
+pub fn context_caused_by(context: String, err: &impl std::fmt::Display) -> String {
+    format!("{context}\r\nCaused by: {err}")
+}
+
+#[doc(hidden)]
+///`strip-location-in-release` feature is enabled, checked together with `cfg!(debug_assertions)`
+///inside of macro expansions, so the profile of the crate using [`context!`] decides
+pub const STRIP_LOCATION_IN_RELEASE: bool = cfg!(feature = "strip-location-in-release");
+
+#[doc(hidden)]
+///Used by `#[always_context]` with `#[context(once)]` for `?` inside of a chain already carrying context (`a()?.b()?`)
+///
+///`Result` is returned unchanged. `Option` has no error which could be propagated, `None` becomes
+/// [`NoneWithContext`] error with `context` as its message
+pub trait ContextOnce {
+    type Output;
+
+    fn context_once<C, F>(self, context: F) -> Self::Output
+    where
+        C: std::fmt::Display + Send + Sync + 'static,
+        F: FnOnce() -> C;
+}
+
+impl<T, E> ContextOnce for Result<T, E> {
+    type Output = Result<T, E>;
+
+    fn context_once<C, F>(self, _context: F) -> Self::Output
+    where
+        C: std::fmt::Display + Send + Sync + 'static,
+        F: FnOnce() -> C,
+    {
+        self
+    }
+}
+
+impl<T> ContextOnce for Option<T> {
+    type Output = Result<T, NoneWithContext>;
+
+    fn context_once<C, F>(self, context: F) -> Self::Output
+    where
+        C: std::fmt::Display + Send + Sync + 'static,
+        F: FnOnce() -> C,
+    {
+        self.ok_or_else(|| NoneWithContext(context().to_string()))
+    }
+}
+
+#[doc(hidden)]
+///`None` found by [`ContextOnce`], displayed as the context
+#[derive(Debug)]
+pub struct NoneWithContext(pub String);
+
+impl std::fmt::Display for NoneWithContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for NoneWithContext {}
+
+#[doc(hidden)]
+#[macro_export]
+/// String created by [`context!`] closure, uses the formatter set with [`set_context_formatter`] if there is one
+macro_rules! context_formatted {
+    (@json $(, $($rest:tt)*)?) => {
+        $crate::context_json!($($($rest)*)?)
+    };
+    ($($arg:tt)*) => {
+        $crate::with_context_prefix(match $crate::context_formatter() {
+            //Formatter receives the location, it's skipped when the location is stripped
+            Some(formatter) if !$crate::STRIP_LOCATION_IN_RELEASE || cfg!(debug_assertions) => {
+                formatter(
+                    file!(),
+                    line!(),
+                    $crate::context_message!($($arg)*),
+                    &$crate::context_options!($($arg)*),
+                )
+            }
+            _ => $crate::context_internal!($($arg)*),
+        })
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+/// Message part of [`context!`] input (without the location) as `&str`, skips `@...` options
+macro_rules! context_message {
+    () => {
+        ""
+    };
+    (@sep = $sep:literal $(, $($rest:tt)*)?) => {
+        $crate::context_message!($($($rest)*)?)
+    };
+    (@thread $(, $($rest:tt)*)?) => {
+        $crate::context_message!($($($rest)*)?)
+    };
+    (@oneline $(, $($rest:tt)*)?) => {
+        $crate::context_message!($($($rest)*)?)
+    };
+    (@fn_name $(, $($rest:tt)*)?) => {
+        $crate::context_message!($($($rest)*)?)
+    };
+    ($($arg:tt)*) => {
+        &format!($($arg)*)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+/// [`ContextOptions`] from `@...` options of [`context!`] input, skips the message
+macro_rules! context_options {
+    (@sep = $sep:literal $(, $($rest:tt)*)?) => {
+        $crate::ContextOptions {
+            sep: ::core::option::Option::Some($sep),
+            ..$crate::context_options!($($($rest)*)?)
+        }
+    };
+    (@thread $(, $($rest:tt)*)?) => {
+        $crate::ContextOptions {
+            thread: true,
+            ..$crate::context_options!($($($rest)*)?)
+        }
+    };
+    (@oneline $(, $($rest:tt)*)?) => {
+        $crate::ContextOptions {
+            oneline: true,
+            ..$crate::context_options!($($($rest)*)?)
+        }
+    };
+    (@fn_name $(, $($rest:tt)*)?) => {
+        $crate::ContextOptions {
+            fn_name: ::core::option::Option::Some(__FN_NAME),
+            ..$crate::context_options!($($($rest)*)?)
+        }
+    };
+    ($($arg:tt)*) => {
+        <$crate::ContextOptions as ::core::default::Default>::default()
+    };
+}
+
+#[cfg(feature = "context-json")]
+#[doc(hidden)]
+pub use serde_json as __serde_json;
+
+#[cfg(feature = "context-json")]
+#[doc(hidden)]
+#[macro_export]
+/// `@json` mode of [`context!`], `{"file": ..., "line": ..., "msg": ..., key: value, ...}`
+macro_rules! context_json {
+    ($($msg:literal)? $(, $key:ident = $value:expr)* $(,)?) => {{
+        let mut object = $crate::__serde_json::Map::new();
+        if !$crate::STRIP_LOCATION_IN_RELEASE || cfg!(debug_assertions) {
+            object.insert("file".to_owned(), file!().into());
+            object.insert("line".to_owned(), line!().into());
+        }
+        $(object.insert("msg".to_owned(), format!($msg).into());)?
+        $(
+            object.insert(stringify!($key).to_owned(), $crate::context_json_value(&$value));
+        )*
+        $crate::__serde_json::Value::Object(object).to_string()
+    }};
+}
+
+#[cfg(not(feature = "context-json"))]
+#[doc(hidden)]
+#[macro_export]
+/// `@json` mode of [`context!`] without the `context-json` feature
+macro_rules! context_json {
+    ($($arg:tt)*) => {
+        compile_error!(
+            "`context!(@json, ...)` requires `context-json` feature of easy-macros-helpers"
+        )
+    };
+}
+
+#[cfg(feature = "context-json")]
+#[doc(hidden)]
+///Field value of `context!(@json, ...)`, serialization errors (like maps with non-string keys) are stored as the value
+pub fn context_json_value<T: serde::Serialize + ?Sized>(value: &T) -> serde_json::Value {
+    serde_json::to_value(value)
+        .unwrap_or_else(|err| serde_json::Value::String(format!("<serialization error: {err}>")))
+}
+
+thread_local! {
+    ///Set with [`set_context_formatter`], `None` - layout chosen at compile time (default one or `@...` options)
+    static CONTEXT_FORMATTER: std::cell::Cell<Option<ContextFormatter>> = const { std::cell::Cell::new(None) };
+}
+
+/// Function creating [`context!`] strings, receives `file!()`, `line!()`, the formatted message (empty if there is none)
+/// and `@...` options of the call
+pub type ContextFormatter =
+    fn(file: &str, line: u32, msg: &str, options: &ContextOptions) -> String;
+
+/// `@...` options of a single [`context!`] call, passed to the formatter set with [`set_context_formatter`]
+///
+/// All fields are empty when the call has no options, [`default_context_formatter`] lays them out
+/// the same way as [`context!`] without a custom formatter.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContextOptions {
+    /// `@sep = "..."`, separator between the location and the message
+    pub sep: Option<&'static str>,
+    /// `@thread`, name of the current thread should be included (`std::thread::current().name()`)
+    pub thread: bool,
+    /// `@oneline`, `"message (at file:line)"` layout is requested
+    pub oneline: bool,
+    /// `@fn_name`, name of the function from [`with_fn_name`]
+    pub fn_name: Option<&'static str>,
+}
+
+/// Replaces the layout of every [`context!`] string created on the current thread.
+///
+/// For bespoke error formats, e.g. `"[file:line] message"` or JSON. The formatter is called when
+/// the context closure is called (on error), with the message already formatted.
+/// [`default_context_formatter`] creates the default layout and can be used inside of a custom formatter.
+///
+/// Like [`set_context_prefix`], the formatter is **thread-local**, set it at the start of every thread
+/// (or task) that should use it.
+///
+/// `@sep`, `@thread`, `@oneline` and `@fn_name` options of [`context!`] are passed to the formatter in [`ContextOptions`],
+/// it decides how (and if) they are shown. [`set_context_prefix`] is still added before the formatter output. [`bail_ctx!`], [`ensure_ctx!`] and [`wrap_ctx!`]
+/// use [`context!`], so they use the formatter too, [`context_code!`] keeps the default layout.
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", context_formatter_example)]
+pub fn set_context_formatter(formatter: ContextFormatter) {
+    CONTEXT_FORMATTER.with(|current| current.set(Some(formatter)));
+}
+
+/// Removes the formatter set with [`set_context_formatter`] on the current thread,
+/// [`context!`] goes back to the layout chosen at compile time.
+pub fn reset_context_formatter() {
+    CONTEXT_FORMATTER.with(|current| current.set(None));
+}
+
+/// Default layout of [`context!`]: `"file:line"` for an empty `msg`, `"file:line\r\nmsg"` otherwise.
+///
+/// `options` change it the same way as in [`context!`] without a custom formatter
+/// (`"file:line (fn: name, thread: name){sep}msg"`, `"msg (at file:line, fn: name, thread: name)"` with `oneline`).
+pub fn default_context_formatter(
+    file: &str,
+    line: u32,
+    msg: &str,
+    options: &ContextOptions,
+) -> String {
+    let mut details = Vec::new();
+    if let Some(fn_name) = options.fn_name {
+        details.push(format!("fn: {fn_name}"));
+    }
+    if options.thread {
+        let thread = std::thread::current();
+        details.push(format!("thread: {}", thread.name().unwrap_or("unnamed")));
+    }
+
+    if options.oneline {
+        let mut location = format!("{file}:{line}");
+        for detail in details {
+            location.push_str(", ");
+            location.push_str(&detail);
+        }
+        return if msg.is_empty() {
+            location
+        } else {
+            format!("{msg} (at {location})")
+        };
+    }
+
+    let mut location = format!("{file}:{line}");
+    if !details.is_empty() {
+        location = format!("{location} ({})", details.join(", "));
+    }
+    if msg.is_empty() {
+        location
+    } else {
+        format!("{location}{}{msg}", options.sep.unwrap_or("\r\n"))
+    }
+}
+
+#[doc(hidden)]
+///Formatter set with [`set_context_formatter`] on the current thread
+pub fn context_formatter() -> Option<ContextFormatter> {
+    CONTEXT_FORMATTER.with(|current| current.get())
+}
+
+#[doc(hidden)]
+#[macro_export]
+/// Adds syntax checking from format! macro, skips `@...` options of [`context!`]
+macro_rules! context_format_check {
+    () => {};
+    //Checked by `context_json!` itself
+    (@json $($rest:tt)*) => {};
+    (@sep = $sep:literal $(, $($rest:tt)*)?) => {
+        $crate::context_format_check!($($($rest)*)?)
+    };
+    (@thread $(, $($rest:tt)*)?) => {
+        $crate::context_format_check!($($($rest)*)?)
+    };
+    (@oneline $(, $($rest:tt)*)?) => {
+        $crate::context_format_check!($($($rest)*)?)
+    };
+    (@fn_name $(, $($rest:tt)*)?) => {
+        $crate::context_format_check!($($($rest)*)?)
+    };
+    ($($arg:tt)*) => {
+        let _ = || {
+            let _ = format!($($arg)*);
+        };
+    };
+}
+
+#[macro_export]
+/// Returns early with an [anyhow](https://crates.io/crates/anyhow) error, which message contains file and line information.
+///
+/// Equivalent to `return Err(anyhow::Error::msg(context!(...)()))`, so the error message has the same
+/// format as the one produced by [`context!`]. Works like `anyhow::bail!`, but keeps the location of the failure.
+///
+/// Supports the same syntax as [`context!`] (and [`format!`]).
+///
+/// # Syntax
+///
+/// ```ignore
+/// bail_ctx!()                         // Just file:line info
+/// bail_ctx!("message")                // Static message with file:line
+/// bail_ctx!("format {}", arg)         // Formatted message with file:line
+/// ```
+///
+/// # Requirements
+///
+/// The `anyhow` crate needs to be present in the dependencies, the surrounding function
+/// has to return `anyhow::Result`.
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", bail_ctx_example)]
+macro_rules! bail_ctx {
+    ($($arg:tt)*) => {
+        return ::core::result::Result::Err(::anyhow::Error::msg(($crate::context!($($arg)*))()))
+    };
+}
+
+#[cfg(feature = "std-only")]
+#[macro_export]
+/// Version of [`bail_ctx!`] without `anyhow`, returns early with an error created from the context string.
+///
+/// The string is converted with `From::from`, so the surrounding function can return
+/// `Result<T, Box<dyn std::error::Error>>` (also with `+ Send + Sync`) or `Result<T, String>`.
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", std_only_bail_ctx_example)]
+macro_rules! bail_ctx_std {
+    ($($arg:tt)*) => {
+        return ::core::result::Result::Err(::core::convert::From::from($crate::context_str!($($arg)*)))
+    };
+}
+
+#[macro_export]
+/// Returns early with an [anyhow](https://crates.io/crates/anyhow) error if the condition is `false`,
+/// error message contains file and line information.
+///
+/// Works like `anyhow::ensure!`, the error is created in the same way as in [`bail_ctx!`].
+/// Message arguments support the same syntax as [`context!`] (and [`format!`]).
+///
+/// # Syntax
+///
+/// ```ignore
+/// ensure_ctx!(cond)                       // Just file:line info
+/// ensure_ctx!(cond, "message")            // Static message with file:line
+/// ensure_ctx!(cond, "format {}", arg)     // Formatted message with file:line
+/// ```
+///
+/// # Requirements
+///
+/// The `anyhow` crate needs to be present in the dependencies, the surrounding function
+/// has to return `anyhow::Result`. Use `ensure_ctx_std!` (`std-only` feature) without `anyhow`.
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", ensure_ctx_example)]
+macro_rules! ensure_ctx {
+    ($cond:expr $(,)?) => {
+        if !$cond {
+            $crate::bail_ctx!();
         }
     };
+    ($cond:expr, $($arg:tt)*) => {
+        if !$cond {
+            $crate::bail_ctx!($($arg)*);
+        }
+    };
+}
+
+#[cfg(feature = "std-only")]
+#[macro_export]
+/// Version of [`ensure_ctx!`] without `anyhow`, the error is created the same way as in [`bail_ctx_std!`].
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", std_only_bail_ctx_example)]
+macro_rules! ensure_ctx_std {
+    ($cond:expr $(,)?) => {
+        if !$cond {
+            $crate::bail_ctx_std!();
+        }
+    };
+    ($cond:expr, $($arg:tt)*) => {
+        if !$cond {
+            $crate::bail_ctx_std!($($arg)*);
+        }
+    };
+}
+
+#[macro_export]
+/// Wraps an existing error into an [anyhow](https://crates.io/crates/anyhow) error with file and line information added as context.
+///
+/// Equivalent to `anyhow::Error::from(err).context(context!(...)())`, the original error stays
+/// in the error chain. Complements [`bail_ctx!`] and [`ensure_ctx!`] when the error already exists.
+///
+/// Message arguments support the same syntax as [`context!`] (and [`format!`]).
+///
+/// # Syntax
+///
+/// ```ignore
+/// wrap_ctx!(err)                       // Just file:line info
+/// wrap_ctx!(err, "message")            // Static message with file:line
+/// wrap_ctx!(err, "format {}", arg)     // Formatted message with file:line
+/// ```
+///
+/// # Requirements
+///
+/// The `anyhow` crate needs to be present in the dependencies, `err` has to be convertible
+/// into `anyhow::Error` (`anyhow::Error` itself or any `std::error::Error + Send + Sync + 'static`).
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", wrap_ctx_example)]
+macro_rules! wrap_ctx {
+    ($err:expr $(,)?) => {
+        ::anyhow::Error::from($err).context(($crate::context!())())
+    };
+    ($err:expr, $($arg:tt)*) => {
+        ::anyhow::Error::from($err).context(($crate::context!($($arg)*))())
+    };
+}
+
+#[cfg(feature = "std-only")]
+#[macro_export]
+/// Version of [`wrap_ctx!`] without `anyhow`, returns `Box<dyn std::error::Error + Send + Sync>`.
+///
+/// The original error (`Display`) is added after the context, same as with [`context_map_err!`].
+macro_rules! wrap_ctx_std {
+    ($err:expr $(,)?) => {
+        ::std::boxed::Box::<dyn ::std::error::Error + ::core::marker::Send + ::core::marker::Sync>::from(
+            ($crate::context_map_err!())($err),
+        )
+    };
+    ($err:expr, $($arg:tt)*) => {
+        ::std::boxed::Box::<dyn ::std::error::Error + ::core::marker::Send + ::core::marker::Sync>::from(
+            ($crate::context_map_err!($($arg)*))($err),
+        )
+    };
+}
+
+#[macro_export]
+/// Creates a closure returning an [anyhow](https://crates.io/crates/anyhow) error with file and line information, for `Option::ok_or_else`.
+///
+/// Equivalent to `|| anyhow::Error::msg(context!(...)())`, the error message has the same format as
+/// the one produced by [`context!`]. Like with [`context!`], nothing is formatted until the closure is called.
+///
+/// # Syntax
+///
+/// ```ignore
+/// opt.ok_or_else(context_err!())                   // Just file:line info
+/// opt.ok_or_else(context_err!("message"))          // Static message with file:line
+/// opt.ok_or_else(context_err!("format {}", arg))   // Formatted message with file:line
+/// ```
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", context_err_example)]
+macro_rules! context_err {
+    ($($arg:tt)*) => {
+        || ::anyhow::Error::msg(($crate::context!($($arg)*))())
+    };
+}
+
+#[cfg(feature = "std-only")]
+#[macro_export]
+/// Version of [`context_err!`] without `anyhow`, the closure returns `Box<dyn std::error::Error + Send + Sync>`.
+macro_rules! context_err_std {
     ($($arg:tt)*) => {
+        || ::std::boxed::Box::<dyn ::std::error::Error + ::core::marker::Send + ::core::marker::Sync>::from(
+            $crate::context_str!($($arg)*),
+        )
+    };
+}
+
+#[macro_export]
+/// Same as [`context!`], but the context string starts with a machine readable error code.
+///
+/// The code can be read back from the error message with [`extract_code`]. Any [`Display`](std::fmt::Display)
+/// value can be used as the code (it shouldn't contain `]`).
+///
+/// # Syntax
+///
+/// ```ignore
+/// context_code!(code)                         // Code with file:line info
+/// context_code!(code, "message")              // Code with static message and file:line
+/// context_code!(code, "format {}", arg)       // Code with formatted message and file:line
+/// ```
+///
+/// # Output Format
+///
+/// - **With no message:** `"[code=E1234] src/file.rs:line_number"`
+/// - **With message:** `"[code=E1234] src/file.rs:line_number\r\nYour custom message here"`
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", context_code_example)]
+macro_rules! context_code {
+    ($code:expr $(,)?) => {
         || {
-            // Adds syntax checking from format! macro
-            let _ = || {
-                let _ = format!($($arg)*);
-            };
-            $crate::context_internal!($($arg)*)
+            $crate::context_code_internal!($code)
         }
     };
+    ($code:expr, $($arg:tt)*) => {
+        || {
+            $crate::context_format_check!($($arg)*);
+            $crate::context_code_internal!($code, $($arg)*)
+        }
+    };
+}
+
+/// Creates a context string with the same layout as [`context!`], for messages built at runtime.
+///
+/// Useful when the format string isn't a literal, which [`context!`] requires.
+/// Returns `"file:line"` for an empty `msg`, `"file:line\r\nmsg"` otherwise. Like with [`context!`],
+/// the formatter set with [`set_context_formatter`] and the prefix set with [`set_context_prefix`] are used.
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", context_string_example)]
+pub fn context_string(file: &str, line: u32, msg: String) -> String {
+    let formatter = context_formatter().unwrap_or(default_context_formatter);
+    with_context_prefix(formatter(file, line, &msg, &ContextOptions::default()))
+}
+
+/// Reads the error code from a message created with [`context_code!`].
+///
+/// Returns `None` if the message doesn't start with the `[code=...]` prefix.
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", context_code_example)]
+pub fn extract_code(err_msg: &str) -> Option<&str> {
+    let rest = err_msg.strip_prefix("[code=")?;
+    let end = rest.find(']')?;
+    Some(&rest[..end])
+}
+
+/// Renders the whole chain of `err`, collapsing consecutive [`context!`] entries from the same file.
+///
+/// When many `?` in one function add [`context!`], the chain repeats the same file path.
+/// Consecutive entries with the same file are written as one `"file:line, line"` header
+/// followed by their messages (outermost first). Groups and other errors in the chain
+/// (like the root cause) are separated with `"\r\nCaused by: "`.
+///
+/// Only the default layout (`"file:line"` on the first line) is recognized, entries with other layouts
+/// (`@oneline`, `@fn_name`, custom formatters, ...) are written unchanged. This is a display-time helper,
+/// the error itself isn't changed.
+///
+/// Accepts `&anyhow::Error` and `&Box<dyn Error + Send + Sync>`, the chain is read with [`source`](std::error::Error::source).
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", dedup_context_chain_example)]
+pub fn dedup_context_chain(err: &impl AsRef<dyn std::error::Error + Send + Sync>) -> String {
+    ///Consecutive entries from one file, or a single entry without a recognized location
+    struct Group {
+        file: Option<String>,
+        lines: Vec<String>,
+        messages: Vec<String>,
+    }
+
+    let mut groups: Vec<Group> = Vec::new();
+    let mut cause: Option<&dyn std::error::Error> = Some(err.as_ref());
+    while let Some(current) = cause {
+        cause = current.source();
+        let entry = current.to_string();
+        let (first_line, message) = entry.split_once("\r\n").unwrap_or((entry.as_str(), ""));
+        let location = first_line
+            .rsplit_once(':')
+            .filter(|(file, line)| !file.is_empty() && line.parse::<u32>().is_ok());
+
+        match (location, groups.last_mut()) {
+            (Some((file, line)), Some(group)) if group.file.as_deref() == Some(file) => {
+                group.lines.push(line.to_string());
+                group
+                    .messages
+                    .extend((!message.is_empty()).then(|| message.to_string()));
+            }
+            (Some((file, line)), _) => groups.push(Group {
+                file: Some(file.to_string()),
+                lines: vec![line.to_string()],
+                messages: (!message.is_empty())
+                    .then(|| message.to_string())
+                    .into_iter()
+                    .collect(),
+            }),
+            (None, _) => groups.push(Group {
+                file: None,
+                lines: Vec::new(),
+                messages: vec![entry],
+            }),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|group| match group.file {
+            Some(file) => std::iter::once(format!("{file}:{}", group.lines.join(", ")))
+                .chain(group.messages)
+                .collect::<Vec<_>>()
+                .join("\r\n"),
+            None => group.messages.concat(),
+        })
+        .collect::<Vec<_>>()
+        .join("\r\nCaused by: ")
 }
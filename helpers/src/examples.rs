@@ -7,12 +7,65 @@
 
 use crate::*;
 
+// Context macro examples, `std-only` feature (no anyhow)
+#[cfg(feature = "std-only")]
+mod std_only_examples {
+    use super::*;
+
+    #[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+    #[docify::export_content]
+    #[test]
+    fn std_only_context_example() {
+        fn parse_port(port: &str) -> Result<u16, String> {
+            port.parse::<u16>()
+                .map_err(context_map_err!("Invalid port {}", port))
+        }
+
+        let line = line!() - 3;
+        let error = parse_port("http").unwrap_err();
+        assert_eq!(
+            error,
+            format!(
+                "src/examples.rs:{line}\r\nInvalid port http\r\nCaused by: invalid digit found in string"
+            )
+        );
+    }
+
+    #[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+    #[docify::export_content]
+    #[test]
+    fn std_only_bail_ctx_example() {
+        fn check_port(port: u16) -> Result<u16, Box<dyn std::error::Error>> {
+            if port == 0 {
+                bail_ctx_std!("Port can't be zero");
+            }
+            ensure_ctx_std!(port >= 1024, "Port {} is reserved", port);
+            Ok(port)
+        }
+
+        assert_eq!(check_port(8080).unwrap(), 8080);
+        assert!(
+            check_port(0)
+                .unwrap_err()
+                .to_string()
+                .ends_with("\r\nPort can't be zero")
+        );
+        assert!(
+            check_port(80)
+                .unwrap_err()
+                .to_string()
+                .ends_with("\r\nPort 80 is reserved")
+        );
+    }
+}
+
 // Context macro examples
 #[cfg(feature = "context")]
 mod context_examples {
     use super::*;
     use anyhow::Context;
 
+    #[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
     #[docify::export_content]
     #[test]
     fn readme_context_basic_example() {
@@ -39,24 +92,231 @@ mod context_examples {
         );
     }
 
+    #[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
     #[docify::export_content]
     #[test]
-    fn context_basic_usage_example() {
-        use std::fs;
+    fn bail_ctx_example() {
+        fn parse_port(port: i64) -> anyhow::Result<u16> {
+            if !(0..=65535).contains(&port) {
+                bail_ctx!("Port {} is out of range", port);
+            }
+            Ok(port as u16)
+        }
+
+        assert_eq!(parse_port(8080).unwrap(), 8080);
+
+        let error_msg = parse_port(70000).unwrap_err().to_string();
+        assert_eq!(
+            error_msg,
+            format!(
+                "src/examples.rs:{}\r\nPort 70000 is out of range",
+                line!() - 12 // bail_ctx! is called 12 lines above
+            )
+        );
+    }
+
+    #[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+    #[docify::export_content]
+    #[test]
+    fn ensure_ctx_example() {
+        fn divide(a: i32, b: i32) -> anyhow::Result<i32> {
+            ensure_ctx!(b != 0, "Cannot divide {} by zero", a);
+            Ok(a / b)
+        }
+
+        assert_eq!(divide(10, 2).unwrap(), 5);
+
+        let error_msg = divide(10, 0).unwrap_err().to_string();
+        assert_eq!(
+            error_msg,
+            format!(
+                "src/examples.rs:{}\r\nCannot divide 10 by zero",
+                line!() - 11 // ensure_ctx! is called 11 lines above
+            )
+        );
+    }
+
+    #[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+    #[docify::export_content]
+    #[test]
+    fn wrap_ctx_example() {
+        let path = "/nonexistent/config.toml";
+        let io_error = std::fs::read_to_string(path).unwrap_err();
+
+        let line = line!() + 1;
+        let error = wrap_ctx!(io_error, "Failed to read {}", path);
+        assert_eq!(
+            error.to_string(),
+            format!("src/examples.rs:{line}\r\nFailed to read /nonexistent/config.toml")
+        );
+        // The original error is kept as the source
+        assert!(error.downcast_ref::<std::io::Error>().is_some());
+    }
+
+    #[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+    #[docify::export_content]
+    #[test]
+    fn dedup_context_chain_example() {
+        fn read_port(config: &str) -> anyhow::Result<u16> {
+            let port = config
+                .parse::<u16>()
+                .with_context(context!("Parsing port"))?;
+            Ok(port)
+        }
+
+        fn start(config: &str) -> anyhow::Result<u16> {
+            let port = read_port(config).with_context(context!("Starting server"))?;
+            Ok(port)
+        }
+
+        let line = line!() - 9; // first context! is called 9 lines above
+        let error = start("http").unwrap_err();
+        assert_eq!(
+            dedup_context_chain(&error),
+            format!(
+                "src/examples.rs:{}, {line}\r\nStarting server\r\nParsing port\r\n\
+                 Caused by: invalid digit found in string",
+                line + 5
+            )
+        );
+    }
+
+    #[docify::export_content]
+    #[test]
+    fn context_string_example() {
+        let fields = ["id", "name"];
+        // Message is built at runtime, context! can't be used here
+        let msg = format!("Missing fields: {}", fields.join(", "));
 
-        fn risky_operation() -> anyhow::Result<String> {
-            // This will show "src/examples.rs:line" if it fails
-            fs::read_to_string("missing_file.txt").with_context(context!())
+        let line = line!();
+        let error = anyhow::Error::msg(context_string(file!(), line, msg));
+        assert_eq!(
+            error.to_string(),
+            format!("src/examples.rs:{line}\r\nMissing fields: id, name")
+        );
+    }
+
+    #[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+    #[docify::export_content]
+    #[test]
+    fn context_prefix_example() {
+        fn load_user(id: u64) -> anyhow::Result<String> {
+            std::fs::read_to_string(format!("users/{id}.json"))
+                .with_context(context!("Loading user {}", id))
         }
 
-        let result = risky_operation();
+        // Set once at the start of the request handling
+        set_context_prefix("[request=7f3a]".to_string());
+
+        let line = line!() - 6; // context! is called 6 lines above
+        let error = load_user(42).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!("[request=7f3a] src/examples.rs:{line}\r\nLoading user 42")
+        );
+
+        clear_context_prefix();
+    }
+
+    #[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+    #[docify::export_content]
+    #[test]
+    fn context_err_example() {
+        fn find_port(config: &[(&str, u16)]) -> anyhow::Result<u16> {
+            let (_, port) = config
+                .iter()
+                .find(|(key, _)| *key == "port")
+                .ok_or_else(context_err!("Missing `port` in {} entries", config.len()))?;
+            Ok(*port)
+        }
+
+        assert_eq!(find_port(&[("port", 8080)]).unwrap(), 8080);
+
+        let line = line!() - 6; // context_err! is called 6 lines above
+        let error = find_port(&[("host", 1)]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!("src/examples.rs:{line}\r\nMissing `port` in 1 entries")
+        );
+    }
+
+    #[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+    #[docify::export_content]
+    #[test]
+    fn context_str_example() {
+        let attempts = 3;
+        let line = line!() + 1;
+        let message: String = context_str!("Gave up after {} attempts", attempts);
+        assert_eq!(
+            message,
+            format!("src/examples.rs:{line}\r\nGave up after 3 attempts")
+        );
+    }
+
+    #[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+    #[docify::export_content]
+    #[test]
+    fn context_formatter_example() {
+        fn bracketed(file: &str, line: u32, msg: &str, _options: &ContextOptions) -> String {
+            format!("[{file}:{line}] {msg}")
+        }
+
+        fn load_user(id: u64) -> anyhow::Result<String> {
+            std::fs::read_to_string(format!("users/{id}.json"))
+                .with_context(context!("Loading user {}", id))
+        }
+
+        // Set once at the start of the thread
+        set_context_formatter(bracketed);
+
+        let line = line!() - 6; // context! is called 6 lines above
+        let error = load_user(42).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!("[src/examples.rs:{line}] Loading user 42")
+        );
+
+        reset_context_formatter();
+    }
+
+    #[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+    #[docify::export_content]
+    #[test]
+    fn context_code_example() {
+        let rows: Vec<String> = Vec::new();
+        let user = rows.into_iter().next().context("No rows returned");
+
+        let id = 7;
+        let line = line!() + 1;
+        let user = user.with_context(context_code!("E1234", "User {} not found", id));
+
+        let error_msg = user.unwrap_err().to_string();
+        assert_eq!(
+            error_msg,
+            format!("[code=E1234] src/examples.rs:{line}\r\nUser 7 not found")
+        );
+
+        // Code can be read back from the message
+        assert_eq!(extract_code(&error_msg), Some("E1234"));
+    }
+
+    #[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+    #[docify::export_content]
+    #[test]
+    fn context_basic_usage_example() {
+        use std::fs;
+
+        // This will show "src/examples.rs:line" if it fails
+        let line = line!() + 1;
+        let result = fs::read_to_string("missing_file.txt").with_context(context!());
         assert!(result.is_err());
 
         let error_msg = format!("{:?}", result.unwrap_err());
         // Should contain file path and line
-        assert!(error_msg.contains(format!("src/examples.rs:{}", line!() - 8).as_str()));
+        assert!(error_msg.contains(format!("src/examples.rs:{line}").as_str()));
     }
 
+    #[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
     #[docify::export_content]
     #[test]
     fn context_with_custom_message_example() {
@@ -81,6 +341,7 @@ mod context_examples {
         );
     }
 
+    #[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
     #[docify::export_content]
     #[test]
     fn context_with_formatted_message_example() {
@@ -121,6 +382,7 @@ mod context_examples {
         );
     }
 
+    #[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
     #[docify::export_content]
     #[test]
     fn context_chaining_multiple_levels_example() {
@@ -161,6 +423,7 @@ mod context_examples {
         );
     }
 
+    #[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
     #[docify::export_content]
     #[test]
     fn context_manual_generation_example() {
@@ -176,6 +439,98 @@ mod context_examples {
             )
         );
     }
+
+    #[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+    #[docify::export_content]
+    #[test]
+    fn context_custom_separator_example() {
+        let line = line!() + 1;
+        let ctx = context!(@sep = "\n", "Operation failed with code {}", 500);
+
+        assert_eq!(
+            ctx(),
+            format!("src/examples.rs:{line}\nOperation failed with code 500")
+        );
+
+        // Any separator works, not only new lines
+        let line = line!() + 1;
+        let ctx = context!(@sep = " | ", "Operation failed");
+        assert_eq!(ctx(), format!("src/examples.rs:{line} | Operation failed"));
+    }
+
+    #[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+    #[docify::export_content]
+    #[test]
+    fn context_oneline_example() {
+        let user_id = 42;
+        let line = line!() + 1;
+        let ctx = context!(@oneline, "Failed to load user {}", user_id);
+
+        assert_eq!(
+            ctx(),
+            format!("Failed to load user 42 (at src/examples.rs:{line})")
+        );
+    }
+
+    #[cfg(all(
+        feature = "context-json",
+        any(debug_assertions, not(feature = "strip-location-in-release"))
+    ))]
+    #[docify::export_content]
+    #[test]
+    fn context_json_example() {
+        let user_id = 42;
+        let roles = ["admin"];
+        let line = line!() + 1;
+        let ctx = context!(@json, "Failed to load user {user_id}", id = user_id, roles = roles);
+
+        let parsed: serde_json::Value = serde_json::from_str(&ctx()).unwrap();
+        assert_eq!(
+            parsed,
+            serde_json::json!({
+                "file": "src/examples.rs",
+                "line": line,
+                "msg": "Failed to load user 42",
+                "id": 42,
+                "roles": ["admin"],
+            })
+        );
+    }
+
+    #[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+    #[docify::export_content]
+    #[test]
+    fn with_fn_name_example() {
+        let line = line!() + 3;
+        #[with_fn_name]
+        fn load_config() -> anyhow::Result<()> {
+            Err(anyhow::anyhow!("missing file")).with_context(context!("Loading config"))
+        }
+
+        let error = load_config().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!("src/examples.rs:{line} (fn: load_config)\r\nLoading config")
+        );
+    }
+
+    #[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+    #[docify::export_content]
+    #[test]
+    fn context_thread_name_example() {
+        let handle = std::thread::Builder::new()
+            .name("worker-1".to_string())
+            .spawn(|| {
+                let line = line!() + 1;
+                let ctx = context!(@thread, "Request failed");
+                assert_eq!(
+                    ctx(),
+                    format!("src/examples.rs:{line} (thread: worker-1)\r\nRequest failed")
+                );
+            })
+            .unwrap();
+        handle.join().unwrap();
+    }
 }
 
 // TokensBuilder examples
@@ -218,6 +573,208 @@ mod full_examples {
         );
     }
 
+    #[docify::export_content]
+    #[test]
+    fn tokens_builder_dedup_items_example() {
+        let mut builder = TokensBuilder::default();
+        // Two generators both needing the same import
+        builder.add(quote! { use std::fmt::Display; });
+        builder.add(quote! { struct First; });
+        builder.add(quote! { use std::fmt::Display; });
+        builder.add(quote! { struct Second; });
+
+        builder.dedup_items();
+
+        assert_eq!(
+            builder.finalize().to_string(),
+            quote! {
+                use std::fmt::Display;
+                struct First;
+                struct Second;
+            }
+            .to_string()
+        );
+    }
+
+    #[docify::export_content]
+    #[test]
+    fn tokens_builder_checkpoint_example() {
+        let mut builder = TokensBuilder::default();
+        builder.add(quote! { struct Config; });
+
+        let checkpoint = builder.checkpoint();
+        // Try generating a `Default` implementation
+        builder.add(quote! { impl Default for Config { fn default() -> Self { Config } } });
+        let derive_failed = true;
+        if derive_failed {
+            // Roll back and generate a constructor instead
+            builder.restore(checkpoint);
+            builder.add(quote! { impl Config { fn new() -> Self { Config } } });
+        }
+
+        assert_eq!(
+            builder.finalize().to_string(),
+            quote! {
+                struct Config;
+                impl Config { fn new() -> Self { Config } }
+            }
+            .to_string()
+        );
+    }
+
+    #[docify::export_content]
+    #[test]
+    fn tokens_builder_prefix_each_example() {
+        let mut builder = TokensBuilder::default();
+        for name in ["first", "second", "third"] {
+            let ident = quote::format_ident!("{}", name);
+            builder.add(quote! { fn #ident() {} });
+        }
+
+        builder.prefix_each(quote! { #[allow(dead_code)] });
+
+        assert_eq!(
+            builder.finalize().to_string(),
+            quote! {
+                #[allow(dead_code)]
+                fn first() {}
+                #[allow(dead_code)]
+                fn second() {}
+                #[allow(dead_code)]
+                fn third() {}
+            }
+            .to_string()
+        );
+    }
+
+    #[docify::export_content]
+    #[test]
+    fn tokens_builder_finalize_item_example() {
+        let mut result = TokensBuilder::default();
+        result.add(quote! { fn answer() -> i32 });
+        result.add(quote! { { 42 } });
+
+        let item = result.finalize_item().unwrap();
+        assert!(matches!(item, syn::Item::Fn(_)));
+
+        // Missing function body, caught before the tokens are returned from the macro
+        let mut broken = TokensBuilder::default();
+        broken.add(quote! { fn answer() -> i32 });
+        assert!(broken.finalize_item().is_err());
+    }
+
+    #[docify::export_content]
+    #[test]
+    fn tokens_builder_add_if_example() {
+        let with_id = true;
+        let with_name = false;
+
+        let mut fields = TokensBuilder::default();
+        fields
+            .add_if(with_id, quote! { id: u64, })
+            .add_if(with_name, quote! { name: String, });
+        let fields = fields.finalize();
+
+        let tokens = quote! { struct User { #fields } };
+        assert_eq!(
+            readable_token_stream(&tokens.to_string()),
+            "struct User { id: u64, }"
+        );
+    }
+
+    #[docify::export_content]
+    #[test]
+    fn tokens_builder_add_if_with_example() {
+        let extra_fields: Vec<syn::Ident> = vec![];
+
+        let mut result = TokensBuilder::default();
+        result
+            .add_if(true, quote! { value: i32, })
+            // Closure is called only when there are extra fields
+            .add_if_with(!extra_fields.is_empty(), || {
+                quote! { #(#extra_fields: Option<i32>,)* }
+            });
+
+        let tokens = result.finalize();
+        assert_eq!(readable_token_stream(&tokens.to_string()), "value: i32,");
+    }
+
+    #[docify::export_content]
+    #[test]
+    fn tokens_builder_add_all_example() {
+        let fields = indexed_name(parse_quote!(field), 2);
+
+        let mut result = TokensBuilder::default();
+        result
+            .add_all(fields.iter().map(|field| quote! { let #field = 0; }))
+            .add(quote! { field0 + field1 });
+
+        let tokens = result.finalize();
+        assert_eq!(
+            readable_token_stream(&tokens.to_string()),
+            "let field0 = 0; let field1 = 0; field0 + field1"
+        );
+    }
+
+    #[docify::export_content]
+    #[test]
+    fn tokens_builder_add_cfg_example() {
+        let mut result = TokensBuilder::default();
+        result.add_cfg(
+            quote! { feature = "serde" },
+            quote! {
+                impl Serialize for User {}
+                impl Deserialize for User {}
+            },
+        );
+
+        // Every item is gated separately
+        let tokens = result.finalize();
+        assert_eq!(
+            readable_token_stream(&tokens.to_string()),
+            "#[cfg(feature = \"serde\")] impl Serialize for User { } #[cfg(feature = \"serde\")] impl Deserialize for User { }"
+        );
+    }
+
+    #[docify::export_content]
+    #[test]
+    fn split_items_example() {
+        let tokens = quote! {
+            #[derive(Debug)]
+            struct User;
+            impl User {}
+            let user = User;
+        };
+
+        let chunks: Vec<String> = split_items(tokens)
+            .into_iter()
+            .map(|chunk| readable_token_stream(&chunk.to_string()))
+            .collect();
+        assert_eq!(
+            chunks,
+            vec![
+                "#[derive(Debug)] struct User;",
+                "impl User { }",
+                // Not an item, returned as the trailing chunk
+                "let user = User;",
+            ]
+        );
+    }
+
+    #[docify::export_content]
+    #[test]
+    fn tokens_builder_comment_example() {
+        let mut result = TokensBuilder::default();
+        result.comment("getters");
+        result.add(quote! { fn get_id() -> u64 { 1 } });
+
+        let tokens = result.finalize();
+        assert_eq!(
+            readable_token_stream(&tokens.to_string()),
+            "const _: &str = \"getters\"; fn get_id() -> u64 { 1 }"
+        );
+    }
+
     #[docify::export_content]
     #[test]
     fn tokens_builder_braced_example() {
@@ -233,6 +790,33 @@ mod full_examples {
         );
     }
 
+    #[docify::export_content]
+    #[test]
+    fn tokens_builder_map_tokens_example() {
+        let mut result = TokensBuilder::default();
+        result.add(quote! { fn old_name() {} });
+        result.add(quote! { old_name(); });
+
+        // Rename every `old_name` identifier
+        result.map_tokens(|tokens| {
+            tokens
+                .into_iter()
+                .map(|token| match token {
+                    proc_macro2::TokenTree::Ident(ident) if ident == "old_name" => {
+                        proc_macro2::Ident::new("new_name", ident.span()).into()
+                    }
+                    other => other,
+                })
+                .collect()
+        });
+
+        let tokens = result.finalize();
+        assert_eq!(
+            readable_token_stream(&tokens.to_string()),
+            "fn new_name() { } new_name();"
+        );
+    }
+
     #[docify::export_content]
     #[test]
     fn tokens_builder_finalize_example() {
@@ -246,6 +830,40 @@ mod full_examples {
         );
     }
 
+    #[docify::export_content]
+    #[test]
+    fn tokens_builder_into_example() {
+        let mut result = TokensBuilder::default();
+        result.add(quote! { println!("Done!"); });
+
+        // Same as `result.finalize()`
+        let final_tokens: proc_macro2::TokenStream = result.into();
+        assert_eq!(
+            readable_token_stream(&final_tokens.to_string()),
+            "println!(\"Done!\");"
+        );
+    }
+
+    #[docify::export_content]
+    #[test]
+    fn tokens_builder_interpolation_example() {
+        let mut body = TokensBuilder::default();
+        body.add(quote! { let x = 1; });
+        body.add(quote! { x + 1 });
+
+        // No need to finalize the builder before using it inside of quote!
+        let body = &body;
+        let function = quote! {
+            fn compute() -> i32 {
+                #body
+            }
+        };
+        assert_eq!(
+            readable_token_stream(&function.to_string()),
+            "fn compute() -> i32 { let x = 1; x + 1 }"
+        );
+    }
+
     // README TokensBuilder example - using extern crate name for external users
     #[docify::export_content]
     #[test]
@@ -306,6 +924,82 @@ mod full_examples {
         );
     }
 
+    #[docify::export_content]
+    #[test]
+    fn indexed_tuple_access_example() {
+        let indices = indexed_tuple_access(3);
+
+        // Clone impl body for `struct Point(i32, i32, i32);`
+        let output = quote! {
+            impl Clone for Point {
+                fn clone(&self) -> Self {
+                    Point(#(self.#indices.clone()),*)
+                }
+            }
+        };
+        let expected = quote! {
+            impl Clone for Point {
+                fn clone(&self) -> Self {
+                    Point(self.0.clone(), self.1.clone(), self.2.clone())
+                }
+            }
+        };
+        assert_eq!(output.to_string(), expected.to_string());
+    }
+
+    #[docify::export_content]
+    #[test]
+    fn indexed_name_groups_example() {
+        // Comparing two tuple variants field by field
+        let [left, right] = &indexed_name_groups(&["l", "r"], 2)[..] else {
+            unreachable!()
+        };
+
+        let output = quote! {
+            (Self::Pair(#(#left),*), Self::Pair(#(#right),*)) => true #(&& #left == #right)*
+        };
+        let expected = quote! {
+            (Self::Pair(l0, l1), Self::Pair(r0, r1)) => true && l0 == r0 && l1 == r1
+        };
+        assert_eq!(output.to_string(), expected.to_string());
+    }
+
+    #[docify::export_content]
+    #[test]
+    fn indexed_lifetime_example() {
+        let lifetimes = indexed_lifetime("a", 2);
+        let fields = indexed_name(syn::parse_quote!(field), 2);
+
+        let output = quote! {
+            struct Refs<#(#lifetimes),*> {
+                #(#fields: &#lifetimes str,)*
+            }
+        };
+        let expected = quote! {
+            struct Refs<'a0, 'a1> {
+                field0: &'a0 str,
+                field1: &'a1 str,
+            }
+        };
+        assert_eq!(output.to_string(), expected.to_string());
+    }
+
+    #[docify::export_content]
+    #[test]
+    fn unique_ident_example() {
+        // Every macro invocation gets its own helper struct
+        let first = unique_ident("helper");
+        let second = unique_ident("helper");
+        assert_ne!(first, second);
+
+        let output = quote! {
+            struct #first;
+            struct #second;
+        };
+        // `struct __helper_0 ; struct __helper_1 ;` (counter depends on previous calls)
+        assert!(output.to_string().starts_with("struct __helper_"));
+    }
+
     // find_crate examples
 
     #[docify::export_content]
@@ -364,6 +1058,53 @@ mod full_examples {
         }
     }
 
+    #[docify::export_content]
+    #[test]
+    fn find_reexport_example() {
+        // `syn` is a dependency of easy_macros_helpers, `easy-macros` is not
+        let comma = find_reexport(
+            &[
+                ("easy-macros", quote!(::helpers::syn)),
+                ("syn", quote!(::token)),
+            ],
+            "Comma",
+        );
+        assert_eq!(comma.unwrap().to_string(), "syn :: token :: Comma");
+
+        assert!(find_reexport(&[("easy-macros", quote!())], "context").is_none());
+    }
+
+    #[docify::export_content]
+    #[test]
+    fn find_crate_resolution_example() {
+        // In easy_macros_helpers Cargo.toml:
+        // syn = {version = "2.0", features = ["full"], optional = true}
+        // find-crate = ["dep:proc-macro-crate", "dep:proc-macro2", "dep:quote", "dep:syn", "dep:toml_edit"]
+        let resolution = find_crate_resolution("syn", quote!());
+        let Resolution::Optional { path, features } = &resolution else {
+            panic!("syn is an optional dependency of easy_macros_helpers");
+        };
+        assert_eq!(path.to_string(), "syn");
+        assert!(features.contains(&"find-crate".to_string()));
+        // Through `find-crate` and other features
+        assert!(features.contains(&"full".to_string()));
+
+        // Emitted next to the generated code, fails only if none of the features are enabled
+        let check = resolution.feature_check("`syn` is required.");
+        assert!(check.to_string().contains("compile_error"));
+
+        // Regular dependency
+        assert!(matches!(
+            find_crate_resolution("docify", quote!()),
+            Resolution::Found(_)
+        ));
+        // Not used by `easy_macros_helpers`
+        assert!(matches!(
+            find_crate_resolution("my_crate", quote!()),
+            Resolution::Absent
+        ));
+    }
+
     #[docify::export_content]
     fn readme_find_crate_example() {
         // Simple crate lookup
@@ -400,6 +1141,43 @@ mod full_examples {
         assert!(errors.no_errors());
     }
 
+    #[docify::export_content]
+    #[test]
+    fn error_data_finish_or_errors_example() {
+        let generated = quote! { 1 + 2 };
+
+        // No errors, generated code is returned
+        let errors = Vec::<String>::new();
+        let result = errors.finish_or_errors(generated.clone());
+        assert_eq!(result.to_string(), generated.to_string());
+
+        // An error becomes a `compile_error!` expression, generated code is dropped
+        let errors = vec!["Invalid syntax".to_string()];
+        let result = errors.finish_or_errors(generated.clone());
+        assert_eq!(
+            result.to_string(),
+            quote! { compile_error!("Invalid syntax") }.to_string()
+        );
+
+        // Multiple errors are placed in a block, still a single expression
+        let errors = vec![
+            "Invalid syntax".to_string(),
+            "Missing required field".to_string(),
+        ];
+        let result = errors.finish_or_errors(generated);
+        assert_eq!(
+            result.to_string(),
+            quote! {
+                {
+                    compile_error!("Invalid syntax");
+                    compile_error!("Missing required field")
+                }
+            }
+            .to_string()
+        );
+        assert!(syn::parse2::<syn::Expr>(result).is_ok());
+    }
+
     #[docify::export_content]
     #[test]
     fn error_data_custom_implementation() {
@@ -518,6 +1296,32 @@ mod full_examples {
         );
     }
 
+    // expr_warning_wrap example
+
+    #[docify::export_content]
+    #[test]
+    #[cfg(not(feature = "nightly-diagnostics"))]
+    fn expr_warning_wrap_basic_usage() {
+        let mut expr = parse_quote!(old_api());
+        let mut warnings = vec!["old_api is deprecated, use new_api instead".to_string()];
+
+        expr_warning_wrap(&mut expr, &mut warnings);
+
+        // On stable the message is shown by a deprecation warning, value stays the same
+        assert_eq!(
+            quote! { #expr }.to_string(),
+            quote! {
+                {
+                    #[deprecated(note = "old_api is deprecated, use new_api instead")]
+                    const fn __expr_warning_0() {}
+                    __expr_warning_0();
+                    old_api()
+                }
+            }
+            .to_string()
+        );
+    }
+
     #[docify::export_content]
     #[test]
     fn readme_error_wrapping_example() {
@@ -553,6 +1357,70 @@ mod full_examples {
         assert_eq!(result, "fnhello()->String{\"hello world\".to_string()}");
     }
 
+    #[docify::export_content]
+    #[test]
+    fn token_stream_consistent_string_sorted_attrs_example() {
+        let first = quote! {
+            #[derive(Debug)]
+            #[serde(rename_all = "camelCase")]
+            struct User { name: String }
+        };
+        let second = quote! {
+            #[serde(rename_all = "camelCase")]
+            #[derive(Debug)]
+            struct User { name: String }
+        };
+
+        assert_ne!(
+            token_stream_to_consistent_string(first.clone()),
+            token_stream_to_consistent_string(second.clone())
+        );
+        assert_eq!(
+            token_stream_to_consistent_string_sorted_attrs(first),
+            token_stream_to_consistent_string_sorted_attrs(second)
+        );
+    }
+
+    #[docify::export_content]
+    #[test]
+    fn token_stream_consistent_string_canonical_numbers_example() {
+        let user_written = quote! { #[limit(max = 1_000_u32)] };
+        let expected = quote! { #[limit(max = 1000u32)] };
+
+        assert_ne!(
+            token_stream_to_consistent_string(user_written.clone()),
+            token_stream_to_consistent_string(expected.clone())
+        );
+        assert_eq!(
+            token_stream_to_consistent_string_canonical_numbers(user_written),
+            "#[limit(max=1000u32)]"
+        );
+        assert_eq!(
+            token_stream_to_consistent_string_canonical_numbers(expected),
+            "#[limit(max=1000u32)]"
+        );
+    }
+
+    #[docify::export_content]
+    #[test]
+    fn consistent_string_diff_example() {
+        let generated = quote! { fn add(a: i32, b: i32) -> i32 { a - b } };
+        let expected = quote! { fn add(a: i32, b: i32) -> i32 { a + b } };
+
+        assert_eq!(
+            consistent_string_diff(&generated, &expected).unwrap(),
+            "token streams differ at token 15\r\n\
+             left:  fn add ( a : i32 , b : i32 ) -> i32 { a ⟦-⟧ b }\r\n\
+             right: fn add ( a : i32 , b : i32 ) -> i32 { a ⟦+⟧ b }"
+        );
+
+        // Spacing doesn't matter, same as in `token_stream_to_consistent_string`
+        let spaced: proc_macro2::TokenStream = "fn add ( a : i32 , b : i32 ) -> i32 { a + b }"
+            .parse()
+            .unwrap();
+        assert_eq!(consistent_string_diff(&spaced, &expected), None);
+    }
+
     #[docify::export_content]
     #[test]
     fn readable_token_stream_example() {
@@ -564,4 +1432,39 @@ mod full_examples {
         let clean = readable_token_stream(input);
         assert_eq!(clean, "a b c");
     }
+
+    #[docify::export_content]
+    #[test]
+    fn assert_tokens_eq_example() {
+        let tokens = quote! { let value: Option<String> = None; };
+
+        // Same as `assert_eq!(readable_token_stream(&tokens.to_string()), "...")`
+        assert_tokens_eq!(tokens, "let value: Option<String> = None;");
+        assert_tokens_eq!(
+            tokens,
+            "let value: Option<String> = None;",
+            "in {}",
+            "example"
+        );
+    }
+
+    #[docify::export_content]
+    #[test]
+    fn readable_token_stream_with_example() {
+        let options = ReadableOptions {
+            join_operators: true,
+            ..Default::default()
+        };
+
+        assert_eq!(readable_token_stream("x + = y"), "x + = y");
+        assert_eq!(readable_token_stream_with("x + = y", options), "x += y");
+
+        // Number literal next to `< <` - shift
+        assert_eq!(readable_token_stream_with("x < < 1", options), "x << 1");
+        // Otherwise - generics
+        assert_eq!(
+            readable_token_stream_with("Vec < Vec < T > >", options),
+            "Vec<Vec<T>>"
+        );
+    }
 }
@@ -1,3 +1,4 @@
+use proc_macro2::TokenStream;
 use syn::{Block, Expr, ExprBlock, spanned::Spanned};
 
 /// Collect and provide error information for [`expr_error_wrap`].
@@ -34,6 +35,30 @@ pub trait CompileErrorProvider {
     ///
     /// A vector of error messages that were accumulated
     fn error_data(&mut self) -> Vec<String>;
+
+    /// Returns `value` if there are no errors, otherwise a `compile_error!` for every collected error.
+    ///
+    /// Useful as the last step of a macro which accumulates errors instead of stopping at the first one,
+    /// the result can be returned from the macro directly. Errors are emitted as an expression
+    /// (`compile_error!(...)`, a block of them for multiple errors), so it also works in expression position.
+    ///
+    /// # Examples
+    ///
+    #[doc = docify::embed!("src/examples.rs", error_data_finish_or_errors_example)]
+    fn finish_or_errors(mut self, value: TokenStream) -> TokenStream
+    where
+        Self: Sized,
+    {
+        if self.no_errors() {
+            return value;
+        }
+        match self.error_data().as_slice() {
+            [error] => quote::quote! { compile_error!(#error) },
+            errors => quote::quote! {
+                { #(compile_error!(#errors));* }
+            },
+        }
+    }
 }
 
 impl CompileErrorProvider for Vec<String> {
@@ -111,3 +136,67 @@ pub fn expr_error_wrap(expr: &mut Expr, error_info: &mut impl CompileErrorProvid
         });
     }
 }
+
+/// Non-fatal version of [`expr_error_wrap`], reports collected messages as warnings.
+///
+/// The expression keeps its value, so compilation proceeds. Useful for deprecations.
+///
+/// # Behavior
+///
+/// If there are no messages in `warning_info`, the expression is left unchanged.
+/// Otherwise:
+/// - With the `nightly-diagnostics` feature (requires nightly compiler), every message is emitted
+///   as a `proc_macro::Diagnostic` warning pointing at the expression, the expression itself is left unchanged.
+///   Works only inside of a running procedural macro.
+/// - On stable, the expression is wrapped in a block containing a `#[deprecated]` function for every message,
+///   which is then called, so the compiler shows the message in a deprecation warning.
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", expr_warning_wrap_basic_usage)]
+pub fn expr_warning_wrap(expr: &mut Expr, warning_info: &mut impl CompileErrorProvider) {
+    if warning_info.no_errors() {
+        return;
+    }
+    let warnings = warning_info.error_data();
+
+    let span = expr.span();
+
+    #[cfg(feature = "nightly-diagnostics")]
+    {
+        for warning in warnings {
+            proc_macro::Diagnostic::spanned(span.unwrap(), proc_macro::Level::Warning, warning)
+                .emit();
+        }
+    }
+
+    #[cfg(not(feature = "nightly-diagnostics"))]
+    {
+        let mut warning_calls = Vec::new();
+        for (index, warning) in warnings.iter().enumerate() {
+            let fn_name = syn::Ident::new(&format!("__expr_warning_{index}"), span);
+            let deprecated_fn: syn::Stmt = syn::parse_quote_spanned! {span=>
+                #[deprecated(note = #warning)]
+                const fn #fn_name() {}
+            };
+            let call: syn::Stmt = syn::parse_quote_spanned! {span=>
+                #fn_name();
+            };
+            warning_calls.push(deprecated_fn);
+            warning_calls.push(call);
+        }
+
+        replace_with::replace_with_or_abort(expr, |ex| {
+            warning_calls.push(syn::Stmt::Expr(ex, None));
+
+            Expr::Block(ExprBlock {
+                attrs: vec![],
+                label: None,
+                block: Block {
+                    brace_token: Default::default(),
+                    stmts: warning_calls,
+                },
+            })
+        });
+    }
+}
@@ -1,6 +1,7 @@
 use proc_macro_crate::FoundCrate;
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
+use toml_edit::{DocumentMut, Item};
 
 /// Locates a crate in the current Cargo.toml and generates the appropriate path reference.
 ///
@@ -112,3 +113,219 @@ pub fn find_crate_list(list: &[(&str, TokenStream)]) -> Option<TokenStream> {
     }
     None
 }
+
+/// Locates an item (or module) reachable through re-exports of one of the root crates.
+///
+/// Formalizes the fallback pattern of [`find_crate_list`], where every candidate carries
+/// the path under which it re-exports the same thing (`easy-macros` re-exports helpers at its root,
+/// `easy-macros-attributes` as `::helpers`). The first root present in Cargo.toml is used,
+/// with its path and `::leaf` appended. Like [`find_crate`], it properly handles crate renaming.
+///
+/// # Arguments
+///
+/// * `root_candidates` - `(original_crate_name, path_to_reexport)` tuples, checked in order.
+///   Add the crate defining `leaf` itself with an empty path to support depending on it directly
+/// * `leaf` - Path appended after the root path, for example `context` or `token::Comma`.
+///   Empty to get the re-exported crate (or module) itself
+///
+/// # Returns
+///
+/// * `Some(TokenStream)` - `root::path_to_reexport::leaf` for the first root found
+/// * `None` - If none of the roots are found in Cargo.toml, or `leaf` is not a relative path (`token::Comma`, not `::Comma`)
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", find_reexport_example)]
+pub fn find_reexport(root_candidates: &[(&str, TokenStream)], leaf: &str) -> Option<TokenStream> {
+    let leaf = if leaf.is_empty() {
+        None
+    } else {
+        let leaf: syn::Path = syn::parse_str(leaf).ok()?;
+        if leaf.leading_colon.is_some() {
+            return None;
+        }
+        Some(leaf)
+    };
+    for (name, path_to_reexport) in root_candidates {
+        let after_name = match &leaf {
+            Some(leaf) => quote! {#path_to_reexport::#leaf},
+            None => path_to_reexport.clone(),
+        };
+        if let Some(result) = find_crate(name, after_name) {
+            return Some(result);
+        }
+    }
+    None
+}
+
+/// Result of [`find_crate_resolution`], tells apart crates which are missing from optional dependencies.
+#[derive(Debug, Clone)]
+pub enum Resolution {
+    /// Regular dependency (or the current crate), path to it with the suffix appended
+    Found(TokenStream),
+    /// Dependency marked with `optional = true`, only usable when one of `features` is enabled
+    ///
+    /// Procedural macros can't see which features are active, so the crate might be available
+    /// or not. Use [`Resolution::feature_check`] to report a helpful error in the second case.
+    Optional {
+        /// Path to the crate with the suffix appended
+        path: TokenStream,
+        /// Features of the current crate enabling this dependency (directly or through other features)
+        features: Vec<String>,
+    },
+    /// Crate is not present in Cargo.toml
+    Absent,
+}
+
+impl Resolution {
+    /// Path to the crate, `None` if it's [`Resolution::Absent`]
+    pub fn path(&self) -> Option<&TokenStream> {
+        match self {
+            Resolution::Found(path) | Resolution::Optional { path, .. } => Some(path),
+            Resolution::Absent => None,
+        }
+    }
+
+    /// `compile_error!` which triggers only when none of the features enabling an optional dependency are active
+    ///
+    /// Features are appended to `message`. Returns nothing for [`Resolution::Found`] and [`Resolution::Absent`].
+    pub fn feature_check(&self, message: &str) -> TokenStream {
+        let Resolution::Optional { features, .. } = self else {
+            return TokenStream::new();
+        };
+        let feature_list = features
+            .iter()
+            .map(|feature| format!("`{feature}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let message = format!("{message} Enable it with one of these features: {feature_list}");
+        quote! {
+            #[cfg(not(any(#(feature = #features),*)))]
+            compile_error!(#message);
+        }
+    }
+}
+
+/// Like [`find_crate`], but also checks whether the crate is an optional dependency.
+///
+/// `proc_macro_crate` finds optional dependencies even when the feature enabling them is inactive,
+/// so the generated code fails with an unresolved path. This function reads `optional = true`
+/// from the current Cargo.toml and lists every feature which enables the dependency
+/// (`dep:name`, `name/feature`, the implicit `name` feature and features depending on those).
+///
+/// # Returns
+///
+/// * [`Resolution::Found`] - Regular dependency or the current crate
+/// * [`Resolution::Optional`] - Optional dependency, with features enabling it
+/// * [`Resolution::Absent`] - Crate is not found in the current Cargo.toml
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", find_crate_resolution_example)]
+pub fn find_crate_resolution(crate_name: &str, after_name: TokenStream) -> Resolution {
+    let Some(path) = find_crate(crate_name, after_name) else {
+        return Resolution::Absent;
+    };
+
+    //Manifest read already succeeded inside of `proc_macro_crate`
+    let manifest = std::env::var("CARGO_MANIFEST_DIR").ok().and_then(|dir| {
+        std::fs::read_to_string(std::path::Path::new(&dir).join("Cargo.toml")).ok()
+    });
+
+    match manifest.and_then(|manifest| optional_dependency_features(&manifest, crate_name)) {
+        Some(features) => Resolution::Optional { path, features },
+        None => Resolution::Found(path),
+    }
+}
+
+///Features enabling `crate_name` dependency, `None` if it's not an optional dependency in `manifest`
+pub(crate) fn optional_dependency_features(
+    manifest: &str,
+    crate_name: &str,
+) -> Option<Vec<String>> {
+    let manifest = manifest.parse::<DocumentMut>().ok()?;
+
+    //Only `dependencies` (also target specific ones), dev-dependencies can't be optional
+    let target_tables = manifest
+        .get("target")
+        .and_then(Item::as_table_like)
+        .into_iter()
+        .flat_map(|targets| targets.iter())
+        .filter_map(|(_, target)| target.as_table_like()?.get("dependencies"));
+    let dep_tables = manifest
+        .get("dependencies")
+        .into_iter()
+        .chain(target_tables)
+        .filter_map(Item::as_table_like);
+
+    let mut dep_key = None;
+    for table in dep_tables {
+        for (key, value) in table.iter() {
+            let package = value.get("package").and_then(Item::as_str).unwrap_or(key);
+            if package != crate_name {
+                continue;
+            }
+            let optional = value
+                .get("optional")
+                .and_then(Item::as_bool)
+                .unwrap_or(false);
+            if !optional {
+                //Always available
+                return None;
+            }
+            dep_key = Some(key.to_string());
+        }
+    }
+    let dep_key = dep_key?;
+
+    let feature_table = manifest.get("features").and_then(Item::as_table_like);
+    let feature_values = |feature: &Item| -> Vec<String> {
+        feature
+            .as_array()
+            .into_iter()
+            .flat_map(|values| values.iter())
+            .filter_map(|value| value.as_str().map(str::to_string))
+            .collect()
+    };
+
+    let dep_syntax = format!("dep:{dep_key}");
+    let uses_dep_syntax = feature_table.is_some_and(|table| {
+        table
+            .iter()
+            .any(|(_, values)| feature_values(values).contains(&dep_syntax))
+    });
+
+    //Without any `dep:name` there is an implicit feature with the dependency name
+    let mut features = Vec::new();
+    if !uses_dep_syntax {
+        features.push(dep_key.clone());
+    }
+
+    let Some(feature_table) = feature_table else {
+        return Some(features);
+    };
+    let sub_feature_prefix = format!("{dep_key}/");
+    //Features enabling other features, until nothing new is found
+    loop {
+        let mut changed = false;
+        for (feature, values) in feature_table.iter() {
+            if features.iter().any(|f| f == feature) {
+                continue;
+            }
+            let enables = feature_values(values).iter().any(|value| {
+                *value == dep_syntax
+                    || value.starts_with(&sub_feature_prefix)
+                    || features.contains(value)
+            });
+            if enables {
+                features.push(feature.to_string());
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    Some(features)
+}
@@ -28,3 +28,116 @@ pub fn indexed_name(name: syn::Ident, count: usize) -> Vec<syn::Ident> {
     }
     names
 }
+
+/// Generates one vector of indexed identifiers per base name, all of them with the same length.
+///
+/// Same as calling [`indexed_name`] for every base with the same `count`, useful when patterns
+/// and bindings need aligned identifier sets (`a0` with `b0`, `a1` with `b1`, ...) zipped in `quote!`.
+///
+/// # Arguments
+///
+/// * `bases` - Base names, one output vector is generated for each of them (in the same order)
+/// * `count` - The number of indexed identifiers in every vector (0 to count-1)
+///
+/// # Returns
+///
+/// `[[base0_0, base0_1, ...], [base1_0, base1_1, ...], ...]`, where `baseN_i` is the `N`th base with `i` appended
+///
+/// # Panics
+///
+/// If any of the bases is not a valid identifier.
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", indexed_name_groups_example)]
+pub fn indexed_name_groups(bases: &[&str], count: usize) -> Vec<Vec<syn::Ident>> {
+    bases
+        .iter()
+        .map(|base| indexed_name(quote::format_ident!("{}", base), count))
+        .collect()
+}
+
+/// Generates a vector of tuple field indices (`0`, `1`, `2`, ...).
+///
+/// Complements [`indexed_name`] for tuple structs, where fields are accessed by index
+/// (`self.0`, `self.1`) instead of by name.
+///
+/// # Arguments
+///
+/// * `count` - The number of indices to generate (0 to count-1)
+///
+/// # Returns
+///
+/// A vector of `syn::Index`, usable in `quote!` as `#(self.#indices),*`
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", indexed_tuple_access_example)]
+pub fn indexed_tuple_access(count: usize) -> Vec<syn::Index> {
+    (0..count).map(syn::Index::from).collect()
+}
+
+/// Generates a vector of lifetimes by appending numeric indices to a base name.
+///
+/// Complements [`indexed_name`] for generic lifetime parameters, e.g. `impl<'a0, 'a1> ...`.
+///
+/// # Arguments
+///
+/// * `base` - The base lifetime name, with or without the leading `'` (`"a"` and `"'a"` are the same)
+/// * `count` - The number of indexed lifetimes to generate (0 to count-1)
+///
+/// # Returns
+///
+/// A vector of `syn::Lifetime` with numeric suffixes: `['base0, 'base1, 'base2, ...]`
+///
+/// # Panics
+///
+/// If `base` is not a valid identifier (for example empty or starting with a digit).
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", indexed_lifetime_example)]
+pub fn indexed_lifetime(base: &str, count: usize) -> Vec<syn::Lifetime> {
+    let base = base.strip_prefix('\'').unwrap_or(base);
+    (0..count)
+        .map(|i| syn::Lifetime::new(&format!("'{base}{i}"), proc_macro2::Span::call_site()))
+        .collect()
+}
+
+///Incremented on every [`unique_ident`] call
+static UNIQUE_IDENT_COUNTER: std::sync::atomic::AtomicUsize =
+    std::sync::atomic::AtomicUsize::new(0);
+
+/// Generates an identifier that doesn't repeat across calls, in format `__{base}_{counter}`.
+///
+/// Useful for hidden helper items (structs, functions, statics) generated by a macro
+/// that can be used multiple times in the same module, e.g. `__helper_0`, `__helper_1`.
+///
+/// # Arguments
+///
+/// * `base` - Base name placed between `__` and the counter
+///
+/// # Hygiene
+///
+/// - The counter is shared by the whole process (every macro using this function), it is not reset
+///   between macro invocations, so generated names are not stable. Don't rely on them in expected output
+///   and don't reference them from code written by hand.
+///   Use [`indexed_name`] when names need to be deterministic.
+/// - Compiler may run macros of different crates in the same process or in separate ones,
+///   identifiers are unique only within a single compilation, mark generated items as private
+///   (or `#[doc(hidden)]`) when they can be seen from other crates.
+/// - The identifier uses [`Span::call_site`](proc_macro2::Span::call_site), so it resolves
+///   like any identifier written by the macro user, only the `__` prefix protects it from collisions
+///   with user code.
+///
+/// # Panics
+///
+/// If `__{base}_0` is not a valid identifier.
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", unique_ident_example)]
+pub fn unique_ident(base: &str) -> syn::Ident {
+    let index = UNIQUE_IDENT_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    quote::format_ident!("__{}_{}", base, index)
+}
@@ -3,25 +3,54 @@
 //! ### General Use (not only for macros)
 //!
 //! - [`context!`] - Generates context strings for error handling with automatic file/line information
+//! - [`bail_ctx!`] - Returns early with an `anyhow::Error` containing file/line information
+//! - [`ensure_ctx!`] - Returns early with an `anyhow::Error` containing file/line information if the condition is false
+//! - [`context_err!`] - Closure creating an `anyhow::Error` with file/line information, for `Option::ok_or_else`
+//! - [`wrap_ctx!`] - Wraps an existing error into an `anyhow::Error` with file/line information added as context
+//! - [`context_code!`] with [`extract_code`] - Context strings starting with a machine readable error code
+//! - [`context_str!`] - Same as `context!(...)()`, the context `String` without a closure
+//! - [`with_fn_name`] - Attribute making [`context!`] calls inside of a function include its name
+//! - [`context_string`] - Runtime version of [`context!`], for messages that aren't format string literals
+//! - [`dedup_context_chain`] - Renders an error chain, consecutive [`context!`] entries from the same file share one `file:line, line` header
+//! - [`set_context_prefix`] - Thread-local prefix (like a request ID) added to every [`context!`] string
+//! - [`set_context_formatter`] - Thread-local custom layout of [`context!`] strings (file, line, message and `@...` options)
+//! - `context-json` feature - `context!(@json, "message", key = value)` creates a JSON object for structured loggers
+//! - `strip-location-in-release` feature - [`context!`] keeps only the message in release builds, `file:line` is omitted
+//! - `std-only` feature - `context_map_err!` (closure for `map_err`), `bail_ctx_std!`, `ensure_ctx_std!`, `wrap_ctx_std!` and `context_err_std!`, versions of the context macros without `anyhow`
 //!
 //! ### Token Stream Management
 //!
 //! - [`TokensBuilder`] - Accumulate and combine token streams with methods inside
+//! - [`split_items`] - Split a token stream into top-level items (trailing non-item tokens as the last chunk)
 //! - [`readable_token_stream`] - Format token strings for better readability
+//! - [`readable_token_stream_with`] with [`ReadableOptions`] - Same as above, with optional operator joining
+//! - [`assert_tokens_eq!`] - Test assertion comparing [`readable_token_stream`] output, shows where the strings differ
 //! - [`token_stream_to_consistent_string`] - Normalize token representation across contexts
+//! - [`token_stream_to_consistent_string_sorted_attrs`] - Same as above, ignoring order of leading attributes
+//! - [`token_stream_to_consistent_string_canonical_numbers`] - Same as above, ignoring `_` in numeric literals (`1_000u32` == `1000u32`)
+//! - [`consistent_string_diff`] - Readable diff of two token streams, `None` if they are equal
 //!
 //! ### Error Handling
 //!
 //! - [`parse_macro_input!`] - Enhanced version of syn's macro that returns `Ok(TokenStream)` on parse errors (instead of `TokenStream`)
 //! - [`expr_error_wrap`] with [`CompileErrorProvider`] trait - Wrap expressions with compile-time error reporting
+//! - [`expr_warning_wrap`] - Same as above, but reports warnings and lets compilation proceed
 //!
 //! ### Code Generation Utilities
 //!
 //! - [`indexed_name`] - Generate indexed identifiers (`field0`, `field1`, etc.)
+//! - [`indexed_name_groups`] - Same as above for multiple base names at once, all vectors aligned (`a0`/`b0`, `a1`/`b1`, etc.)
+//! - [`indexed_tuple_access`] - Generate tuple field indices (`0`, `1`, etc.)
+//! - [`indexed_lifetime`] - Generate indexed lifetimes (`'a0`, `'a1`, etc.)
+//! - [`unique_ident`] - Generate identifiers that don't repeat across macro invocations (`__helper_0`, `__helper_1`, etc.)
 //! - [`find_crate`] - Locate crate references for generated code (supports renaming)
 //! - [`find_crate_list`] - Try multiple crates, return first found
+//! - [`find_crate_resolution`] - Like [`find_crate`], but tells apart optional dependencies
+//! - [`find_reexport`] - Path to an item re-exported by the first root crate found
 //!
 
+#![cfg_attr(feature = "nightly-diagnostics", feature(proc_macro_diagnostic))]
+
 #[cfg(feature = "context")]
 mod context;
 #[cfg(feature = "context")]
@@ -33,27 +62,35 @@ mod tests;
 #[cfg(feature = "indexed-name")]
 mod indexed_name;
 #[cfg(feature = "indexed-name")]
-pub use indexed_name::indexed_name;
+pub use indexed_name::{
+    indexed_lifetime, indexed_name, indexed_name_groups, indexed_tuple_access, unique_ident,
+};
 
+#[cfg(any(feature = "tokens-builder", feature = "nightly-diagnostics"))]
+extern crate proc_macro;
 #[cfg(feature = "tokens-builder")]
 mod tokens_builder;
 #[cfg(feature = "tokens-builder")]
-pub use tokens_builder::TokensBuilder;
+pub use tokens_builder::{Checkpoint, TokensBuilder, split_items};
 
 #[cfg(feature = "expr-error-wrap")]
 mod expr_error_wrap;
 #[cfg(feature = "expr-error-wrap")]
-pub use expr_error_wrap::{CompileErrorProvider, expr_error_wrap};
+pub use expr_error_wrap::{CompileErrorProvider, expr_error_wrap, expr_warning_wrap};
 
 #[cfg(feature = "readable-token-stream")]
 mod readable_token_stream;
 #[cfg(feature = "readable-token-stream")]
-pub use readable_token_stream::readable_token_stream;
+pub use readable_token_stream::{
+    ReadableOptions, assert_readable_eq, readable_token_stream, readable_token_stream_with,
+};
 
 #[cfg(feature = "find-crate")]
 mod find_crate;
 #[cfg(feature = "find-crate")]
-pub use find_crate::{find_crate, find_crate_list};
+pub use find_crate::{
+    Resolution, find_crate, find_crate_list, find_crate_resolution, find_reexport,
+};
 
 #[cfg(feature = "token-stream-consistent")]
 mod token_stream_to_consistent_string;
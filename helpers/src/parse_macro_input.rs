@@ -22,8 +22,32 @@
 ///
 /// // Parse with type inference
 /// let input = parse_macro_input!(tokens);
+///
+/// // Parse only a prefix, remaining tokens are returned unparsed (`proc_macro2::TokenStream`)
+/// let (name, rest) = parse_macro_input!(tokens as syn::Ident, rest);
+///
+/// // Parse a brace-less list into a `Vec`
+/// let items: Vec<syn::Item> = parse_macro_input!(tokens as [syn::Item]);
+/// // Statements, last one can be an expression without `;`
+/// let stmts: Vec<syn::Stmt> = parse_macro_input!(tokens as [syn::Stmt]);
+///
+/// // Keep parsing after an invalid item, report errors of all invalid items at once
+/// let items: Vec<syn::Item> = parse_macro_input!(tokens as [syn::Item]; combine_errors);
 /// ```
 ///
+/// # Combining Errors
+///
+/// With `; combine_errors` an item which fails to parse is skipped (up to and including the next `;` or `{ ... }`)
+/// and parsing continues with the next one. All errors are combined with `syn::Error::combine`,
+/// so every invalid item gets its own `compile_error!`. Only available for the `[$ty]` form,
+/// a single value can't be parsed further after its first error.
+///
+/// # Remaining Tokens
+///
+/// With `, rest` only a `$ty` is parsed from the start of the input, the macro evaluates to `($ty, proc_macro2::TokenStream)`
+/// with everything after it (empty if nothing is left). Useful for macros with a header followed by
+/// tokens passed through verbatim. Errors while parsing `$ty` are returned early, the same way as without `, rest`.
+///
 /// # Examples
 ///
 /// ```ignore
@@ -52,9 +76,100 @@
 /// # Parameters
 ///
 /// - `$tokenstream` - The input `TokenStream` to parse
-/// - `$ty` - The target type to parse into (with `as` syntax)
+/// - `$ty` - The target type to parse into (with `as` syntax), `[$ty]` parses until the end of input into `Vec<$ty>`
+/// - `combine_errors` - Report errors of all invalid items of `[$ty]` instead of only the first one
+/// - `rest` - Parse a `$ty` prefix and return it together with the remaining tokens
 /// - `$parser` - A custom parser function (with `with` syntax)
 macro_rules! parse_macro_input {
+    ($tokenstream:ident as [$ty:ty]; combine_errors) => {
+        match syn::parse::Parser::parse(
+            |input: syn::parse::ParseStream| {
+                use syn::parse::discouraged::Speculative;
+
+                let mut items = Vec::<$ty>::new();
+                let mut errors = syn::__private::None::<syn::Error>;
+                while !input.is_empty() {
+                    let fork = input.fork();
+                    match fork.parse::<$ty>() {
+                        syn::__private::Ok(item) => {
+                            input.advance_to(&fork);
+                            items.push(item);
+                        }
+                        syn::__private::Err(err) => {
+                            match &mut errors {
+                                syn::__private::Some(errors) => errors.combine(err),
+                                syn::__private::None => errors = syn::__private::Some(err),
+                            }
+                            //Skip the invalid item, it ends with `;` or `{ ... }` (optionally followed by `;`)
+                            while !input.is_empty() {
+                                let semi = input.peek(syn::Token![;]);
+                                let brace = input.peek(syn::token::Brace);
+                                input.step(|cursor| match cursor.token_tree() {
+                                    syn::__private::Some((_, rest)) => syn::__private::Ok(((), rest)),
+                                    syn::__private::None => syn::__private::Err(cursor.error("unexpected end of input")),
+                                })?;
+                                if brace && input.peek(syn::Token![;]) {
+                                    input.parse::<syn::Token![;]>()?;
+                                }
+                                if semi || brace {
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                }
+                match errors {
+                    syn::__private::Some(errors) => syn::__private::Err(errors),
+                    syn::__private::None => syn::__private::Ok(items),
+                }
+            },
+            $tokenstream,
+        ) {
+            syn::__private::Ok(data) => data,
+            syn::__private::Err(err) => {
+                return Ok(syn::__private::TokenStream::from(err.to_compile_error()));
+            }
+        }
+    };
+    //Statements are parsed like block contents, so the last one doesn't need `;`
+    ($tokenstream:ident as [Stmt]) => {
+        $crate::parse_macro_input!($tokenstream with syn::Block::parse_within)
+    };
+    ($tokenstream:ident as [syn::Stmt]) => {
+        $crate::parse_macro_input!($tokenstream with syn::Block::parse_within)
+    };
+    ($tokenstream:ident as [$ty:ty]) => {
+        match syn::parse::Parser::parse(
+            |input: syn::parse::ParseStream| {
+                let mut items = Vec::<$ty>::new();
+                while !input.is_empty() {
+                    items.push(input.parse()?);
+                }
+                syn::__private::Ok(items)
+            },
+            $tokenstream,
+        ) {
+            syn::__private::Ok(data) => data,
+            syn::__private::Err(err) => {
+                return Ok(syn::__private::TokenStream::from(err.to_compile_error()));
+            }
+        }
+    };
+    ($tokenstream:ident as $ty:ty, rest) => {
+        match syn::parse::Parser::parse(
+            |input: syn::parse::ParseStream| {
+                let parsed: $ty = input.parse()?;
+                let rest: syn::__private::TokenStream2 = input.parse()?;
+                syn::__private::Ok((parsed, rest))
+            },
+            $tokenstream,
+        ) {
+            syn::__private::Ok(data) => data,
+            syn::__private::Err(err) => {
+                return Ok(syn::__private::TokenStream::from(err.to_compile_error()));
+            }
+        }
+    };
     ($tokenstream:ident as $ty:ty) => {
         match syn::parse::<$ty>($tokenstream) {
             syn::__private::Ok(data) => data,
@@ -20,6 +20,9 @@
 /// - Spaces before closing delimiters and punctuation: `.`, `,`, `(`, `[`, `:`, `;`, `!`, `<`, `>`, `?`
 /// - Spaces between consecutive closing delimiters: `))`, `}}`, `]]`
 ///
+/// Operators split into separate tokens (`x + = y`) are left as they are,
+/// use [`readable_token_stream_with`] with [`ReadableOptions::join_operators`] to join them.
+///
 /// # Examples
 ///
 #[doc = docify::embed!("src/examples.rs", readable_token_stream_example)]
@@ -35,25 +38,120 @@
 /// not actual token content. If this assertion fails, it indicates a bug in the
 /// whitespace removal logic.
 pub fn readable_token_stream(tokens_str: &str) -> String {
-    let mut result = String::new();
+    readable_token_stream_with(tokens_str, ReadableOptions::default())
+}
 
-    let mut char_iter_future = tokens_str.chars();
-    char_iter_future.next();
+/// Asserts that `actual` formatted with [`readable_token_stream`] is equal to `expected`.
+///
+/// `actual` can be anything implementing `Display` (`TokenStream`, `syn` types through `to_token_stream()`, `&str`),
+/// `expected` is compared as it is. On failure the panic message shows both strings aligned,
+/// with a marker under the first differing character. Optional format arguments are added to the message,
+/// same as in `assert_eq!`.
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", assert_tokens_eq_example)]
+#[macro_export]
+macro_rules! assert_tokens_eq {
+    ($actual:expr, $expected:expr $(,)?) => {
+        $crate::assert_readable_eq(&$actual.to_string(), $expected, None)
+    };
+    ($actual:expr, $expected:expr, $($arg:tt)+) => {
+        $crate::assert_readable_eq(&$actual.to_string(), $expected, Some(format!($($arg)+)))
+    };
+}
 
-    let char_iter_current = tokens_str.chars();
+///Used by [`assert_tokens_eq!`], panics (at the caller) with a message pointing at the first difference
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_readable_eq(actual: &str, expected: &str, message: Option<String>) {
+    let actual = readable_token_stream(actual);
+    if actual == expected {
+        return;
+    }
 
-    let char_iter_future = char_iter_future.map(Some).chain(std::iter::once(None));
+    let first_difference = actual
+        .chars()
+        .zip(expected.chars())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let message = message.map(|m| format!(": {m}")).unwrap_or_default();
+    panic!(
+        "assertion `readable_token_stream(actual) == expected` failed{message}\n  actual: {actual}\nexpected: {expected}\n          {}^ first difference at character {first_difference}",
+        " ".repeat(first_difference)
+    );
+}
 
-    let iters_zipped = char_iter_current.zip(char_iter_future);
+/// Options for [`readable_token_stream_with`].
+///
+/// `ReadableOptions::default()` gives the same output as [`readable_token_stream`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReadableOptions {
+    /// Join operators split into separate tokens.
+    ///
+    /// - Compound assignment and comparison: `x + = y` → `x += y`, `a = = b` → `a == b`
+    ///   (also `-=`, `*=`, `/=`, `%=`, `^=`, `|=`; `&=`, `!=` and `<=` are joined even without this option)
+    /// - Shifts: `x < < 1` → `x << 1`, `1 > > x` → `1 >> x`
+    ///
+    /// `<<` and `>>` can't be told apart from nested generics (`Vec<Vec<T>>`) without
+    /// parsing, so they are treated as shifts only when a number literal is right next to them
+    /// (`x < < 1`, `1 > > x`). Everything else (`< < x > >`) is formatted as generics (`<<x>>`).
+    /// For the same reason `>=` is never joined (`Vec < T > = x` stays `Vec<T> = x`).
+    pub join_operators: bool,
+
+    /// Never leave whitespace at the end of the output or right before `)` and `]`.
+    ///
+    /// Without this option a space before a closing delimiter is sometimes kept
+    /// (`( hello )` → `(hello )`, `< > ` → `<> `). With it: `(hello)` and `<>`.
+    /// Braces keep their inner spacing (`{ a }`).
+    pub trim_trailing: bool,
+}
+
+/// Same as [`readable_token_stream`], with additional formatting controlled by `options`.
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", readable_token_stream_with_example)]
+pub fn readable_token_stream_with(tokens_str: &str, options: ReadableOptions) -> String {
+    let mut result = String::new();
+
+    let chars = tokens_str.chars().collect::<Vec<_>>();
 
     let mut last_char = ' ';
 
-    for (c, future_c) in iters_zipped {
+    let mut index = 0;
+    while index < chars.len() {
+        let c = chars[index];
+        let future_c = chars.get(index + 1).copied();
+        index += 1;
+
         match c {
             ' ' => {
                 if last_char == ' ' {
                     continue;
                 }
+                if options.trim_trailing
+                    && matches!(
+                        chars[index..].iter().find(|c| **c != ' '),
+                        None | Some(')' | ']')
+                    )
+                {
+                    continue;
+                }
+                if options.join_operators {
+                    if let Some((shift, len)) = shift_operator_at(&chars, index, &result) {
+                        //Skip both operator chars (and the space between them)
+                        index += len;
+                        result.push(' ');
+                        result.push_str(shift);
+                        result.push(' ');
+                        last_char = ' ';
+                        continue;
+                    }
+                    if compound_assignment_at(&chars, index, last_char) {
+                        continue;
+                    }
+                }
                 match (last_char, future_c) {
                     ('>', Some('>' | '(' | '{' | '[' | ',' | ']' | ':' | ';')) => {
                         continue;
@@ -92,3 +190,46 @@ pub fn readable_token_stream(tokens_str: &str) -> String {
 
     result
 }
+
+///`index` - position right after a space
+///
+///Returns shift operator and its length if `< <` or `> >` starts at `index` and a number literal is right next to it
+///(`before` - output so far, its last token is checked)
+///
+///Already joined `<<` and `>>` (followed by a space) are also matched, so the output stays the same on a second pass
+fn shift_operator_at(chars: &[char], index: usize, before: &str) -> Option<(&'static str, usize)> {
+    let (shift, len) = match chars.get(index..index + 3)? {
+        ['<', ' ', '<'] => ("<<", 3),
+        ['>', ' ', '>'] => (">>", 3),
+        ['<', '<', ' '] => ("<<", 2),
+        ['>', '>', ' '] => (">>", 2),
+        _ => return None,
+    };
+    let number_after = matches!(
+        chars.get(index + len..index + len + 2),
+        Some([' ', next]) if next.is_ascii_digit()
+    );
+    if ends_with_number(before) || number_after {
+        Some((shift, len))
+    } else {
+        None
+    }
+}
+
+///`index` - position right after a space
+///
+///Last token of `before` is a number literal (starts with a digit), identifiers like `u8` or `T1` are not
+fn ends_with_number(before: &str) -> bool {
+    let token_start = before
+        .trim_end_matches(|c: char| c.is_alphanumeric() || c == '_')
+        .len();
+    before[token_start..].starts_with(|c: char| c.is_ascii_digit())
+}
+
+///Space between an operator and a standalone `=` (`x + = y`)
+fn compound_assignment_at(chars: &[char], index: usize, last_char: char) -> bool {
+    //`>` is left out, `Vec < T > = x` is far more common than `a > = b`
+    matches!(last_char, '+' | '-' | '*' | '/' | '%' | '^' | '|' | '=')
+        && chars.get(index) == Some(&'=')
+        && matches!(chars.get(index + 1), None | Some(' '))
+}
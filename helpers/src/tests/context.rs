@@ -1,8 +1,12 @@
 //! Tests specifically for the context macro
 
-use crate::context;
+use crate::{context, context_code, context_string, dedup_context_chain, ensure_ctx, extract_code};
+//Used only by tests checking the `file:line` location
+#[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+use crate::{bail_ctx, context_err, context_str, with_fn_name, wrap_ctx};
 use anyhow::Context;
 
+#[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
 #[test]
 fn context_basic_usage() {
     // Basic usage with no arguments - just file:line
@@ -13,6 +17,7 @@ fn context_basic_usage() {
     assert_eq!(result, format!("src/tests/context.rs:{}", line!() - 4));
 }
 
+#[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
 #[test]
 fn context_with_message() {
     // Usage with a simple message
@@ -26,6 +31,7 @@ fn context_with_message() {
     );
 }
 
+#[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
 #[test]
 fn context_with_formatting() {
     // Usage with format arguments
@@ -45,6 +51,7 @@ fn context_with_formatting() {
     );
 }
 
+#[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
 #[test]
 fn context_with_anyhow() {
     use std::fs;
@@ -69,6 +76,7 @@ fn context_with_anyhow() {
     );
 }
 
+#[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
 #[test]
 fn context_multiple_format_args() {
     let file_path = "/path/to/file.txt";
@@ -92,3 +100,713 @@ fn context_multiple_format_args() {
         )
     );
 }
+
+#[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+#[test]
+fn bail_ctx_with_formatting() {
+    fn delete_user(user_id: u64) -> anyhow::Result<()> {
+        bail_ctx!("Failed to delete user {}", user_id);
+    }
+
+    let result = delete_user(42);
+
+    // Should match exact format: "src/tests/context.rs:line\r\nFailed to delete user 42"
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        format!(
+            "src/tests/context.rs:{}\r\nFailed to delete user 42",
+            line!() - 10
+        )
+    );
+}
+
+#[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+#[test]
+fn bail_ctx_no_message() {
+    fn fail() -> anyhow::Result<()> {
+        bail_ctx!();
+    }
+
+    assert_eq!(
+        fail().unwrap_err().to_string(),
+        format!("src/tests/context.rs:{}", line!() - 5)
+    );
+}
+
+#[test]
+fn ensure_ctx_passing() {
+    fn check(value: u64) -> anyhow::Result<u64> {
+        ensure_ctx!(value > 10, "Value {} is too small", value);
+        Ok(value)
+    }
+
+    assert_eq!(check(42).unwrap(), 42);
+}
+
+#[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+#[test]
+fn ensure_ctx_failing() {
+    fn check(value: u64) -> anyhow::Result<u64> {
+        ensure_ctx!(value > 10, "Value {} is too small", value);
+        Ok(value)
+    }
+
+    // Should match exact format: "src/tests/context.rs:line\r\nValue 5 is too small"
+    assert_eq!(
+        check(5).unwrap_err().to_string(),
+        format!(
+            "src/tests/context.rs:{}\r\nValue 5 is too small",
+            line!() - 9
+        )
+    );
+}
+
+#[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+#[test]
+fn ensure_ctx_no_message() {
+    fn check(value: bool) -> anyhow::Result<()> {
+        ensure_ctx!(value);
+        Ok(())
+    }
+
+    assert!(check(true).is_ok());
+    assert_eq!(
+        check(false).unwrap_err().to_string(),
+        format!("src/tests/context.rs:{}", line!() - 7)
+    );
+}
+
+#[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+#[test]
+fn wrap_ctx_io_error() {
+    let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "config.toml missing");
+    let line = line!() + 1;
+    let error = wrap_ctx!(io_error, "Loading config for {}", "server");
+
+    let debug = format!("{:?}", error);
+    assert!(debug.contains(&format!(
+        "src/tests/context.rs:{}\r\nLoading config for server",
+        line
+    )));
+    assert!(debug.contains("config.toml missing"));
+}
+
+#[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+#[test]
+fn wrap_ctx_anyhow_error_no_message() {
+    let original = anyhow::anyhow!("original failure");
+    let line = line!() + 1;
+    let error = wrap_ctx!(original);
+
+    assert_eq!(error.to_string(), format!("src/tests/context.rs:{}", line));
+    assert_eq!(error.root_cause().to_string(), "original failure");
+}
+
+#[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+#[test]
+fn context_code_no_message() {
+    let result = context_code!(404)();
+
+    assert_eq!(
+        result,
+        format!("[code=404] src/tests/context.rs:{}", line!() - 4)
+    );
+    assert_eq!(extract_code(&result), Some("404"));
+}
+
+#[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+#[test]
+fn context_code_with_formatting() {
+    let table = "users";
+    let code = "DB_MISSING";
+
+    let result = context_code!(code, "Table {} is missing {}", table, 2)();
+
+    assert_eq!(
+        result,
+        format!(
+            "[code=DB_MISSING] src/tests/context.rs:{}\r\nTable users is missing 2",
+            line!() - 6
+        )
+    );
+    assert_eq!(extract_code(&result), Some("DB_MISSING"));
+}
+
+#[test]
+fn context_code_round_trip_anyhow() {
+    fn failing() -> anyhow::Result<()> {
+        Err(anyhow::anyhow!("inner error")).with_context(context_code!("E42", "Outer"))
+    }
+
+    let error = failing().unwrap_err();
+    // Outermost context is the message of the error
+    assert_eq!(extract_code(&error.to_string()), Some("E42"));
+    // Inner errors have no code
+    assert_eq!(extract_code(&error.root_cause().to_string()), None);
+}
+
+#[test]
+fn extract_code_without_prefix() {
+    assert_eq!(extract_code("src/main.rs:42\r\nNo code here"), None);
+    assert_eq!(extract_code("[code=unterminated"), None);
+    assert_eq!(extract_code(" [code=E1] leading space"), None);
+    assert_eq!(extract_code("[code=] empty"), Some(""));
+}
+
+#[test]
+fn context_formats_only_on_error() {
+    use std::cell::Cell;
+    use std::fmt;
+
+    /// Counts how many times it was formatted
+    struct FormatCounter<'a>(&'a Cell<usize>);
+
+    impl fmt::Display for FormatCounter<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.set(self.0.get() + 1);
+            write!(f, "counter")
+        }
+    }
+
+    let formatted = Cell::new(0);
+    let counter = FormatCounter(&formatted);
+
+    let ok: anyhow::Result<u8> = Ok(1);
+    let ok = ok.with_context(context!("Rendered {}", counter));
+    assert_eq!(ok.unwrap(), 1);
+    // Ok path, message was never created
+    assert_eq!(formatted.get(), 0);
+
+    let err: anyhow::Result<u8> = Err(anyhow::anyhow!("failure"));
+    let err = err.with_context(context!("Rendered {}", counter));
+    assert!(err.unwrap_err().to_string().ends_with("Rendered counter"));
+    assert_eq!(formatted.get(), 1);
+}
+
+#[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+#[test]
+fn context_string_same_as_macro() {
+    let (from_macro, line) = (context!("Value {} is invalid", 42)(), line!());
+    let from_fn = context_string(file!(), line, format!("Value {} is invalid", 42));
+    assert_eq!(from_fn, from_macro);
+
+    let (from_macro, line) = (context!()(), line!());
+    let from_fn = context_string(file!(), line, String::new());
+    assert_eq!(from_fn, from_macro);
+
+    // Prefix and formatter are used the same way
+    fn bracketed(file: &str, line: u32, msg: &str, _options: &crate::ContextOptions) -> String {
+        format!("[{file}:{line}] {msg}")
+    }
+    crate::set_context_prefix("[request=3]".to_string());
+    crate::set_context_formatter(bracketed);
+    let (from_macro, line) = (context!("Value {} is invalid", 42)(), line!());
+    let from_fn = context_string(file!(), line, format!("Value {} is invalid", 42));
+    crate::reset_context_formatter();
+    crate::clear_context_prefix();
+    assert_eq!(from_fn, from_macro);
+    assert_eq!(
+        from_fn,
+        format!("[request=3] [src/tests/context.rs:{line}] Value 42 is invalid")
+    );
+}
+
+#[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+#[test]
+fn context_custom_separator() {
+    let line = line!() + 1;
+    let ctx = context!(@sep = "\n", "Value {} is too small", 5);
+    assert_eq!(
+        ctx(),
+        format!("src/tests/context.rs:{line}\nValue 5 is too small")
+    );
+
+    // Braces in the separator are not format placeholders
+    let line = line!() + 1;
+    let ctx = context!(@sep = " {} ", "Failed");
+    assert_eq!(ctx(), format!("src/tests/context.rs:{line} {{}} Failed"));
+
+    // No message, no separator
+    let line = line!() + 1;
+    let ctx = context!(@sep = "\n");
+    assert_eq!(ctx(), format!("src/tests/context.rs:{line}"));
+}
+
+#[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+#[test]
+fn context_custom_separator_with_context() {
+    fn fails() -> anyhow::Result<()> {
+        anyhow::bail!("inner")
+    }
+
+    let line = line!() + 1;
+    let ctx = context!(@sep = "\n", "outer");
+    let error = fails().with_context(ctx).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        format!("src/tests/context.rs:{line}\nouter")
+    );
+}
+
+#[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+#[test]
+fn context_thread_name() {
+    let handle = std::thread::Builder::new()
+        .name("worker-7".to_string())
+        .spawn(|| {
+            let line = line!() + 1;
+            let ctx = context!(@thread, "Value {} is too small", 5);
+            assert_eq!(
+                ctx(),
+                format!("src/tests/context.rs:{line} (thread: worker-7)\r\nValue 5 is too small")
+            );
+
+            // Combined with a custom separator, no message
+            let line = line!() + 1;
+            let ctx = context!(@sep = "\n", @thread);
+            assert_eq!(
+                ctx(),
+                format!("src/tests/context.rs:{line} (thread: worker-7)")
+            );
+        })
+        .unwrap();
+    handle.join().unwrap();
+
+    let handle = std::thread::spawn(|| {
+        let line = line!() + 1;
+        let ctx = context!(@thread);
+        assert_eq!(
+            ctx(),
+            format!("src/tests/context.rs:{line} (thread: unnamed)")
+        );
+    });
+    handle.join().unwrap();
+}
+
+#[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+#[test]
+fn context_oneline_no_new_lines() {
+    let name = "config.toml";
+    let line = line!() + 1;
+    let ctx = context!(@oneline, "Missing {} in {}", "key", name);
+    let result = ctx();
+    assert_eq!(
+        result,
+        format!("Missing key in config.toml (at src/tests/context.rs:{line})")
+    );
+    assert!(!result.contains('\n') && !result.contains('\r'));
+
+    let line = line!() + 1;
+    let ctx = context!(@oneline);
+    assert_eq!(ctx(), format!("src/tests/context.rs:{line}"));
+
+    let line = line!() + 1;
+    let ctx = context_code!("E1", @oneline, "Failed");
+    let result = ctx();
+    assert_eq!(
+        result,
+        format!("[code=E1] Failed (at src/tests/context.rs:{line})")
+    );
+    assert_eq!(extract_code(&result), Some("E1"));
+
+    let handle = std::thread::Builder::new()
+        .name("log-worker".to_string())
+        .spawn(|| {
+            let line = line!() + 1;
+            let ctx = context!(@thread, @oneline, "Failed");
+            assert_eq!(
+                ctx(),
+                format!("Failed (at src/tests/context.rs:{line}, thread: log-worker)")
+            );
+        })
+        .unwrap();
+    handle.join().unwrap();
+}
+
+#[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+#[test]
+fn context_prefix_added_on_current_thread() {
+    crate::set_context_prefix("[service=billing]".to_string());
+
+    let line = line!() + 1;
+    let ctx = context!("Value {} is too small", 5);
+    assert_eq!(
+        ctx(),
+        format!("[service=billing] src/tests/context.rs:{line}\r\nValue 5 is too small")
+    );
+
+    // Other threads don't see the prefix
+    std::thread::spawn(|| {
+        let line = line!() + 1;
+        let ctx = context!();
+        assert_eq!(ctx(), format!("src/tests/context.rs:{line}"));
+    })
+    .join()
+    .unwrap();
+
+    // Prefix is read when the closure is called
+    let ctx = context!();
+    crate::set_context_prefix("[service=orders]".to_string());
+    assert!(ctx().starts_with("[service=orders] src/tests/context.rs:"));
+
+    // `context_code!` keeps the code at the start
+    assert_eq!(extract_code(&context_code!("E1")()), Some("E1"));
+
+    // Macros built on `context!` add the prefix too
+    fn check(value: u8) -> anyhow::Result<u8> {
+        ensure_ctx!(value > 1, "Value {} is too small", value);
+        if value > 5 {
+            bail_ctx!("Value {} is too big", value);
+        }
+        Ok(value)
+    }
+    assert!(
+        check(0)
+            .unwrap_err()
+            .to_string()
+            .starts_with("[service=orders] src/tests/context.rs:")
+    );
+    assert!(
+        check(9)
+            .unwrap_err()
+            .to_string()
+            .starts_with("[service=orders] src/tests/context.rs:")
+    );
+    let wrapped = wrap_ctx!(std::fmt::Error, "Formatting failed");
+    assert!(
+        wrapped
+            .to_string()
+            .starts_with("[service=orders] src/tests/context.rs:")
+    );
+
+    crate::clear_context_prefix();
+    let line = line!() + 1;
+    assert_eq!(context!()(), format!("src/tests/context.rs:{line}"));
+}
+
+#[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+#[test]
+fn context_custom_formatter() {
+    fn bracketed(file: &str, line: u32, msg: &str, options: &crate::ContextOptions) -> String {
+        match options.sep {
+            Some(sep) => format!("[{file}:{line}]{sep}{msg}"),
+            None => format!("[{file}:{line}] {msg}"),
+        }
+    }
+
+    crate::set_context_formatter(bracketed);
+
+    let line = line!() + 1;
+    let ctx = context!("Value {} is too small", 5);
+    assert_eq!(
+        ctx(),
+        format!("[src/tests/context.rs:{line}] Value 5 is too small")
+    );
+
+    // `@...` options are passed separately, message is passed without them
+    let line = line!() + 1;
+    let ctx = context!(@sep = "\n", @thread, "Failed");
+    assert_eq!(ctx(), format!("[src/tests/context.rs:{line}]\nFailed"));
+
+    // Empty message
+    let line = line!() + 1;
+    assert_eq!(context!()(), format!("[src/tests/context.rs:{line}] "));
+
+    // Prefix is still added before the formatter output
+    crate::set_context_prefix("[request=1]".to_string());
+    let line = line!() + 1;
+    let ctx = context!("Failed");
+    assert_eq!(
+        ctx(),
+        format!("[request=1] [src/tests/context.rs:{line}] Failed")
+    );
+    crate::clear_context_prefix();
+
+    // Other threads keep the default layout
+    std::thread::spawn(|| {
+        let line = line!() + 1;
+        assert_eq!(context!()(), format!("src/tests/context.rs:{line}"));
+    })
+    .join()
+    .unwrap();
+
+    // Default formatter gives the same result as no formatter
+    crate::set_context_formatter(crate::default_context_formatter);
+    let line = line!() + 1;
+    let with_default = context!("Value {} is too small", 5)();
+    crate::reset_context_formatter();
+    assert_eq!(
+        with_default,
+        format!("src/tests/context.rs:{line}\r\nValue 5 is too small")
+    );
+}
+
+#[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+#[test]
+fn context_formatter_receives_options() {
+    fn options_only(_file: &str, _line: u32, msg: &str, options: &crate::ContextOptions) -> String {
+        format!("{msg} {options:?}")
+    }
+
+    const __FN_NAME: &str = "handler";
+    crate::set_context_formatter(options_only);
+    let with_options = context!(@sep = " | ", @thread, @fn_name, "Failed {}", 1)();
+    let with_oneline = context!(@oneline, "Failed")();
+    crate::reset_context_formatter();
+
+    assert_eq!(
+        with_options,
+        format!(
+            "Failed 1 {:?}",
+            crate::ContextOptions {
+                sep: Some(" | "),
+                thread: true,
+                oneline: false,
+                fn_name: Some("handler"),
+            }
+        )
+    );
+    assert_eq!(
+        with_oneline,
+        format!(
+            "Failed {:?}",
+            crate::ContextOptions {
+                oneline: true,
+                ..Default::default()
+            }
+        )
+    );
+}
+
+#[test]
+fn default_context_formatter_same_as_options() {
+    const __FN_NAME: &str = "handler";
+
+    // Every option (and an empty message) laid out the same way with and without the default formatter
+    fn both(create: fn() -> String) -> (String, String) {
+        crate::reset_context_formatter();
+        let without = create();
+        crate::set_context_formatter(crate::default_context_formatter);
+        let with = create();
+        crate::reset_context_formatter();
+        (without, with)
+    }
+
+    let cases: [fn() -> String; 6] = [
+        || context!(@sep = " - ", "Failed {}", 1)(),
+        || context!(@thread, "Failed")(),
+        || context!(@fn_name, @thread)(),
+        || context!(@oneline, @fn_name, "Failed")(),
+        || context!(@oneline, @thread)(),
+        || context!(@fn_name, @sep = ": ", "Failed")(),
+    ];
+    for create in cases {
+        let (without, with) = both(create);
+        assert_eq!(without, with);
+    }
+}
+
+#[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+#[test]
+fn context_str_same_as_called_closure() {
+    let line = line!() + 1;
+    let message = context_str!("x {}", 1);
+    assert_eq!(message, format!("src/tests/context.rs:{line}\r\nx 1"));
+
+    let line = line!() + 1;
+    assert_eq!(context_str!(), format!("src/tests/context.rs:{line}"));
+
+    let line = line!() + 1;
+    let message = context_str!(@oneline, "x {}", 1);
+    assert_eq!(message, format!("x 1 (at src/tests/context.rs:{line})"));
+}
+
+#[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+#[test]
+fn context_err_on_none() {
+    let value: Option<u8> = None;
+    let line = line!() + 2;
+    let error = value
+        .ok_or_else(context_err!("Value {} is missing", "x"))
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        format!("src/tests/context.rs:{line}\r\nValue x is missing")
+    );
+
+    let line = line!() + 1;
+    let error = value.ok_or_else(context_err!()).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains(&format!("src/tests/context.rs:{line}"))
+    );
+
+    // Closure isn't called for `Some`
+    assert_eq!(Some(1).ok_or_else(context_err!()).unwrap(), 1);
+}
+
+#[cfg(any(debug_assertions, not(feature = "strip-location-in-release")))]
+#[test]
+fn with_fn_name_in_annotated_function() {
+    struct Loader;
+
+    impl Loader {
+        #[with_fn_name]
+        fn load(&self) -> Vec<String> {
+            let line = line!();
+            let in_closure = || context_str!(@oneline, "closure");
+            //Nested items are skipped, otherwise they would use the outer `__FN_NAME`
+            fn nested() -> String {
+                context_str!()
+            }
+            vec![
+                context!()(),
+                format!("{} {line}", context_str!("in {}", "macro")),
+                in_closure(),
+                nested(),
+            ]
+        }
+    }
+
+    let line = line!() - 15;
+    assert_eq!(
+        Loader.load(),
+        vec![
+            format!("src/tests/context.rs:{} (fn: load)", line + 7),
+            format!(
+                "src/tests/context.rs:{} (fn: load)\r\nin macro {line}",
+                line + 8
+            ),
+            format!("closure (at src/tests/context.rs:{}, fn: load)", line + 1),
+            format!("src/tests/context.rs:{}", line + 4),
+        ]
+    );
+}
+
+#[cfg(all(
+    feature = "context-json",
+    any(debug_assertions, not(feature = "strip-location-in-release"))
+))]
+#[test]
+fn context_json_valid_object() {
+    let path = std::path::Path::new("config.toml");
+    let attempts = vec![1, 2];
+    let error = std::fs::read_to_string("/nonexistent/config.toml")
+        .with_context(context!(@json, "Reading \"config\"", path = path, attempts = attempts))
+        .unwrap_err();
+
+    let line = line!() - 3;
+
+    //Compared as parsed values, key order depends on `serde_json` features
+    let parsed: serde_json::Value = serde_json::from_str(&error.to_string()).unwrap();
+    assert_eq!(
+        parsed,
+        serde_json::json!({
+            "file": "src/tests/context.rs",
+            "line": line,
+            "msg": "Reading \"config\"",
+            "path": "config.toml",
+            "attempts": [1, 2],
+        })
+    );
+
+    //No message, only location
+    let line = line!() + 1;
+    let parsed: serde_json::Value = serde_json::from_str(&context!(@json)()).unwrap();
+    assert_eq!(
+        parsed,
+        serde_json::json!({ "file": "src/tests/context.rs", "line": line })
+    );
+}
+
+#[test]
+fn dedup_context_chain_groups_files() {
+    let root = anyhow::anyhow!("root cause");
+    let error = root
+        .context(context_string("src/db.rs", 7, "Query".to_string()))
+        .context(context_string("src/api.rs", 30, String::new()))
+        .context(context_string("src/api.rs", 12, "Handler".to_string()));
+
+    let rendered = dedup_context_chain(&error);
+    assert_eq!(
+        rendered,
+        "src/api.rs:12, 30\r\nHandler\r\nCaused by: src/db.rs:7\r\nQuery\r\nCaused by: root cause"
+    );
+    assert_eq!(rendered.matches("src/api.rs").count(), 1);
+
+    // Same file separated by another one isn't merged
+    let error = anyhow::anyhow!(context_string("src/a.rs", 1, "A".to_string()))
+        .context(context_string("src/b.rs", 2, "B".to_string()))
+        .context(context_string("src/a.rs", 3, "C".to_string()));
+    assert_eq!(
+        dedup_context_chain(&error),
+        "src/a.rs:3\r\nC\r\nCaused by: src/b.rs:2\r\nB\r\nCaused by: src/a.rs:1\r\nA"
+    );
+}
+
+//Release counterparts of the location checking tests, which are skipped with the location stripped
+#[cfg(all(not(debug_assertions), feature = "strip-location-in-release"))]
+#[test]
+fn strip_location_in_release_other_macros() {
+    fn delete_user(user_id: u64) -> anyhow::Result<()> {
+        crate::bail_ctx!("Failed to delete user {}", user_id);
+    }
+    fn check(value: u64) -> anyhow::Result<u64> {
+        ensure_ctx!(value > 10, "Value {} is too small", value);
+        Ok(value)
+    }
+    fn bracketed(file: &str, line: u32, msg: &str, _options: &crate::ContextOptions) -> String {
+        format!("[{file}:{line}] {msg}")
+    }
+
+    assert_eq!(
+        delete_user(42).unwrap_err().to_string(),
+        "Failed to delete user 42"
+    );
+    assert_eq!(check(5).unwrap_err().to_string(), "Value 5 is too small");
+
+    let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "config.toml missing");
+    let error = crate::wrap_ctx!(io_error, "Loading config for {}", "server");
+    assert_eq!(error.to_string(), "Loading config for server");
+    assert_eq!(error.root_cause().to_string(), "config.toml missing");
+
+    assert_eq!(
+        context_code!("DB_MISSING", "Table {} is missing", "users")(),
+        "[code=DB_MISSING] Table users is missing"
+    );
+
+    // Prefix is still added
+    crate::set_context_prefix("[request=5]".to_string());
+    assert_eq!(context!("Loading")(), "[request=5] Loading");
+    crate::clear_context_prefix();
+
+    // Formatter receives the location, it isn't called when the location is stripped
+    crate::set_context_formatter(bracketed);
+    assert_eq!(context!("Loading")(), "Loading");
+    crate::reset_context_formatter();
+}
+
+#[cfg(feature = "strip-location-in-release")]
+#[test]
+fn strip_location_in_release() {
+    let message = context!("Loading {}", "config")();
+    let coded = context_code!("E1", "Loading")();
+    let empty = context!()();
+
+    #[cfg(debug_assertions)]
+    {
+        assert!(message.starts_with("src/tests/context.rs:"));
+        assert!(message.ends_with("\r\nLoading config"));
+        assert!(coded.starts_with("[code=E1] src/tests/context.rs:"));
+        assert!(empty.starts_with("src/tests/context.rs:"));
+    }
+    #[cfg(not(debug_assertions))]
+    {
+        assert_eq!(message, "Loading config");
+        assert_eq!(coded, "[code=E1] Loading");
+        assert_eq!(empty, "");
+        assert_eq!(context_code!(404)(), "[code=404]");
+        assert_eq!(context!(@thread, @oneline, "Loading")(), "Loading");
+    }
+}
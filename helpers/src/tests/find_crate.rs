@@ -0,0 +1,121 @@
+use crate::find_crate::optional_dependency_features;
+use crate::find_reexport;
+use quote::quote;
+
+#[test]
+fn regular_dependency_is_not_optional() {
+    let manifest = r#"
+        [dependencies]
+        syn = "2.0"
+        quote = { version = "1.0" }
+    "#;
+    assert_eq!(optional_dependency_features(manifest, "syn"), None);
+    assert_eq!(optional_dependency_features(manifest, "quote"), None);
+    assert_eq!(optional_dependency_features(manifest, "missing"), None);
+}
+
+#[test]
+fn implicit_feature() {
+    let manifest = r#"
+        [dependencies]
+        syn = { version = "2.0", optional = true }
+
+        [features]
+        macros = ["syn"]
+        full = ["macros"]
+        other = []
+    "#;
+    assert_eq!(
+        optional_dependency_features(manifest, "syn"),
+        Some(vec![
+            "syn".to_string(),
+            "macros".to_string(),
+            "full".to_string()
+        ])
+    );
+}
+
+#[test]
+fn dep_syntax_and_sub_features() {
+    let manifest = r#"
+        [dependencies]
+        syn = { version = "2.0", optional = true }
+        serde = { version = "1.0", optional = true }
+
+        [features]
+        parsing = ["dep:syn"]
+        derive = ["serde/derive"]
+        weak = ["serde?/std"]
+    "#;
+    //No implicit `syn` feature because of `dep:syn`
+    assert_eq!(
+        optional_dependency_features(manifest, "syn"),
+        Some(vec!["parsing".to_string()])
+    );
+    //`serde?/std` doesn't enable the dependency
+    assert_eq!(
+        optional_dependency_features(manifest, "serde"),
+        Some(vec!["serde".to_string(), "derive".to_string()])
+    );
+}
+
+#[test]
+fn renamed_and_target_dependency() {
+    let manifest = r#"
+        [target.'cfg(unix)'.dependencies]
+        my_syn = { package = "syn", version = "2.0", optional = true }
+
+        [features]
+        unix-parsing = ["dep:my_syn"]
+    "#;
+    assert_eq!(
+        optional_dependency_features(manifest, "syn"),
+        Some(vec!["unix-parsing".to_string()])
+    );
+}
+
+#[test]
+fn find_reexport_two_roots() {
+    //First root missing, second one used with its re-export path
+    let found = find_reexport(
+        &[
+            ("missing-root-crate", quote! {::reexported}),
+            ("quote", quote! {::__private}),
+        ],
+        "TokenStream",
+    );
+    assert_eq!(
+        found.unwrap().to_string(),
+        quote! { quote::__private::TokenStream }.to_string()
+    );
+
+    //Both present, first one wins, multi segment leaf
+    let found = find_reexport(&[("syn", quote! {}), ("quote", quote! {})], "token::Comma");
+    assert_eq!(
+        found.unwrap().to_string(),
+        quote! { syn::token::Comma }.to_string()
+    );
+
+    //No root present
+    assert!(find_reexport(&[("missing-root-crate", quote! {})], "Comma").is_none());
+
+    //Empty leaf, re-exported crate itself
+    let found = find_reexport(
+        &[
+            ("missing-root-crate", quote! {::reexported}),
+            ("quote", quote! {::__private}),
+        ],
+        "",
+    );
+    assert_eq!(
+        found.unwrap().to_string(),
+        quote! { quote::__private }.to_string()
+    );
+}
+
+#[test]
+fn find_reexport_invalid_leaf() {
+    assert!(find_reexport(&[("syn", quote! {})], "token::").is_none());
+    assert!(find_reexport(&[("syn", quote! {})], "token Comma").is_none());
+    assert!(find_reexport(&[("syn", quote! {})], "::Comma").is_none());
+}
@@ -0,0 +1,76 @@
+//! Tests for indexed lifetimes, identifier groups and unique identifiers
+
+use crate::{indexed_lifetime, indexed_name_groups, unique_ident};
+use quote::quote;
+
+#[test]
+fn indexed_lifetime_in_bound_list() {
+    let lifetimes = indexed_lifetime("a", 3);
+    let output = quote! {
+        impl<#(#lifetimes),*> Trait for Item where #(#lifetimes: 'static),* {}
+    };
+    let expected = quote! {
+        impl<'a0, 'a1, 'a2> Trait for Item where 'a0: 'static, 'a1: 'static, 'a2: 'static {}
+    };
+    assert_eq!(output.to_string(), expected.to_string());
+
+    // Generated code is valid
+    let item: syn::ItemImpl = syn::parse2(output).unwrap();
+    assert_eq!(item.generics.lifetimes().count(), 3);
+}
+
+#[test]
+fn indexed_lifetime_with_apostrophe_and_empty() {
+    let lifetimes = indexed_lifetime("'de", 2);
+    assert_eq!(lifetimes[0].ident, "de0");
+    assert_eq!(lifetimes[1].to_string(), "'de1");
+
+    assert!(indexed_lifetime("a", 0).is_empty());
+}
+
+#[test]
+fn unique_ident_calls_differ() {
+    let first = unique_ident("helper");
+    let second = unique_ident("helper");
+    assert_ne!(first, second);
+
+    for ident in [&first, &second] {
+        let ident = ident.to_string();
+        let counter = ident.strip_prefix("__helper_").unwrap();
+        counter.parse::<usize>().unwrap();
+    }
+
+    // Generated identifier is usable as an item name
+    let item: syn::ItemStruct = syn::parse2(quote! { struct #first; }).unwrap();
+    assert_eq!(item.ident, first);
+}
+
+#[test]
+fn indexed_name_groups_aligned() {
+    let groups = indexed_name_groups(&["a", "b"], 3);
+
+    assert_eq!(groups.len(), 2);
+    let names = groups
+        .iter()
+        .map(|group| {
+            group
+                .iter()
+                .map(|ident| ident.to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec![vec!["a0", "a1", "a2"], vec!["b0", "b1", "b2"]]);
+
+    // Zipped in `quote!`
+    let (a, b) = (&groups[0], &groups[1]);
+    assert_eq!(
+        quote! { #(let #b = #a;)* }.to_string(),
+        quote! { let b0 = a0; let b1 = a1; let b2 = a2; }.to_string()
+    );
+
+    // Empty inputs
+    assert!(indexed_name_groups(&[], 3).is_empty());
+    let empty_groups = indexed_name_groups(&["a", "b"], 0);
+    assert_eq!(empty_groups.len(), 2);
+    assert!(empty_groups.iter().all(Vec::is_empty));
+}
@@ -3,3 +3,22 @@ mod readable_token_stream;
 
 #[cfg(feature = "context")]
 mod context;
+
+//Every test checks the `file:line` location
+#[cfg(all(
+    feature = "std-only",
+    any(debug_assertions, not(feature = "strip-location-in-release"))
+))]
+mod std_only;
+
+#[cfg(feature = "tokens-builder")]
+mod tokens_builder;
+
+#[cfg(feature = "token-stream-consistent")]
+mod token_stream_to_consistent_string;
+
+#[cfg(feature = "indexed-name")]
+mod indexed_name;
+
+#[cfg(feature = "find-crate")]
+mod find_crate;
@@ -1,4 +1,4 @@
-use crate::readable_token_stream;
+use crate::{ReadableOptions, readable_token_stream, readable_token_stream_with};
 
 #[test]
 fn test_removes_unnecessary_spaces() {
@@ -372,6 +372,65 @@ fn test_idempotency() {
     }
 }
 
+#[test]
+fn test_idempotency_join_operators() {
+    let options = ReadableOptions {
+        join_operators: true,
+        ..Default::default()
+    };
+    let test_cases = vec![
+        "x + = y",
+        "x < < 1",
+        "1 > > x",
+        "x < < 1 > > y",
+        "let v : Vec < T > = x",
+        "a = = b",
+        "Vec < Vec < u8 > > = x",
+        "HashMap < u32 , Vec < i64 > >",
+    ];
+
+    for input in test_cases {
+        let first_pass = readable_token_stream_with(input, options);
+        let second_pass = readable_token_stream_with(&first_pass, options);
+        assert_eq!(
+            first_pass, second_pass,
+            "Function should be idempotent. Input: `{input}`, First: `{first_pass}`, Second: `{second_pass}`"
+        );
+    }
+}
+
+#[test]
+fn test_idempotency_all_options() {
+    //`TokenStream::to_string` output, tokens separated by single spaces
+    let test_cases = [
+        "x + = y",
+        "x < < 1",
+        "Vec < Vec < u8 > > = x",
+        "fn main ( ) { foo ( a , b ) ; }",
+        "( hello )",
+        "# [ derive ( Debug ) ] struct S < > { }",
+        "impl < T > Clone for Vec < T > { fn clone ( & self ) -> Self { todo ! ( ) } }",
+        "let v : Vec < T > = x ;",
+    ];
+
+    for join_operators in [false, true] {
+        for trim_trailing in [false, true] {
+            let options = ReadableOptions {
+                join_operators,
+                trim_trailing,
+            };
+            for input in test_cases {
+                let first_pass = readable_token_stream_with(input, options);
+                let second_pass = readable_token_stream_with(&first_pass, options);
+                assert_eq!(
+                    first_pass, second_pass,
+                    "Function should be idempotent. Input: `{input}`, Options: {options:?}, First: `{first_pass}`, Second: `{second_pass}`"
+                );
+            }
+        }
+    }
+}
+
 #[test]
 fn test_whitespace_only_removal_invariant() {
     // The function should only remove whitespace characters, never content
@@ -538,3 +597,142 @@ fn test_unicode_and_special_chars() {
         );
     }
 }
+
+#[test]
+fn test_join_operators() {
+    let options = ReadableOptions {
+        join_operators: true,
+        ..Default::default()
+    };
+    let test_cases = vec![
+        // Compound assignment
+        ("x + = y", "x += y"),
+        ("x - = y", "x -= y"),
+        ("x * = y", "x *= y"),
+        ("x / = y", "x /= y"),
+        ("x % = y", "x %= y"),
+        ("x ^ = y", "x ^= y"),
+        ("x | = y", "x |= y"),
+        ("x & = y", "x &= y"),
+        // Comparison
+        ("a = = b", "a == b"),
+        ("a ! = b", "a!= b"),
+        ("a < = b", "a<= b"),
+        // `>=` is not joined, generics followed by `=` are more common
+        ("let v : Vec < T > = x", "let v: Vec<T> = x"),
+        // Shifts, only with a number literal next to them
+        ("x < < 1", "x << 1"),
+        ("1 > > x", "1 >> x"),
+        ("x < < 1 > > y", "x << 1 >> y"),
+        // Generics
+        ("< < x > >", "<<x>>"),
+        ("Vec < Vec < T > >", "Vec<Vec<T>>"),
+        ("Vec < Vec < T > > = x", "Vec<Vec<T>> = x"),
+        // Identifiers ending with a digit are not number literals
+        ("Vec < Vec < u8 > >", "Vec<Vec<u8>>"),
+        ("Option < Vec < i64 > >", "Option<Vec<i64>>"),
+        ("HashMap < u32 , Vec < i64 > >", "HashMap<u32, Vec<i64>>"),
+        ("Vec < Vec < T1 > > = x", "Vec<Vec<T1>> = x"),
+        ("x < < 1u8", "x << 1u8"),
+        ("0x1F > > y", "0x1F >> y"),
+        // Same for already joined `>>` (second pass)
+        ("let v : Vec<u8 >> = x", "let v: Vec<u8>> = x"),
+        ("1 >> x", "1 >> x"),
+        // Already joined operators and `=>` are left as they are
+        ("x += y", "x += y"),
+        ("Some ( y ) => y", "Some(y ) => y"),
+        ("let x = - y", "let x = - y"),
+    ];
+
+    for (input, expected) in test_cases {
+        let result = readable_token_stream_with(input, options);
+        assert_eq!(result, expected, "Failed for operators: `{input}`");
+    }
+}
+
+#[test]
+fn test_default_options_same_as_readable_token_stream() {
+    let test_cases = vec![
+        "x + = y",
+        "x < < 1",
+        "1 > > x",
+        "impl < T > Clone for Vec < T > where T : Clone",
+        "match x { Some ( y ) => y , None => 0 }",
+    ];
+
+    for input in test_cases {
+        assert_eq!(
+            readable_token_stream_with(input, ReadableOptions::default()),
+            readable_token_stream(input),
+            "Failed for input: `{input}`"
+        );
+    }
+}
+
+#[test]
+fn test_trim_trailing() {
+    let options = ReadableOptions {
+        trim_trailing: true,
+        ..Default::default()
+    };
+    let test_cases = vec![
+        (" ( hello )", "(hello)"),
+        ("hello ( ) ", "hello()"),
+        (" [ ] ", "[]"),
+        (" < > ", "<>"),
+        ("( a , b  )", "(a, b)"),
+        ("[ 1 , 2 ]", "[1, 2]"),
+        ("foo  ", "foo"),
+        // Braces keep their spacing
+        ("{ a }", "{ a }"),
+        ("{    }", "{ }"),
+    ];
+
+    for (input, expected) in test_cases {
+        let result = readable_token_stream_with(input, options);
+        assert_eq!(result, expected, "Failed for input: `{input}`");
+        assert_eq!(
+            result,
+            result.trim_end(),
+            "Trailing whitespace for: `{input}`"
+        );
+    }
+}
+
+#[test]
+fn test_trim_trailing_with_join_operators() {
+    let options = ReadableOptions {
+        join_operators: true,
+        trim_trailing: true,
+    };
+    assert_eq!(
+        readable_token_stream_with("( x + = 1 ) ", options),
+        "(x += 1)"
+    );
+    assert_eq!(
+        readable_token_stream_with("f ( x < < 1 )", options),
+        "f(x << 1)"
+    );
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn assert_tokens_eq_quote_fragment() {
+    let tokens = quote::quote! {
+        impl Display for Wrapper<T> {
+            fn fmt(&self, f: &mut Formatter) -> Result { write!(f, "{}", self.0) }
+        }
+    };
+    crate::assert_tokens_eq!(
+        tokens,
+        "impl Display for Wrapper<T>{ fn fmt(&self, f: &mut Formatter) -> Result { write!(f, \"{}\", self.0) }}"
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "  actual: Vec<u8>\nexpected: Vec<u16>\n               ^ first difference at character 5"
+)]
+fn assert_tokens_eq_failure_message() {
+    crate::assert_tokens_eq!("Vec < u8 >", "Vec<u16>");
+}
@@ -0,0 +1,109 @@
+//! Tests of the `std-only` context macros, without anyhow
+
+use crate::{bail_ctx_std, context_err_std, context_map_err, ensure_ctx_std, wrap_ctx_std};
+use std::error::Error;
+
+#[test]
+fn context_without_message() {
+    let line = line!() + 1;
+    let error = "x".parse::<i32>().map_err(context_map_err!()).unwrap_err();
+    assert_eq!(
+        error,
+        format!("src/tests/std_only.rs:{line}\r\nCaused by: invalid digit found in string")
+    );
+}
+
+#[test]
+fn context_with_question_mark_into_box() {
+    fn parse(value: &str) -> Result<i32, Box<dyn Error + Send + Sync>> {
+        Ok(value
+            .parse::<i32>()
+            .map_err(context_map_err!("Parsing {}", value))?)
+    }
+
+    assert_eq!(parse("5").unwrap(), 5);
+    let error = parse("five").unwrap_err().to_string();
+    assert!(error.contains("\r\nParsing five\r\nCaused by: "), "{error}");
+}
+
+#[test]
+fn bail_ctx_into_string_error() {
+    fn fail(code: u32) -> Result<(), String> {
+        bail_ctx_std!("Failed with {}", code);
+    }
+
+    let line = line!() - 3;
+    assert_eq!(
+        fail(3).unwrap_err(),
+        format!("src/tests/std_only.rs:{line}\r\nFailed with 3")
+    );
+}
+
+#[test]
+fn ensure_ctx_passes() {
+    fn check(value: i32) -> Result<i32, Box<dyn Error>> {
+        ensure_ctx_std!(value > 0);
+        Ok(value)
+    }
+
+    assert_eq!(check(1).unwrap(), 1);
+    let line = line!() - 5;
+    assert_eq!(
+        check(0).unwrap_err().to_string(),
+        format!("src/tests/std_only.rs:{line}")
+    );
+}
+
+#[test]
+fn wrap_ctx_boxed_error() {
+    let error = "x".parse::<u8>().unwrap_err();
+    let wrapped = wrap_ctx_std!(error, "Reading byte");
+    let message = wrapped.to_string();
+    assert!(
+        message.contains("\r\nReading byte\r\nCaused by: "),
+        "{message}"
+    );
+}
+
+#[test]
+fn context_err_into_box() {
+    let line = line!() + 2;
+    fn first(values: &[i32]) -> Result<i32, Box<dyn Error + Send + Sync>> {
+        Ok(*values.first().ok_or_else(context_err_std!("No values"))?)
+    }
+
+    assert_eq!(first(&[3]).unwrap(), 3);
+    assert_eq!(
+        first(&[]).unwrap_err().to_string(),
+        format!("src/tests/std_only.rs:{line}\r\nNo values")
+    );
+}
+
+#[test]
+fn bail_ctx_and_ensure_ctx_use_prefix() {
+    let line = line!() + 2;
+    fn check(value: i32) -> Result<i32, String> {
+        ensure_ctx_std!(value > 0, "Value {} is too small", value);
+        if value > 5 {
+            bail_ctx_std!("Value {} is too big", value);
+        }
+        Ok(value)
+    }
+
+    crate::set_context_prefix("[request=9]".to_string());
+    let small = check(0).unwrap_err();
+    let big = check(6).unwrap_err();
+    crate::clear_context_prefix();
+
+    assert_eq!(
+        small,
+        format!("[request=9] src/tests/std_only.rs:{line}\r\nValue 0 is too small")
+    );
+    assert_eq!(
+        big,
+        format!(
+            "[request=9] src/tests/std_only.rs:{}\r\nValue 6 is too big",
+            line + 2
+        )
+    );
+}
@@ -0,0 +1,231 @@
+//! Tests for consistent string conversion with sorted attributes and diffs
+
+use crate::{
+    consistent_string_diff, token_stream_to_consistent_string,
+    token_stream_to_consistent_string_canonical_numbers,
+    token_stream_to_consistent_string_sorted_attrs,
+};
+use proc_macro2::TokenStream;
+
+fn tokens(s: &str) -> TokenStream {
+    s.parse().unwrap()
+}
+
+#[test]
+fn sorted_attrs_order_insensitive() {
+    let a = tokens("#[a] #[b(x = 1)] #[c] pub fn test() {}");
+    let b = tokens("#[c] #[a] #[b(x = 1)] pub fn test() {}");
+
+    assert_eq!(
+        token_stream_to_consistent_string_sorted_attrs(a),
+        token_stream_to_consistent_string_sorted_attrs(b)
+    );
+}
+
+#[test]
+fn sorted_attrs_same_output_without_attributes() {
+    let item = "struct Test { field: u8 }";
+
+    assert_eq!(
+        token_stream_to_consistent_string_sorted_attrs(tokens(item)),
+        token_stream_to_consistent_string(tokens(item))
+    );
+}
+
+#[test]
+fn sorted_attrs_inner_attributes_keep_order() {
+    let a = tokens("#[a] #[b] struct Test { #[x] #[y] field: u8 }");
+    let b = tokens("#[b] #[a] struct Test { #[y] #[x] field: u8 }");
+
+    assert_ne!(
+        token_stream_to_consistent_string_sorted_attrs(a),
+        token_stream_to_consistent_string_sorted_attrs(b)
+    );
+}
+
+#[test]
+fn sorted_attrs_different_attributes_not_equal() {
+    let a = tokens("#[a] #[b] struct Test;");
+    let b = tokens("#[a] #[c] struct Test;");
+
+    assert_ne!(
+        token_stream_to_consistent_string_sorted_attrs(a),
+        token_stream_to_consistent_string_sorted_attrs(b)
+    );
+}
+
+#[test]
+fn diff_none_when_equal() {
+    let a = tokens("struct Test { field : u8 }");
+    let b = tokens("struct Test{field:u8}");
+
+    assert_eq!(consistent_string_diff(&a, &b), None);
+}
+
+#[test]
+fn diff_highlights_differing_token() {
+    let a = tokens("struct Test { field: u8 }");
+    let b = tokens("struct Test { field: u16 }");
+
+    let diff = consistent_string_diff(&a, &b).unwrap();
+    assert!(
+        diff.contains("left:  struct Test { field : ⟦u8⟧ }"),
+        "{diff}"
+    );
+    assert!(
+        diff.contains("right: struct Test { field : ⟦u16⟧ }"),
+        "{diff}"
+    );
+}
+
+#[test]
+fn diff_missing_tokens() {
+    let a = tokens("call(a, b)");
+    let b = tokens("call(a)");
+
+    let diff = consistent_string_diff(&a, &b).unwrap();
+    assert!(
+        diff.starts_with("token streams differ at token 3"),
+        "{diff}"
+    );
+    assert!(diff.contains("left:  call ( a ⟦, b⟧ )"), "{diff}");
+    assert!(diff.contains("right: call ( a ⟦⟧ )"), "{diff}");
+}
+
+///Previous implementation, converted every token with `to_string()` and allocated a new `String` per group
+fn reference_consistent_string(tokens: TokenStream) -> String {
+    use proc_macro2::{Delimiter, TokenTree};
+
+    let mut result_str = String::new();
+    for token in tokens.into_iter() {
+        match token {
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::None => ("", ""),
+                };
+                result_str.push_str(open);
+                result_str.push_str(&reference_consistent_string(group.stream()));
+                result_str.push_str(close);
+            }
+            TokenTree::Ident(ident) => result_str.push_str(ident.to_string().trim()),
+            TokenTree::Punct(punct) => result_str.push_str(punct.to_string().trim()),
+            TokenTree::Literal(literal) => result_str.push_str(literal.to_string().trim()),
+        }
+    }
+    result_str
+}
+
+const SINGLE_BUFFER_INPUTS: &[&str] = &[
+    "",
+    "struct Test { field : u8 , other : Vec < String > }",
+    "fn r#type(a: &mut [u8; 4]) -> Result<(), Box<dyn Error>> { a[0] += 1; Ok(()) }",
+    "#[derive(Debug)] #[serde(rename = \"x y\")] pub enum E { A(i32), B { b: f64 } }",
+    "let s = b\"bytes\"; let c = 'c'; let n = 1_000u64 >> 2; x <<= 3;",
+];
+
+#[test]
+fn single_buffer_same_as_reference() {
+    for input in SINGLE_BUFFER_INPUTS {
+        assert_eq!(
+            token_stream_to_consistent_string(tokens(input)),
+            reference_consistent_string(tokens(input)),
+            "{input}"
+        );
+    }
+}
+
+#[test]
+fn single_buffer_normalized_input_byte_identical() {
+    //Already normalized input, everything is written as it is
+    let normalized = "structTest{field:u8}";
+    let output = token_stream_to_consistent_string(tokens("struct Test { field: u8 }"));
+    assert_eq!(output, normalized);
+
+    let output_stream: TokenStream = tokens("structTest{field:u8}");
+    assert_eq!(
+        token_stream_to_consistent_string(output_stream.clone()).as_bytes(),
+        reference_consistent_string(output_stream).as_bytes()
+    );
+}
+
+///Run with `cargo test --features full consistent_string_benchmark -- --ignored --nocapture`
+#[test]
+#[ignore]
+fn consistent_string_benchmark() {
+    let input: TokenStream = SINGLE_BUFFER_INPUTS.iter().cycle().take(200).fold(
+        TokenStream::new(),
+        |mut stream, input| {
+            stream.extend(tokens(&format!("mod m {{ {input} }}")));
+            stream
+        },
+    );
+
+    let measure = |name: &str, f: &dyn Fn(TokenStream) -> String| {
+        let start = std::time::Instant::now();
+        let mut len = 0;
+        for _ in 0..200 {
+            len += f(input.clone()).len();
+        }
+        println!("{name}: {:?} ({len} bytes)", start.elapsed());
+    };
+
+    measure("reference", &reference_consistent_string);
+    measure("current", &token_stream_to_consistent_string);
+}
+
+#[test]
+fn path_separators_normalized() {
+    let expected = "std::collections::HashMap";
+    for path in [
+        "std :: collections :: HashMap",
+        "std::collections::HashMap",
+        "std:: collections:: HashMap",
+        "std\n::\tcollections ::HashMap",
+    ] {
+        assert_eq!(token_stream_to_consistent_string(tokens(path)), expected);
+    }
+
+    // Leading `::` and turbofish
+    assert_eq!(
+        token_stream_to_consistent_string(tokens(":: std :: vec :: Vec :: < u8 > :: new ( )")),
+        token_stream_to_consistent_string(quote::quote! { ::std::vec::Vec::<u8>::new() })
+    );
+    assert_eq!(
+        token_stream_to_consistent_string(quote::quote! { ::std::vec::Vec::<u8>::new() }),
+        "::std::vec::Vec::<u8>::new()"
+    );
+}
+
+#[test]
+fn canonical_numbers_separators_and_suffixes() {
+    let canonical = |s: &str| token_stream_to_consistent_string_canonical_numbers(tokens(s));
+
+    assert_eq!(canonical("1_000u32"), "1000u32");
+    assert_eq!(canonical("1000u32"), "1000u32");
+    assert_eq!(canonical("1_000_u32"), "1000u32");
+    assert_eq!(canonical("1000 u32"), "1000u32");
+    assert_eq!(canonical("1_u32"), canonical("1u32"));
+    assert_eq!(canonical("2_5.0_f64"), "25.0f64");
+    assert_eq!(canonical("0xFF_FF"), "0xFFFF");
+
+    // Nested in groups, other literals are left unchanged
+    assert_eq!(
+        canonical("f(1_000u32, \"1_000\", b'_', [1_0; 2])"),
+        "f(1000u32,\"1_000\",b'_',[10;2])"
+    );
+}
+
+#[test]
+fn canonical_numbers_default_keeps_separators() {
+    assert_ne!(
+        token_stream_to_consistent_string(tokens("1_000u32")),
+        token_stream_to_consistent_string(tokens("1000u32"))
+    );
+    // Different values or suffixes stay different
+    let canonical = |s: &str| token_stream_to_consistent_string_canonical_numbers(tokens(s));
+    assert_ne!(canonical("1_000u32"), canonical("1000u64"));
+    assert_ne!(canonical("0xff"), canonical("255"));
+}
@@ -0,0 +1,387 @@
+//! Tests for `TokensBuilder` conversions and parsing finalizers
+
+use crate::{TokensBuilder, split_items};
+use quote::quote;
+
+fn builder() -> TokensBuilder {
+    let mut builder = TokensBuilder::default();
+    builder.add(quote! { let a = 1; });
+    builder.add(quote! { let b = a; });
+    builder
+}
+
+#[test]
+fn into_token_stream_same_as_finalize() {
+    let finalized = builder().finalize();
+    let converted: proc_macro2::TokenStream = builder().into();
+
+    assert_eq!(converted.to_string(), finalized.to_string());
+}
+
+#[test]
+fn into_proc_macro_token_stream_available() {
+    // `proc_macro::TokenStream` can't be created outside of procedural macros,
+    // checking that the conversion exists is enough
+    fn assert_into<T: Into<proc_macro::TokenStream>>() {}
+    assert_into::<TokensBuilder>();
+}
+
+#[test]
+fn interpolation_in_quote() {
+    let builder = builder();
+    let builder_ref = &builder;
+
+    let result = quote! {
+        fn test() {
+            #builder_ref
+        }
+    };
+
+    assert_eq!(
+        result.to_string(),
+        quote! {
+            fn test() {
+                let a = 1;
+                let b = a;
+            }
+        }
+        .to_string()
+    );
+
+    // Builder is still usable after interpolation
+    assert_eq!(
+        builder.finalize().to_string(),
+        quote! { let a = 1; let b = a; }.to_string()
+    );
+}
+
+#[test]
+fn interpolation_of_empty_builder() {
+    let builder = TokensBuilder::default();
+    let builder_ref = &builder;
+
+    assert_eq!(
+        quote! { a #builder_ref b }.to_string(),
+        quote! { a b }.to_string()
+    );
+}
+
+#[test]
+fn finalize_item_valid() {
+    let mut builder = TokensBuilder::default();
+    builder.add(quote! { struct Generated });
+    builder.add(quote! { { field: i32 } });
+
+    let item = builder.finalize_item().unwrap();
+    let syn::Item::Struct(item) = item else {
+        panic!(
+            "Expected struct, got: {}",
+            quote::ToTokens::to_token_stream(&item)
+        );
+    };
+    assert_eq!(item.ident, "Generated");
+}
+
+#[test]
+fn finalize_item_broken() {
+    let mut builder = TokensBuilder::default();
+    builder.add(quote! { struct Generated { field: } });
+    assert!(builder.finalize_item().is_err());
+
+    // Two items are not a single item
+    let mut builder = TokensBuilder::default();
+    builder.add(quote! { struct A; });
+    builder.add(quote! { struct B; });
+    assert!(builder.finalize_item().is_err());
+}
+
+#[test]
+fn finalize_expr_valid_and_broken() {
+    let mut builder = TokensBuilder::default();
+    builder.add(quote! { a + });
+    builder.add(quote! { b });
+    assert!(matches!(builder.finalize_expr(), Ok(syn::Expr::Binary(_))));
+
+    let mut builder = TokensBuilder::default();
+    builder.add(quote! { a + });
+    assert!(builder.finalize_expr().is_err());
+}
+
+#[test]
+fn finalize_block_valid_and_broken() {
+    let block = builder().finalize_block().unwrap();
+    assert_eq!(block.stmts.len(), 2);
+
+    // Last statement without `;`
+    let mut with_tail = builder();
+    with_tail.add(quote! { a + b });
+    assert_eq!(with_tail.finalize_block().unwrap().stmts.len(), 3);
+
+    let mut broken = builder();
+    broken.add(quote! { let c = ; });
+    assert!(broken.finalize_block().is_err());
+}
+
+#[test]
+fn prefix_each_three_functions() {
+    let mut builder = TokensBuilder::default();
+    builder.add(quote! { fn a() {} });
+    builder.add(quote! { fn b() {} fn c() {} });
+    builder.prefix_each(quote! { #[allow(dead_code)] });
+
+    let file: syn::File = syn::parse2(builder.finalize()).unwrap();
+    assert_eq!(file.items.len(), 3);
+    for item in file.items {
+        let syn::Item::Fn(item_fn) = item else {
+            panic!("expected fn item");
+        };
+        assert_eq!(item_fn.attrs.len(), 1);
+        assert!(item_fn.attrs[0].path().is_ident("allow"));
+    }
+}
+
+#[test]
+fn prefix_each_empty_and_non_items() {
+    let mut empty = TokensBuilder::default();
+    empty.prefix_each(quote! { #[allow(dead_code)] });
+    assert!(empty.finalize().is_empty());
+
+    // Statements are not items, tokens are left as they were
+    let mut statements = builder();
+    statements.prefix_each(quote! { #[allow(dead_code)] });
+    assert_eq!(
+        statements.finalize().to_string(),
+        builder().finalize().to_string()
+    );
+}
+
+#[test]
+fn dedup_items_identical_uses() {
+    let mut builder = TokensBuilder::default();
+    builder.add(quote! { use std::collections::HashMap; });
+    builder.add(quote! { fn a() {} });
+    // Same item, different spacing
+    builder.add("use std :: collections :: HashMap ;".parse().unwrap());
+    builder.add(quote! { pub use std::collections::HashMap; });
+    builder.dedup_items();
+
+    let file: syn::File = syn::parse2(builder.finalize()).unwrap();
+    assert_eq!(file.items.len(), 3);
+    assert!(matches!(file.items[0], syn::Item::Use(_)));
+    assert!(matches!(file.items[1], syn::Item::Fn(_)));
+    // Visibility differs, not a duplicate
+    assert!(matches!(
+        &file.items[2],
+        syn::Item::Use(item_use) if matches!(item_use.vis, syn::Visibility::Public(_))
+    ));
+}
+
+#[test]
+fn dedup_items_non_items_unchanged() {
+    let mut statements = builder();
+    statements.add(quote! { let a = 1; });
+    statements.dedup_items();
+
+    let mut expected = builder();
+    expected.add(quote! { let a = 1; });
+    assert_eq!(
+        statements.finalize().to_string(),
+        expected.finalize().to_string()
+    );
+}
+
+#[test]
+fn checkpoint_restore_drops_later_fragments() {
+    let mut builder = TokensBuilder::default();
+    builder.add(quote! { struct First; });
+    builder.add(quote! { struct Second; });
+
+    let checkpoint = builder.checkpoint();
+    builder.add(quote! { struct Third; });
+    builder.restore(checkpoint);
+
+    assert_eq!(
+        builder.finalize().to_string(),
+        quote! { struct First; struct Second; }.to_string()
+    );
+}
+
+#[test]
+fn checkpoint_restore_after_rewrite() {
+    let mut builder = TokensBuilder::default();
+    builder.add(quote! { let a = 1; });
+
+    let checkpoint = builder.checkpoint();
+    builder.add(quote! { let b = 2; });
+    builder.braced();
+    builder.restore(checkpoint.clone());
+    let restored = &builder;
+    assert_eq!(
+        quote! { #restored }.to_string(),
+        quote! { let a = 1; }.to_string()
+    );
+
+    //Checkpoint can be used multiple times
+    builder.add(quote! { a });
+    builder.restore(checkpoint);
+    assert_eq!(
+        builder.finalize().to_string(),
+        quote! { let a = 1; }.to_string()
+    );
+}
+
+#[test]
+fn add_all_chained_with_braced() {
+    let mut builder = TokensBuilder::default();
+    builder
+        .add_all(vec![quote! { let a = 1; }, quote! { let b = a; }])
+        .braced();
+
+    assert_eq!(
+        builder.finalize().to_string(),
+        quote! { { let a = 1; let b = a; } }.to_string()
+    );
+
+    // Any `ToTokens` items, empty iterator adds nothing
+    let idents: Vec<syn::Ident> = vec![syn::parse_quote!(x), syn::parse_quote!(y)];
+    let mut builder = TokensBuilder::default();
+    builder.add_all(&idents).add_all(Vec::<syn::Ident>::new());
+    assert_eq!(builder.finalize().to_string(), "x y");
+}
+
+#[test]
+fn add_cfg_single_item_and_statement() {
+    let mut builder = TokensBuilder::default();
+    builder.add_cfg(quote! { feature = "x" }, quote! { fn foo() {} });
+    assert_eq!(
+        builder.finalize().to_string(),
+        quote! { #[cfg(feature = "x")] fn foo() {} }.to_string()
+    );
+
+    // Not an item, gated once
+    let mut builder = TokensBuilder::default();
+    builder
+        .add_cfg(quote! { debug_assertions }, quote! { log(value); })
+        .add(quote! { value });
+    assert_eq!(
+        builder.finalize().to_string(),
+        quote! { #[cfg(debug_assertions)] log(value); value }.to_string()
+    );
+}
+
+#[test]
+fn map_tokens_uppercases_ident() {
+    fn uppercase_idents(tokens: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        tokens
+            .into_iter()
+            .map(|token| match token {
+                proc_macro2::TokenTree::Ident(ident) => {
+                    proc_macro2::Ident::new(&ident.to_string().to_uppercase(), ident.span()).into()
+                }
+                proc_macro2::TokenTree::Group(group) => {
+                    proc_macro2::Group::new(group.delimiter(), uppercase_idents(group.stream()))
+                        .into()
+                }
+                other => other,
+            })
+            .collect()
+    }
+
+    let mut builder = TokensBuilder::default();
+    builder
+        .add_all([quote! { const value: u8 = 1; }])
+        .map_tokens(uppercase_idents)
+        .braced();
+    assert_eq!(
+        builder.finalize().to_string(),
+        quote! { { CONST VALUE: U8 = 1; } }.to_string()
+    );
+
+    // Empty builder passes an empty stream
+    let mut builder = TokensBuilder::default();
+    builder.map_tokens(|tokens| {
+        assert!(tokens.is_empty());
+        quote! { x }
+    });
+    assert_eq!(builder.finalize().to_string(), "x");
+}
+
+#[test]
+fn comment_marker_in_items_and_block() {
+    // Between items
+    let mut builder = TokensBuilder::default();
+    builder
+        .comment("first section")
+        .add_all([quote! { struct A; }])
+        .comment("second section")
+        .add_all([quote! { struct B; }]);
+    let tokens = builder.finalize();
+    assert!(tokens.to_string().contains("\"first section\""));
+    assert!(tokens.to_string().contains("\"second section\""));
+    let file: syn::File = syn::parse2(tokens).expect("markers between items should parse");
+    assert_eq!(file.items.len(), 4);
+
+    // Between statements, followed by a trailing expression
+    let mut builder = TokensBuilder::default();
+    builder.add(quote! { let x = 1; });
+    builder.comment("result");
+    builder.add(quote! { x + 1 });
+    let block = builder
+        .finalize_block()
+        .expect("marker between statements should parse");
+    assert_eq!(block.stmts.len(), 3);
+}
+
+// Markers expand to valid code, both at module level and inside of a function body
+const _: &str = "module level marker";
+
+#[test]
+fn comment_marker_compiles() {
+    const _: &str = "first marker";
+    const _: &str = "second marker";
+    let mut builder = TokensBuilder::default();
+    builder.comment("module level marker");
+    assert_eq!(
+        builder.finalize().to_string(),
+        quote! { const _: &str = "module level marker"; }.to_string()
+    );
+}
+
+#[test]
+fn split_items_two_functions() {
+    let chunks = split_items(quote! {
+        fn first() -> u8 { 1 }
+        #[inline]
+        pub fn second() {}
+    });
+
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(
+        chunks[0].to_string(),
+        quote! { fn first() -> u8 { 1 } }.to_string()
+    );
+    assert_eq!(
+        chunks[1].to_string(),
+        quote! { #[inline] pub fn second() {} }.to_string()
+    );
+}
+
+#[test]
+fn split_items_trailing_and_non_item_tokens() {
+    assert!(split_items(quote! {}).is_empty());
+
+    // No leading item, everything is one chunk
+    let statements = quote! { let a = 1; a + 1 };
+    let chunks = split_items(statements.clone());
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].to_string(), statements.to_string());
+
+    // Trailing tokens after items are kept together, even if they contain more items
+    let chunks = split_items(quote! { struct A; let a = A; struct B; });
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[0].to_string(), quote! { struct A; }.to_string());
+    assert_eq!(
+        chunks[1].to_string(),
+        quote! { let a = A; struct B; }.to_string()
+    );
+}
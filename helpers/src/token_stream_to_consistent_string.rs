@@ -1,4 +1,7 @@
-use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use std::fmt::Write;
+
+use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+use quote::ToTokens;
 
 /// Converts a token stream to a consistent string representation without spaces.
 ///
@@ -31,7 +34,8 @@ use proc_macro2::{Delimiter, TokenStream, TokenTree};
 /// The function handles different token types:
 /// - **Groups**: Processes delimiters (`()`, `{}`, `[]`) and recursively processes contents
 /// - **Identifiers**: Removes leading/trailing whitespace
-/// - **Punctuation**: Removes leading/trailing whitespace  
+/// - **Punctuation**: Removes leading/trailing whitespace, so path separators are always written as `::`
+///   (`std :: collections :: HashMap` and `std::collections::HashMap` give the same string)
 /// - **Literals**: Removes leading/trailing whitespace
 ///
 /// # Use Cases
@@ -40,47 +44,184 @@ use proc_macro2::{Delimiter, TokenStream, TokenTree};
 /// - Debugging token streams with predictable output
 pub fn token_stream_to_consistent_string(tokens: TokenStream) -> String {
     let mut result_str = String::new();
+    write_consistent_string(tokens, &mut result_str, false);
+    result_str
+}
+
+/// Same as [`token_stream_to_consistent_string`], but numeric literals are written in a canonical form.
+///
+/// Digit separators (`_`) are removed and the suffix is attached directly to the digits,
+/// so `1_000u32`, `1000_u32`, `1000 u32` and `1000u32` produce the same string.
+/// Useful when comparing attribute values or expressions containing numbers written by users.
+/// Radix, letter case and form of the number are kept as they are (`0xff`, `0xFF` and `255` stay different).
+///
+/// # Arguments
+///
+/// * `tokens` - The token stream to convert to a consistent string
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", token_stream_consistent_string_canonical_numbers_example)]
+pub fn token_stream_to_consistent_string_canonical_numbers(tokens: TokenStream) -> String {
+    let mut result_str = String::new();
+    write_consistent_string(tokens, &mut result_str, true);
+    result_str
+}
 
+///Appends consistent string of `tokens` to `result_str`, groups are written into the same buffer
+///
+///Idents and punctuation are already consistent (no whitespace), they are written directly
+///without going through `to_string()` and trimming, only literals are checked
+///
+///`canonical_numbers` - remove `_` from numeric literals
+fn write_consistent_string(tokens: TokenStream, result_str: &mut String, canonical_numbers: bool) {
     for token in tokens.into_iter() {
         match token {
             TokenTree::Group(group) => {
-                match group.delimiter() {
-                    Delimiter::Parenthesis => {
-                        result_str.push('(');
-                    }
-                    Delimiter::Brace => {
-                        result_str.push('{');
-                    }
-                    Delimiter::Bracket => {
-                        result_str.push('[');
-                    }
-                    Delimiter::None => {}
-                }
-                result_str.push_str(&token_stream_to_consistent_string(group.stream()));
-                match group.delimiter() {
-                    Delimiter::Parenthesis => {
-                        result_str.push(')');
-                    }
-                    Delimiter::Brace => {
-                        result_str.push('}');
-                    }
-                    Delimiter::Bracket => {
-                        result_str.push(']');
-                    }
-                    Delimiter::None => {}
-                }
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => (Some('('), Some(')')),
+                    Delimiter::Brace => (Some('{'), Some('}')),
+                    Delimiter::Bracket => (Some('['), Some(']')),
+                    Delimiter::None => (None, None),
+                };
+                result_str.extend(open);
+                write_consistent_string(group.stream(), result_str, canonical_numbers);
+                result_str.extend(close);
             }
             TokenTree::Ident(ident) => {
-                result_str.push_str(ident.to_string().trim_start().trim_end());
+                //Writing doesn't fail for String
+                let _ = write!(result_str, "{ident}");
             }
             TokenTree::Punct(punct) => {
-                result_str.push_str(punct.to_string().trim_start().trim_end());
+                result_str.push(punct.as_char());
             }
             TokenTree::Literal(literal) => {
-                result_str.push_str(literal.to_string().trim_start().trim_end());
+                let literal = literal.to_string();
+                let trimmed = literal.trim();
+                //Numeric literals are the only ones starting with a digit
+                if canonical_numbers && trimmed.starts_with(|c: char| c.is_ascii_digit()) {
+                    result_str.extend(trimmed.chars().filter(|c| *c != '_'));
+                } else {
+                    result_str.push_str(trimmed);
+                }
             }
         }
     }
+}
+
+/// Same as [`token_stream_to_consistent_string`], but leading outer attributes are sorted first.
+///
+/// `#[a] #[b] struct X;` and `#[b] #[a] struct X;` produce the same string, useful when
+/// attribute order shouldn't matter in comparisons. Only top-level attributes at the start
+/// of the token stream are sorted, attributes inside (e.g. on fields) keep their order.
+///
+/// # Arguments
+///
+/// * `tokens` - The token stream (usually an item) to convert to a consistent string
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", token_stream_consistent_string_sorted_attrs_example)]
+pub fn token_stream_to_consistent_string_sorted_attrs(tokens: TokenStream) -> String {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+
+    //`#` followed by `[...]`
+    let mut attributes = Vec::new();
+    let mut rest_start = 0;
+    while let [TokenTree::Punct(pound), TokenTree::Group(group), ..] = &tokens[rest_start..] {
+        if pound.as_char() != '#' || group.delimiter() != Delimiter::Bracket {
+            break;
+        }
+        attributes.push(token_stream_to_consistent_string(
+            tokens[rest_start..rest_start + 2].iter().cloned().collect(),
+        ));
+        rest_start += 2;
+    }
+    attributes.sort();
 
+    let mut result_str = attributes.concat();
+    result_str.push_str(&token_stream_to_consistent_string(
+        tokens[rest_start..].iter().cloned().collect(),
+    ));
     result_str
 }
+
+/// Compares two token streams like [`token_stream_to_consistent_string`] does and describes the difference.
+///
+/// Returns `None` when both are equal. Otherwise returns a readable diff with tokens separated by spaces,
+/// where the differing part is wrapped in `⟦ ⟧` (`⟦⟧` means that nothing is there on that side).
+///
+/// # Arguments
+///
+/// * `a` - Left side of the comparison (e.g. generated code)
+/// * `b` - Right side of the comparison (e.g. expected code)
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", consistent_string_diff_example)]
+pub fn consistent_string_diff(a: &impl ToTokens, b: &impl ToTokens) -> Option<String> {
+    let a = a.to_token_stream();
+    let b = b.to_token_stream();
+
+    if token_stream_to_consistent_string(a.clone()) == token_stream_to_consistent_string(b.clone())
+    {
+        return None;
+    }
+
+    let a = consistent_tokens(a);
+    let b = consistent_tokens(b);
+
+    let prefix = a.iter().zip(b.iter()).take_while(|(a, b)| a == b).count();
+    let max_suffix = a.len().min(b.len()) - prefix;
+    let suffix = a
+        .iter()
+        .rev()
+        .zip(b.iter().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let describe = |tokens: &[String]| {
+        let before = tokens[..prefix].join(" ");
+        let differing = tokens[prefix..tokens.len() - suffix].join(" ");
+        let after = tokens[tokens.len() - suffix..].join(" ");
+        format!("{before} ⟦{differing}⟧ {after}").trim().to_string()
+    };
+
+    Some(format!(
+        "token streams differ at token {prefix}\r\nleft:  {}\r\nright: {}",
+        describe(&a),
+        describe(&b)
+    ))
+}
+
+///Every token (and group delimiter) as a separate consistent string, joint punctuation (`->`) stays together
+fn consistent_tokens(tokens: TokenStream) -> Vec<String> {
+    let mut result: Vec<String> = Vec::new();
+    let mut joint = false;
+    for token in tokens.into_iter() {
+        let last_joint = std::mem::take(&mut joint);
+        match token {
+            TokenTree::Punct(punct) => {
+                joint = punct.spacing() == Spacing::Joint;
+                match result.last_mut() {
+                    Some(last) if last_joint => last.push(punct.as_char()),
+                    _ => result.push(punct.as_char().to_string()),
+                }
+            }
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => (Some("("), Some(")")),
+                    Delimiter::Brace => (Some("{"), Some("}")),
+                    Delimiter::Bracket => (Some("["), Some("]")),
+                    Delimiter::None => (None, None),
+                };
+                result.extend(open.map(str::to_string));
+                result.extend(consistent_tokens(group.stream()));
+                result.extend(close.map(str::to_string));
+            }
+            token => result.push(token_stream_to_consistent_string(token.into())),
+        }
+    }
+    result
+}
@@ -1,4 +1,5 @@
 use proc_macro2::TokenStream;
+use quote::ToTokens;
 
 /// A builder for accumulating and formatting token streams in procedural macros.
 ///
@@ -31,6 +32,120 @@ impl TokensBuilder {
         self.result.extend(item);
     }
 
+    /// Adds tokens to the accumulated result only if `cond` is `true`.
+    ///
+    /// Returns `&mut Self`, so multiple calls can be chained together.
+    /// Use [`add_if_with`](Self::add_if_with) when creating the tokens is expensive.
+    ///
+    /// # Arguments
+    ///
+    /// * `cond` - Whether the tokens should be added
+    /// * `tokens` - The tokens to add to the result
+    ///
+    /// # Examples
+    ///
+    #[doc = docify::embed!("src/examples.rs", tokens_builder_add_if_example)]
+    pub fn add_if(&mut self, cond: bool, tokens: impl ToTokens) -> &mut Self {
+        if cond {
+            tokens.to_tokens(&mut self.result);
+        }
+        self
+    }
+
+    /// Lazy version of [`add_if`](Self::add_if), `tokens` closure is called only if `cond` is `true`.
+    ///
+    /// # Arguments
+    ///
+    /// * `cond` - Whether the tokens should be created and added
+    /// * `tokens` - Closure creating the tokens to add to the result
+    ///
+    /// # Examples
+    ///
+    #[doc = docify::embed!("src/examples.rs", tokens_builder_add_if_with_example)]
+    pub fn add_if_with(&mut self, cond: bool, tokens: impl FnOnce() -> TokenStream) -> &mut Self {
+        if cond {
+            self.result.extend(tokens());
+        }
+        self
+    }
+
+    /// Adds every item of `iter` to the accumulated result, in order.
+    ///
+    /// Returns `&mut Self`, so it can be chained without collecting the items first.
+    ///
+    /// # Arguments
+    ///
+    /// * `iter` - Items (token streams, idents, parsed syn types, ...) to add to the result
+    ///
+    /// # Examples
+    ///
+    #[doc = docify::embed!("src/examples.rs", tokens_builder_add_all_example)]
+    pub fn add_all(&mut self, iter: impl IntoIterator<Item = impl ToTokens>) -> &mut Self {
+        for item in iter {
+            item.to_tokens(&mut self.result);
+        }
+        self
+    }
+
+    /// Adds tokens gated behind `#[cfg(#cfg)]` to the accumulated result.
+    ///
+    /// An attribute applies only to the next item, so when `tokens` are a sequence of items,
+    /// every one of them gets its own `#[cfg(...)]`. Other tokens (a single statement, expression, ...)
+    /// are added with one `#[cfg(...)]` in front of them. To gate a group as a whole, wrap it
+    /// in a module first (`mod gated { ... }`).
+    ///
+    /// Returns `&mut Self`, so multiple calls can be chained together.
+    ///
+    /// # Arguments
+    ///
+    /// * `cfg` - Condition inside of `#[cfg(...)]`, for example `feature = "x"`
+    /// * `tokens` - The tokens to gate and add to the result
+    ///
+    /// # Examples
+    ///
+    #[doc = docify::embed!("src/examples.rs", tokens_builder_add_cfg_example)]
+    pub fn add_cfg(&mut self, cfg: impl ToTokens, tokens: impl ToTokens) -> &mut Self {
+        let tokens = tokens.into_token_stream();
+        let attr = quote::quote! { #[cfg(#cfg)] };
+        match only_items(tokens.clone()) {
+            Some(items) if !items.is_empty() => {
+                for item in items {
+                    attr.to_tokens(&mut self.result);
+                    self.result.extend(item);
+                }
+            }
+            _ => {
+                self.result.extend(attr);
+                self.result.extend(tokens);
+            }
+        }
+        self
+    }
+
+    /// Adds a marker carrying `text`, for making generated code easier to read while debugging.
+    ///
+    /// `// comments` can't be emitted, they are dropped by the tokenizer. Instead, the marker is
+    /// an unnamed constant: `const _: &str = "text";`. It's a valid item and a valid statement,
+    /// so it can be placed between items (in a module or file) and between statements (in a block),
+    /// but not inside of expressions. Unnamed constants don't trigger `unused` warnings
+    /// and don't introduce any names, multiple markers can be placed in the same scope.
+    ///
+    /// Returns `&mut Self`, so multiple calls can be chained together.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Text of the marker, visible in the generated code (for example in `cargo expand`)
+    ///
+    /// # Examples
+    ///
+    #[doc = docify::embed!("src/examples.rs", tokens_builder_comment_example)]
+    pub fn comment(&mut self, text: &str) -> &mut Self {
+        self.result.extend(quote::quote! {
+            const _: &str = #text;
+        });
+        self
+    }
+
     /// Wraps the accumulated result with a pair of braces, creating a block expression.
     ///
     /// This is useful when you want to group multiple statements or expressions
@@ -49,10 +164,111 @@ impl TokensBuilder {
         });
     }
 
+    /// Replaces the accumulated result with the output of `f`.
+    ///
+    /// Useful for a final transform before finalizing, for example renaming identifiers
+    /// or running the tokens through a `syn` visitor.
+    ///
+    /// Returns `&mut Self`, so multiple calls can be chained together.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - Function receiving the tokens accumulated so far and returning the new ones
+    ///
+    /// # Examples
+    ///
+    #[doc = docify::embed!("src/examples.rs", tokens_builder_map_tokens_example)]
+    pub fn map_tokens(&mut self, f: impl FnOnce(TokenStream) -> TokenStream) -> &mut Self {
+        self.result = f(std::mem::take(&mut self.result));
+        self
+    }
+
+    /// Inserts `prefix` before every top-level item accumulated so far.
+    ///
+    /// Useful for adding attributes like `#[allow(dead_code)]` to every generated item.
+    /// An empty builder is left empty. If the accumulated tokens are not a sequence of items
+    /// (for example statements or expressions), they are left unchanged.
+    ///
+    /// Returns `&mut Self`, so multiple calls can be chained together.
+    ///
+    /// # Examples
+    ///
+    #[doc = docify::embed!("src/examples.rs", tokens_builder_prefix_each_example)]
+    pub fn prefix_each(&mut self, prefix: impl ToTokens + Clone) -> &mut Self {
+        let Some(items) = self.items() else {
+            return self;
+        };
+
+        let mut result = TokenStream::new();
+        for item in items {
+            prefix.clone().to_tokens(&mut result);
+            result.extend(item);
+        }
+        self.result = result;
+        self
+    }
+
+    /// Removes duplicate top-level items accumulated so far, keeping the first occurrence.
+    ///
+    /// Items are compared with [`token_stream_to_consistent_string`](crate::token_stream_to_consistent_string),
+    /// so differences in spacing don't matter, while any other difference (attributes, visibility, ...) does.
+    /// Useful when generated code from multiple sources emits the same `use` or `impl` twice.
+    /// Order of the remaining items is preserved. If the accumulated tokens are not a sequence of items
+    /// (for example statements or expressions), they are left unchanged.
+    ///
+    /// Returns `&mut Self`, so multiple calls can be chained together.
+    ///
+    /// # Examples
+    ///
+    #[doc = docify::embed!("src/examples.rs", tokens_builder_dedup_items_example)]
+    pub fn dedup_items(&mut self) -> &mut Self {
+        let Some(items) = self.items() else {
+            return self;
+        };
+
+        let mut seen = std::collections::HashSet::new();
+        let mut result = TokenStream::new();
+        for item in items {
+            if seen.insert(crate::token_stream_to_consistent_string(item.clone())) {
+                result.extend(item);
+            }
+        }
+        self.result = result;
+        self
+    }
+
+    /// Captures the tokens accumulated so far, so they can be brought back with [`restore`](Self::restore).
+    ///
+    /// Useful for speculative generation: checkpoint, try generating one form
+    /// and roll back to generate another one if a later step fails.
+    /// The checkpoint holds a copy of the accumulated tokens, so it stays valid after
+    /// [`braced`](Self::braced), [`prefix_each`](Self::prefix_each) and similar calls.
+    ///
+    /// # Examples
+    ///
+    #[doc = docify::embed!("src/examples.rs", tokens_builder_checkpoint_example)]
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            result: self.result.clone(),
+        }
+    }
+
+    /// Replaces the accumulated tokens with the ones captured by [`checkpoint`](Self::checkpoint),
+    /// everything added since then is discarded.
+    pub fn restore(&mut self, checkpoint: Checkpoint) {
+        self.result = checkpoint.result;
+    }
+
+    ///Accumulated tokens split into top-level items, `None` if they aren't a sequence of items
+    fn items(&self) -> Option<Vec<TokenStream>> {
+        only_items(self.result.clone())
+    }
+
     /// Consumes the `TokensBuilder` and returns the final token stream.
     ///
     /// This method should be called once you've finished building your result
     /// and are ready to return it from your function procedural macro.
+    /// `builder.into()` does the same (for both `proc_macro2` and `proc_macro` token streams).
     ///
     /// # Returns
     ///
@@ -64,4 +280,119 @@ impl TokensBuilder {
     pub fn finalize(self) -> TokenStream {
         self.result
     }
+
+    /// Consumes the `TokensBuilder` and parses the result as a single item.
+    ///
+    /// Returns an error early if the generated tokens are malformed,
+    /// instead of failing at the macro call site with a cryptic error.
+    ///
+    /// # Examples
+    ///
+    #[doc = docify::embed!("src/examples.rs", tokens_builder_finalize_item_example)]
+    pub fn finalize_item(self) -> syn::Result<syn::Item> {
+        syn::parse2(self.result)
+    }
+
+    /// Same as [`finalize_item`](Self::finalize_item), but parses the result as an expression.
+    pub fn finalize_expr(self) -> syn::Result<syn::Expr> {
+        syn::parse2(self.result)
+    }
+
+    /// Same as [`finalize_item`](Self::finalize_item), but parses the result as block contents
+    /// (statements, last one can be an expression without `;`). Braces are added to the result.
+    pub fn finalize_block(self) -> syn::Result<syn::Block> {
+        let result = self.result;
+        syn::parse2(quote::quote! {
+            {
+                #result
+            }
+        })
+    }
+}
+
+/// Splits a token stream into top-level items, every item as a separate token stream.
+///
+/// Items (functions, structs, `impl` blocks, `use` declarations, ...) keep their attributes.
+/// Splitting stops at the first tokens which aren't an item (for example statements like `let x = 1;`
+/// or expressions), those trailing tokens are returned unchanged as the last chunk.
+/// So a stream without any leading item is returned as a single chunk,
+/// and an empty stream gives an empty `Vec`. Joining the chunks back gives all of the original tokens.
+///
+/// [`TokensBuilder`] methods working on separate items
+/// ([`add_cfg`](TokensBuilder::add_cfg), [`prefix_each`](TokensBuilder::prefix_each),
+/// [`dedup_items`](TokensBuilder::dedup_items)) are built on top of it.
+///
+/// # Arguments
+///
+/// * `tokens` - The token stream to split
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", split_items_example)]
+pub fn split_items(tokens: TokenStream) -> Vec<TokenStream> {
+    let (mut items, rest) = split_leading_items(tokens);
+    if !rest.is_empty() {
+        items.push(rest);
+    }
+    items
+}
+
+///`tokens` split into top-level items, `None` if they aren't a sequence of items
+fn only_items(tokens: TokenStream) -> Option<Vec<TokenStream>> {
+    let (items, rest) = split_leading_items(tokens);
+    rest.is_empty().then_some(items)
+}
+
+///Items from the start of `tokens` (every one as a separate stream) and the tokens left after them
+fn split_leading_items(tokens: TokenStream) -> (Vec<TokenStream>, TokenStream) {
+    let parser = |input: syn::parse::ParseStream| {
+        let mut items = Vec::new();
+        while !input.is_empty() {
+            //Checked on a fork, so tokens which aren't an item are left in `input`
+            let fork = input.fork();
+            let Ok(item) = fork.parse::<syn::Item>() else {
+                break;
+            };
+            syn::parse::discouraged::Speculative::advance_to(input, &fork);
+            items.push(item.into_token_stream());
+        }
+        Ok((items, input.parse::<TokenStream>()?))
+    };
+    //Parser never fails, tokens which aren't an item end up in the rest
+    syn::parse::Parser::parse2(parser, tokens.clone()).unwrap_or_else(|_| (Vec::new(), tokens))
+}
+
+/// State of a [`TokensBuilder`] captured with [`TokensBuilder::checkpoint`]
+#[derive(Debug, Clone)]
+pub struct Checkpoint {
+    result: TokenStream,
+}
+
+/// Same as [`TokensBuilder::finalize`]
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", tokens_builder_into_example)]
+impl From<TokensBuilder> for TokenStream {
+    fn from(builder: TokensBuilder) -> Self {
+        builder.finalize()
+    }
+}
+
+/// Same as [`TokensBuilder::finalize`] followed by `.into()`, can be returned directly from procedural macros
+impl From<TokensBuilder> for proc_macro::TokenStream {
+    fn from(builder: TokensBuilder) -> Self {
+        builder.finalize().into()
+    }
+}
+
+/// Allows interpolating builder inside of `quote!` without finalizing it
+///
+/// # Examples
+///
+#[doc = docify::embed!("src/examples.rs", tokens_builder_interpolation_example)]
+impl ToTokens for &TokensBuilder {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.result.to_tokens(tokens);
+    }
 }
@@ -272,3 +272,91 @@ pub fn test_whitespace_matching(_item: TokenStream) -> anyhow::Result<TokenStrea
     // All tests passed - whitespace doesn't matter
     Ok(quote! {}.into())
 }
+
+///Expects exactly three items, returns them unchanged
+#[proc_macro]
+#[anyhow_result::anyhow_result]
+pub fn parse_items_test(item: TokenStream) -> anyhow::Result<TokenStream> {
+    let items = helpers::parse_macro_input!(item as [syn::Item]);
+
+    if items.len() != 3 {
+        let msg = format!("Expected 3 items, parsed {}", items.len());
+        return Ok(quote! {
+            compile_error!(#msg);
+        }
+        .into());
+    }
+
+    Ok(quote! { #(#items)* }.into())
+}
+
+///Parses items with `combine_errors`, returns produced errors as a string literal (empty if there are none)
+#[proc_macro]
+pub fn parse_items_errors_test(item: TokenStream) -> TokenStream {
+    fn parse_items(item: TokenStream) -> anyhow::Result<TokenStream> {
+        let _items = helpers::parse_macro_input!(item as [syn::Item]; combine_errors);
+        Ok(TokenStream::new())
+    }
+
+    let errors = match parse_items(item) {
+        Ok(errors) => errors.to_string(),
+        Err(err) => panic!("{err:?}"),
+    };
+    quote! { #errors }.into()
+}
+
+///Expects exactly three statements, returns them as a block expression
+#[proc_macro]
+#[anyhow_result::anyhow_result]
+pub fn parse_stmts_test(item: TokenStream) -> anyhow::Result<TokenStream> {
+    let stmts = helpers::parse_macro_input!(item as [syn::Stmt]);
+
+    if stmts.len() != 3 {
+        let msg = format!("Expected 3 statements, parsed {}", stmts.len());
+        return Ok(quote! {
+            compile_error!(#msg);
+        }
+        .into());
+    }
+
+    Ok(quote! { { #(#stmts)* } }.into())
+}
+
+///Parses a leading identifier, returns `(stringify!(ident), rest)` with the remaining tokens unchanged
+#[proc_macro]
+#[anyhow_result::anyhow_result]
+pub fn parse_rest_test(item: TokenStream) -> anyhow::Result<TokenStream> {
+    let (ident, rest) = helpers::parse_macro_input!(item as syn::Ident, rest);
+
+    Ok(quote! { (stringify!(#ident), #rest) }.into())
+}
+
+///Returns the expression unchanged, with a warning attached by `expr_warning_wrap`
+#[proc_macro]
+#[anyhow_result::anyhow_result]
+pub fn expr_warning_test(item: TokenStream) -> anyhow::Result<TokenStream> {
+    let mut expr = helpers::parse_macro_input!(item as syn::Expr);
+
+    let mut warnings = vec!["expr_warning_test warning".to_string()];
+    helpers::expr_warning_wrap(&mut expr, &mut warnings);
+
+    Ok(quote! { #expr }.into())
+}
+
+///Adds `fn type_name() -> &'static str` to the struct, the struct itself is kept by `keep_item`
+#[proc_macro_attribute]
+#[anyhow_result::anyhow_result(keep_item)]
+pub fn keep_item_test(_attr: TokenStream, item: TokenStream) -> anyhow::Result<TokenStream> {
+    let parsed: ItemStruct = syn::parse(item).context("Expected a struct")?;
+
+    let name = &parsed.ident;
+    let name_str = name.to_string();
+    Ok(quote! {
+        impl #name {
+            pub fn type_name() -> &'static str {
+                #name_str
+            }
+        }
+    }
+    .into())
+}